@@ -0,0 +1,6382 @@
+#![deny(rust_2018_idioms)]
+
+mod util;
+
+use std::{env, fmt, path::Path};
+use std::path::PathBuf;
+
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+use crate::util::{configure_path, meson_array, meson_machine, qnx_tool_prefix};
+#[cfg(any(unix, target_env = "gnu", feature = "cmake"))]
+use crate::util::{defined_symbols, parse_simd_level, symbol_redefinition, X86_SIMD_LEVELS};
+#[cfg(any(unix, target_env = "gnu"))]
+use crate::util::is_multilib_target;
+#[cfg(all(windows, target_env = "msvc", not(feature = "cmake")))]
+use crate::util::parse_checksums;
+#[cfg(feature = "bindgen")]
+use crate::util::{clang_target, prefix_link_names, wrap_extern_blocks};
+use crate::util::{
+    cargo_cfg, coff_machine, copy_directory, dll_exports, elf_machine, elf_word_size,
+    expected_coff_machine, expected_elf_machine, expected_macho_cpu_type, failure_hint,
+    gnu_host_triple, is_at_least_version, is_cross_compiled, is_import_library, is_zig_compiler,
+    library_file_names, macho_cpu_type, make_variable, ogg_source, opus_env, opus_env_name,
+    opus_env_var, opus_variable_names, parse_bool, parse_link_libs, parse_linkage, parse_lto,
+    parse_pc_file, prefixed_path, rustc_linking_word, rustflags_target_cpu, stable_hash,
+    CompanionSource,
+};
+
+use std::process::{Command, Output};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// The version of the Opus bundled as source and prebuilt library-files.
+const BUNDLED_OPUS_VERSION: &str = "1.3";
+
+/// The prefix of every symbol of the Opus built with the `prefix-symbols`-
+/// feature, matching the `link_name`s of the bindings.
+const SYMBOL_PREFIX: &str = "audiopus_";
+
+/// How many of the last lines of a failed command's standard error are shown.
+const STDERR_TAIL_LINES: usize = 20;
+
+/// A failed step of finding, building, or linking Opus.
+///
+/// `main` reports it as a single diagnostic instead of a panic's backtrace.
+#[derive(Debug)]
+struct BuildError {
+    /// The failed step, such as `make install`.
+    step: String,
+    /// What went wrong.
+    message: String,
+    /// The tail of the standard error captured from the step's command.
+    stderr: String,
+    /// How to remedy the failure, if its cause was recognised.
+    hint: Option<String>,
+}
+
+impl BuildError {
+    fn new<M: Into<String>>(step: &str, message: M) -> Self {
+        BuildError {
+            step: step.to_string(),
+            message: message.into(),
+            stderr: String::new(),
+            hint: None,
+        }
+    }
+
+    /// Attaches the steps remedying the failure.
+    fn with_hint<H: Into<String>>(mut self, hint: H) -> Self {
+        self.hint = Some(hint.into());
+
+        self
+    }
+
+    /// Attaches the last `STDERR_TAIL_LINES` lines of `stderr`.
+    fn with_stderr(mut self, stderr: &[u8]) -> Self {
+        let stderr = String::from_utf8_lossy(stderr);
+        let lines: Vec<&str> = stderr.trim_end().lines().collect();
+        let first_line = lines.len().saturating_sub(STDERR_TAIL_LINES);
+
+        self.stderr = lines[first_line..].join("\n");
+
+        self
+    }
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} failed: {}", self.step, self.message)?;
+
+        if !self.stderr.is_empty() {
+            write!(f, "\n\nLast lines of its standard error:\n{}", self.stderr)?;
+        }
+
+        if let Some(ref hint) = self.hint {
+            write!(f, "\n\nHint: {}", hint)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The file logging the output of all build steps, see `run_step`.
+fn build_log_path() -> PathBuf {
+    PathBuf::from(env::var("OUT_DIR").expect("Environment variable `OUT_DIR` is missing."))
+        .join("opus-build.log")
+}
+
+/// Whether the output of all build steps is printed, as requested via
+/// `LIBOPUS_BUILD_VERBOSE` or `OPUS_BUILD_VERBOSE`.
+fn is_build_verbose() -> Result<bool, BuildError> {
+    match opus_env_var("BUILD_VERBOSE") {
+        Some((variable, value)) => parse_bool(&variable, &value)
+            .map_err(|error| BuildError::new("Selecting the verbosity", error)),
+        None => Ok(false),
+    }
+}
+
+/// Appends the `output` of the build `step` to the build log, printing it as
+/// well if `is_printing`.
+fn log_step(step: &str, output: &Output, is_printing: bool) {
+    use std::io::Write;
+
+    let entry = format!(
+        "==> {}\n{}{}\n",
+        step,
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    if is_printing {
+        eprint!("{}", entry);
+    }
+
+    // A missing log only hampers debugging, hence it does not fail the build.
+    let _ = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(build_log_path())
+        .and_then(|mut log| log.write_all(entry.as_bytes()));
+}
+
+/// Runs the `command` of the build `step`, capturing its output into the
+/// build log instead of flooding Cargo's output.
+///
+/// The output is printed if the step fails or `is_build_verbose`.
+///
+/// Fails if the command cannot be started or exits unsuccessfully, hinting at
+/// the remedy if the cause is recognised, see `failure_hint`.
+fn run_step(step: &str, command: &mut Command) -> Result<Output, BuildError> {
+    let is_verbose = is_build_verbose()?;
+
+    let output = command.output().map_err(|error| {
+        let build_error = BuildError::new(step, format!("Could not run the command: {}", error));
+
+        if error.kind() == std::io::ErrorKind::NotFound {
+            build_error.with_hint(
+                "The program could not be found. Make sure it is installed and in \
+                 `PATH` or point its variable, such as `MAKE` or `SH`, to it.",
+            )
+        } else {
+            build_error
+        }
+    })?;
+
+    log_step(step, &output, is_verbose || !output.status.success());
+
+    if output.status.success() {
+        return Ok(output);
+    }
+
+    let build_error = BuildError::new(
+        step,
+        format!(
+            "The command exited with {}, the output of all steps is logged in {}.",
+            output.status,
+            build_log_path().display()
+        ),
+    )
+    .with_stderr(&output.stderr);
+    let hint = failure_hint(&String::from_utf8_lossy(&output.stderr))
+        .or_else(|| failure_hint(&String::from_utf8_lossy(&output.stdout)));
+
+    Err(match hint {
+        Some(hint) => build_error.with_hint(hint),
+        None => build_error,
+    })
+}
+
+/// Canonicalises the path of the bundled Opus sources.
+///
+/// Fails if the `opus`-submodule has not been initialised, as happens when
+/// cloning without `--recurse-submodules`.
+///
+/// An external source tree, such as a patched fork or another release, can be
+/// built instead via `LIBOPUS_SOURCE_DIR` or `OPUS_SOURCE_DIR`.
+fn opus_source_path() -> Result<PathBuf, BuildError> {
+    const STEP: &str = "Locating the Opus sources";
+
+    let external_source_path = opus_env("SOURCE_DIR").map(PathBuf::from);
+    let source_path = match external_source_path.clone() {
+        Some(external_source_path) => external_source_path,
+        #[cfg(feature = "download-source")]
+        None => download_opus_source()?,
+        #[cfg(not(feature = "download-source"))]
+        None => PathBuf::from("opus"),
+    };
+    let build_files: &[&str] = if is_building_with_cc() {
+        &["opus_sources.mk"]
+    } else if cfg!(feature = "cmake") {
+        &["CMakeLists.txt"]
+    } else if cfg!(feature = "meson") {
+        &["meson.build"]
+    } else {
+        &["configure", "autogen.sh"]
+    };
+
+    if !build_files
+        .iter()
+        .any(|build_file| source_path.join(build_file).is_file())
+    {
+        let build_file = build_files[build_files.len() - 1];
+
+        return Err(match external_source_path {
+            Some(external_source_path) => BuildError::new(
+                STEP,
+                format!(
+                    "`OPUS_SOURCE_DIR` does not contain Opus' `{}`: {}",
+                    build_file,
+                    external_source_path.display()
+                ),
+            ),
+            None => BuildError::new(
+                STEP,
+                "The bundled Opus sources are missing, the `opus`-submodule is \
+                 likely uninitialised. Run `git submodule update --init --recursive`, \
+                 enable the `download-source`-feature, or point `OPUS_LIB_DIR` to a \
+                 pre-installed Opus.",
+            ),
+        });
+    }
+
+    source_path.canonicalize().map_err(|error| {
+        BuildError::new(
+            STEP,
+            format!("Could not canonicalise {}: {}", source_path.display(), error),
+        )
+    })
+}
+
+/// The version of the Opus built from source, read from the `package_version`
+/// of an external source tree, which release tarballs contain.
+///
+/// Returns `None` for an external source tree lacking it, such as a plain Git
+/// checkout.
+fn opus_source_version() -> Option<String> {
+    let source_path = match opus_env("SOURCE_DIR") {
+        Some(source_path) => PathBuf::from(source_path),
+        None if cfg!(feature = "download-source") => {
+            return Some(downloaded_opus_release().0.to_string())
+        }
+        None => return Some(BUNDLED_OPUS_VERSION.to_string()),
+    };
+
+    std::fs::read_to_string(source_path.join("package_version"))
+        .ok()?
+        .lines()
+        .filter_map(|line| line.trim().splitn(2, '=').nth(1))
+        .map(|version| version.trim_matches('"').to_string())
+        .next()
+}
+
+/// The Opus release downloaded with the `download-source`-feature and the
+/// SHA-256 of its tarball, selected by the newest `v1_*`-feature.
+fn downloaded_opus_release() -> (&'static str, &'static str) {
+    if cfg!(feature = "v1_5") {
+        (
+            "1.5.2",
+            "65c1d2f78b9f2fb20082c38cbe47c951ad5839345876e46941612ee87f9a7ce1",
+        )
+    } else if cfg!(feature = "v1_4") {
+        (
+            "1.4",
+            "c9b32b4253be5ae63d1ff16eea06b94b5f0f2951b7a02aceef58e3a3ce49c51f",
+        )
+    } else {
+        (
+            "1.3.1",
+            "65b58e1e25b2a114157014736a3d9dfeaad8d41be1c8179866f144a2fb44ff9d",
+        )
+    }
+}
+
+/// The oldest Opus providing the bindings selected by the `v1_*`- and
+/// `projection`-features, `None` if none is enabled.
+fn required_opus_version() -> Option<&'static str> {
+    if cfg!(feature = "v1_5") {
+        Some("1.5")
+    } else if cfg!(feature = "v1_4") {
+        Some("1.4")
+    } else if cfg!(feature = "v1_3_1") {
+        Some("1.3.1")
+    } else if cfg!(feature = "projection") {
+        Some("1.3")
+    } else {
+        None
+    }
+}
+
+/// The oldest Opus to link, read from `LIBOPUS_MIN_VERSION` or
+/// `OPUS_MIN_VERSION`, defaulting to the version the `v1_*`-features select.
+fn minimum_opus_version() -> Option<String> {
+    opus_env("MIN_VERSION").or_else(|| required_opus_version().map(str::to_string))
+}
+
+/// Downloads the release tarball of `downloaded_opus_release` via `curl`,
+/// verifies its SHA-256, and extracts it via `tar` into `OUT_DIR`, returning
+/// the extracted sources.
+///
+/// An already extracted tarball is reused, as it was verified before.
+#[cfg(feature = "download-source")]
+fn download_opus_source() -> Result<PathBuf, BuildError> {
+    use sha2::{Digest, Sha256};
+
+    const STEP: &str = "Downloading the Opus sources";
+
+    let (version, expected_checksum) = downloaded_opus_release();
+    let out_directory =
+        PathBuf::from(env::var("OUT_DIR").expect("Environment variable `OUT_DIR` is missing."));
+    let name = format!("opus-{}", version);
+    let source_path = out_directory.join(&name);
+
+    if source_path.join("package_version").is_file() {
+        println!("cargo:info=Found the downloaded Opus {}.", version);
+
+        return Ok(source_path);
+    }
+
+    let url = format!("https://downloads.xiph.org/releases/opus/{}.tar.gz", name);
+    let archive_path = out_directory.join(format!("{}.tar.gz", name));
+
+    println!("cargo:info=Downloading {}.", url);
+
+    run_step(
+        STEP,
+        Command::new("curl")
+            .arg("--fail")
+            .arg("--silent")
+            .arg("--show-error")
+            .arg("--location")
+            .arg("--output")
+            .arg(&archive_path)
+            .arg(&url),
+    )?;
+
+    let archive = std::fs::read(&archive_path).map_err(|error| {
+        BuildError::new(
+            STEP,
+            format!("Could not read {}: {}", archive_path.display(), error),
+        )
+    })?;
+    let checksum = format!("{:x}", Sha256::digest(&archive));
+
+    if checksum != expected_checksum {
+        let _ = std::fs::remove_file(&archive_path);
+
+        return Err(BuildError::new(
+            STEP,
+            format!(
+                "The SHA-256 of {} is {}, expected {}.",
+                url, checksum, expected_checksum
+            ),
+        ));
+    }
+
+    run_step(
+        "Extracting the Opus sources",
+        Command::new("tar")
+            .arg("-xzf")
+            .arg(&archive_path)
+            .arg("-C")
+            .arg(&out_directory),
+    )?;
+
+    Ok(source_path)
+}
+
+/// The `make` used unless `MAKE` is set: `gmake` on BSD, illumos, and Solaris
+/// hosts, whose own `make` cannot run the Makefiles generated by `configure`.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+fn default_make_program() -> &'static str {
+    if cfg!(any(
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "illumos",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "solaris"
+    )) {
+        "gmake"
+    } else {
+        "make"
+    }
+}
+
+/// Returns the program named by the environment variable `variable`, such as
+/// `gmake` for `MAKE`, defaulting to `default`.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+fn program(variable: &str, default: &str) -> String {
+    let program = env::var(variable).unwrap_or_else(|_| default.to_string());
+
+    println!("cargo:info=Using `{}` as `{}`.", program, variable);
+
+    program
+}
+
+/// Builds Opus on Unix or GNU.
+/// Building for Window's GNU-toolchain this way requires MSYS2, outside of it
+/// Opus is built via the `cc`-crate, see `is_building_with_cc`.
+///
+/// Building Opus consists of four steps:
+/// 1. Run `autogen.sh`, unless the sources ship a generated `configure`.
+/// 2. Configure the generated file to prepare building, see `configure_opus`.
+/// 3. Building Opus.
+/// 4. Installing the built Opus in `OUT_DIR`.
+///
+/// If `LIBOPUS_SKIP_CONFIGURE` or `OPUS_SKIP_CONFIGURE` is set and `OUT_DIR`
+/// already contains a configured Opus, copying the sources and the first two
+/// steps are skipped.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+fn build_opus(build_directory: &Path, is_static: bool) -> Result<(), BuildError> {
+    let is_static_text = rustc_linking_word(is_static);
+
+    let source_path = opus_source_path()?;
+
+    println!(
+        "cargo:info=Opus source path: {:?}.",
+        &source_path.to_string_lossy()
+    );
+    println!(
+        "cargo:info=Opus will be built as {}-library.",
+        is_static_text
+    );
+
+    match universal_architectures(is_static)? {
+        Some(architectures) => build_universal_opus(&source_path, build_directory, &architectures)?,
+        None => {
+            let architecture = cargo_cfg("TARGET_ARCH");
+
+            if cfg!(feature = "meson") {
+                meson_opus(&source_path, build_directory, &architecture, is_static)?
+            } else {
+                make_opus(&source_path, build_directory, &architecture, is_static)?
+            }
+        }
+    }
+
+    prefix_symbols(&build_directory.join("lib"), is_static)?;
+
+    if !is_static {
+        verify_exports(build_directory)?;
+    }
+
+    emit_link("opus", is_static, &build_directory.join("lib"))?;
+    probe_opus("opus", is_static, &build_directory.join("lib"))?;
+    generate_bindings(&[build_directory.join("include").join("opus")])?;
+    check_constants(&[build_directory.join("include").join("opus")])?;
+    emit_pkg_config_file(build_directory)?;
+    emit_metadata(
+        is_static,
+        "opus",
+        &build_directory.join("lib"),
+        Some(&build_directory.join("include").join("opus")),
+        Some(build_directory),
+        opus_source_version().as_ref().map(String::as_str),
+    );
+
+    Ok(())
+}
+
+/// Configures, unless skipped, builds, and installs Opus for the Rust
+/// `architecture` in `build_directory`.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+fn make_opus(
+    source_path: &Path,
+    build_directory: &Path,
+    architecture: &str,
+    is_static: bool,
+) -> Result<(), BuildError> {
+    let opus_path = build_directory.join("opus");
+    let is_skipping_configure = opus_env("SKIP_CONFIGURE").is_some();
+
+    if is_skipping_configure && opus_path.join("Makefile").is_file() {
+        println!("cargo:info=Found configured Opus, skipping `autogen.sh` and `configure`.");
+    } else {
+        if is_skipping_configure {
+            println!("cargo:info=Found no configured Opus, configuring nonetheless.");
+        }
+
+        configure_opus(source_path, build_directory, architecture, is_static)?;
+    }
+
+    let make_program = program("MAKE", default_make_program());
+
+    let mut make_command = Command::new(&make_program);
+
+    if let Ok(jobs) = env::var("NUM_JOBS") {
+        make_command.arg(format!("-j{}", jobs));
+    }
+
+    run_step("`make`", make_command.current_dir(&opus_path))?;
+    run_step(
+        "`make install`",
+        Command::new(&make_program).arg("install").current_dir(&opus_path),
+    )?;
+
+    Ok(())
+}
+
+/// The architectures of a universal macOS library, requested via
+/// `LIBOPUS_UNIVERSAL_ARCHS` or `OPUS_UNIVERSAL_ARCHS` as comma-separated Rust
+/// architectures, such as `x86_64,aarch64`, the default for an empty value.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+fn universal_architectures(is_static: bool) -> Result<Option<Vec<String>>, BuildError> {
+    const STEP: &str = "Selecting the universal architectures";
+
+    let (variable, value) = match opus_env_var("UNIVERSAL_ARCHS") {
+        Some(variable) => variable,
+        None => return Ok(None),
+    };
+
+    if cargo_cfg("TARGET_OS") != "macos" || !is_static {
+        return Err(BuildError::new(
+            STEP,
+            "Universal libraries are only built for macOS and static linking.",
+        ));
+    }
+
+    let mut architectures: Vec<String> = value
+        .split(',')
+        .map(str::trim)
+        .filter(|architecture| !architecture.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    if architectures.is_empty() {
+        architectures = vec!["x86_64".to_string(), "aarch64".to_string()];
+    }
+
+    if let Some(architecture) = architectures
+        .iter()
+        .find(|architecture| !["x86_64", "aarch64"].contains(&architecture.as_str()))
+    {
+        return Err(BuildError::new(
+            STEP,
+            format!(
+                "Unsupported architecture `{}` in `{}`, expected `x86_64` or `aarch64`.",
+                architecture, variable
+            ),
+        ));
+    }
+
+    Ok(Some(architectures))
+}
+
+/// Builds a static Opus for each of the `architectures` in its own directory
+/// below `build_directory` and combines them into a universal `libopus.a`
+/// via `lipo`.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+fn build_universal_opus(
+    source_path: &Path,
+    build_directory: &Path,
+    architectures: &[String],
+) -> Result<(), BuildError> {
+    let mut slices = Vec::new();
+
+    for architecture in architectures {
+        println!(
+            "cargo:info=Building the `{}`-slice of the universal Opus.",
+            architecture
+        );
+
+        let slice_directory = build_directory.join("universal").join(architecture);
+
+        // The `-arch`-, `--host`-, and SIMD-flags follow the slice's
+        // architecture.
+        if cfg!(feature = "meson") {
+            meson_opus(source_path, &slice_directory, architecture, true)?;
+        } else {
+            make_opus(source_path, &slice_directory, architecture, true)?;
+        }
+
+        slices.push(slice_directory.join("lib").join("libopus.a"));
+    }
+
+    let lib_directory = build_directory.join("lib");
+    let first_slice_directory = build_directory.join("universal").join(&architectures[0]);
+
+    std::fs::create_dir_all(&lib_directory)
+        .and_then(|_| {
+            copy_directory(
+                &first_slice_directory.join("include"),
+                &build_directory.join("include"),
+            )
+        })
+        .map_err(|error| {
+            BuildError::new(
+                "Installing the universal Opus",
+                format!(
+                    "Could not install into {}: {}",
+                    build_directory.display(),
+                    error
+                ),
+            )
+        })?;
+
+    run_step(
+        "`lipo`",
+        Command::new("lipo")
+            .arg("-create")
+            .arg("-output")
+            .arg(lib_directory.join("libopus.a"))
+            .args(&slices),
+    )?;
+
+    Ok(())
+}
+
+/// Returns all tools missing to build Opus via autotools: `sh` and `make`,
+/// and for sources lacking a generated `configure` also `autoreconf`, which
+/// `autogen.sh` runs, `aclocal` of automake, and `libtoolize`, named
+/// `glibtoolize` on macOS.
+///
+/// Checked once before building, as `main` falls back to the `cc`-crate if
+/// any is missing.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+fn missing_autotools() -> Vec<String> {
+    let is_missing = |program: &str| Command::new(program).arg("--version").output().is_err();
+
+    let shell_program = env::var("SH").unwrap_or_else(|_| "sh".to_string());
+    let make_program = env::var("MAKE").unwrap_or_else(|_| default_make_program().to_string());
+    let mut missing = Vec::new();
+
+    // `sh -c true` as not every shell knows `--version`.
+    if Command::new(&shell_program).args(&["-c", "true"]).output().is_err() {
+        missing.push(shell_program);
+    }
+
+    if is_missing(&make_program) {
+        missing.push(make_program);
+    }
+
+    let has_configure = match opus_env("SOURCE_DIR") {
+        Some(source_path) => Path::new(&source_path).join("configure").is_file(),
+        None => cfg!(feature = "download-source") || Path::new("opus/configure").is_file(),
+    };
+
+    if !has_configure {
+        for tool in &["autoreconf", "aclocal"] {
+            if is_missing(tool) {
+                missing.push(tool.to_string());
+            }
+        }
+
+        if is_missing("libtoolize") && is_missing("glibtoolize") {
+            missing.push("libtoolize".to_string());
+        }
+    }
+
+    missing
+}
+
+/// Whether the build runs in a container of [`cross`], whose images ship the
+/// cross-compilers but not autotools.
+///
+/// [`cross`]: https://github.com/cross-rs/cross
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+fn is_in_cross_container() -> bool {
+    env::var_os("CROSS_RUNNER").is_some() || env::var_os("CROSS_TOOLCHAIN_PREFIX").is_some()
+}
+
+/// Configures the Opus sources at `source_path` to be built in
+/// `build_directory`.
+///
+/// Release tarballs ship a generated `configure`, which is run out-of-tree,
+/// leaving the sources untouched, thus they may be read-only. Sources lacking
+/// it, such as a Git checkout, are copied into `build_directory` first, as
+/// `autogen.sh` writes the generated files next to them, requiring autotools.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+fn configure_opus(
+    source_path: &Path,
+    build_directory: &Path,
+    architecture: &str,
+    is_static: bool,
+) -> Result<(), BuildError> {
+    let has_configure = source_path.join("configure").is_file();
+
+    // Automake refuses out-of-tree builds of sources configured in-tree.
+    let is_building_out_of_tree = has_configure && !source_path.join("config.status").is_file();
+
+    let opus_path = build_directory.join("opus");
+
+    let copy_result = if is_building_out_of_tree {
+        std::fs::create_dir_all(&opus_path)
+    } else {
+        copy_directory(source_path, &opus_path)
+    };
+
+    copy_result.map_err(|error| {
+        BuildError::new(
+            "Preparing the Opus build directory",
+            format!(
+                "Could not create {} from {}: {}",
+                opus_path.display(),
+                source_path.display(),
+                error
+            ),
+        )
+    })?;
+
+    let shell_program = program("SH", "sh");
+
+    if is_building_out_of_tree {
+        println!("cargo:info=Found a generated `configure`, building out-of-tree.");
+    } else if has_configure {
+        println!("cargo:info=Found an in-tree configured Opus, building a copy.");
+    } else {
+        run_step(
+            "`autogen.sh`",
+            Command::new(&shell_program).arg("autogen.sh").current_dir(&opus_path),
+        )?;
+    }
+
+    let mut command_builder = Command::new(&shell_program);
+
+    if is_building_out_of_tree {
+        command_builder.arg(configure_path(&source_path.join("configure")));
+    } else {
+        command_builder.arg("configure");
+    }
+
+    if is_static {
+        command_builder
+            .arg("--enable-static")
+            .arg("--disable-shared");
+    } else {
+        command_builder
+            .arg("--disable-static")
+            .arg("--enable-shared");
+    }
+
+    for option in feature_options() {
+        command_builder.arg(match option {
+            FeatureOption::CustomModes => "--enable-custom-modes",
+            FeatureOption::FixedPoint => "--enable-fixed-point",
+            FeatureOption::DisableFloatApi => "--disable-float-api",
+            FeatureOption::DeepPlc => "--enable-deep-plc",
+            FeatureOption::Dred => "--enable-dred",
+            FeatureOption::Osce => "--enable-osce",
+            FeatureOption::Assertions => "--enable-assertions",
+            FeatureOption::Hardening => "--enable-hardening",
+            FeatureOption::Fuzzing => "--enable-fuzzing",
+        });
+    }
+
+    let mut c_flags = profile_c_flags()?;
+
+    for flag in arm_float_c_flags() {
+        c_flags.push(' ');
+        c_flags.push_str(flag);
+    }
+
+    for flag in cpu_tuning_c_flags(architecture)? {
+        c_flags.push(' ');
+        c_flags.push_str(&flag);
+    }
+
+    let simd_options = simd_options(architecture)?;
+
+    match simd_options.is_using_intrinsics {
+        Some(true) => {
+            command_builder.arg("--enable-intrinsics");
+        }
+        Some(false) => {
+            command_builder.arg("--disable-intrinsics");
+        }
+        None => {}
+    }
+
+    match simd_options.is_detecting_cpu {
+        Some(true) => {
+            command_builder.arg("--enable-rtcd");
+        }
+        Some(false) => {
+            command_builder.arg("--disable-rtcd");
+        }
+        None => {}
+    }
+
+    if simd_options.is_checking_asm {
+        command_builder.arg("--enable-check-asm");
+    }
+
+    for flag in simd_options.flags {
+        c_flags.push(' ');
+        c_flags.push_str(flag);
+    }
+
+    let sanitizer_flags = sanitizer_c_flags();
+
+    for flag in &sanitizer_flags {
+        c_flags.push(' ');
+        c_flags.push_str(flag);
+    }
+
+    let lto_mode = lto_mode()?;
+
+    for flag in lto_c_flags(lto_mode) {
+        c_flags.push(' ');
+        c_flags.push_str(flag);
+    }
+
+    for flag in visibility_c_flags(is_static)? {
+        c_flags.push(' ');
+        c_flags.push_str(flag);
+    }
+
+    if is_uwp() {
+        c_flags.push_str(" -DWINAPI_FAMILY=WINAPI_FAMILY_APP");
+    }
+
+    if let Ok(user_c_flags) = env::var("CFLAGS") {
+        c_flags.push(' ');
+        c_flags.push_str(&user_c_flags);
+    }
+
+    let mut linker_flags = env::var("LDFLAGS").unwrap_or_default();
+
+    for flag in &sanitizer_flags {
+        linker_flags.push(' ');
+        linker_flags.push_str(flag);
+    }
+
+    for flag in lto_c_flags(lto_mode) {
+        linker_flags.push(' ');
+        linker_flags.push_str(flag);
+    }
+
+    if let Some(sysroot) = fuchsia_sysroot()? {
+        let target = env::var("TARGET").unwrap_or_default();
+
+        for flags in &mut [&mut c_flags, &mut linker_flags] {
+            flags.push_str(&format!(
+                " --target={} --sysroot={}",
+                target,
+                sysroot.display()
+            ));
+        }
+    }
+
+    if let Some(apple_architecture) = apple_architecture(architecture) {
+        println!(
+            "cargo:info=Building Opus for Apple's `{}`-architecture.",
+            apple_architecture
+        );
+
+        c_flags.push_str(" -arch ");
+        c_flags.push_str(apple_architecture);
+        linker_flags.push_str(" -arch ");
+        linker_flags.push_str(apple_architecture);
+
+        let host_architecture = env::var("HOST")
+            .ok()
+            .and_then(|host| host.split('-').next().map(str::to_string));
+
+        if let Some(sdk) = apple_sdk() {
+            let sdk_path = apple_sdk_path(sdk.name)?;
+
+            println!(
+                "cargo:info=Building Opus with the `{}`-SDK for {} {}.",
+                sdk.name, sdk.platform, sdk.version
+            );
+
+            for flags in &mut [&mut c_flags, &mut linker_flags] {
+                flags.push_str(&format!(" -isysroot {} {}", sdk_path, sdk.version_flags()));
+            }
+
+            command_builder.arg(format!("--host={}-apple-darwin", architecture));
+        } else if host_architecture.as_ref().map(String::as_str) != Some(architecture) {
+            command_builder.arg(format!("--host={}-apple-darwin", architecture));
+        }
+    }
+
+    if let Some(ndk) = find_android_ndk()? {
+        let toolchain = android_toolchain(&ndk)?;
+
+        println!(
+            "cargo:info=Building Opus with the Android NDK's `{}`.",
+            toolchain.c_compiler.display()
+        );
+
+        command_builder
+            .env("CC", wrapped_compiler(&toolchain.c_compiler))
+            .env("AR", &toolchain.archiver)
+            .env("RANLIB", &toolchain.ranlib)
+            .arg(format!("--host={}", toolchain.host));
+    } else if apple_architecture(architecture).is_none() {
+        let host = env::var("HOST").ok();
+        let target = env::var("TARGET").unwrap_or_default();
+
+        if is_cross_compiled(host.as_ref().map(String::as_str), &target) {
+            let toolchain = if let Some(native) = find_ohos_sdk()? {
+                ohos_toolchain(&native, &target)
+            } else if let Some(toolchain) =
+                multilib_toolchain(host.as_ref().map(String::as_str), &target)?
+            {
+                toolchain
+            } else {
+                cross_toolchain(&target)
+            };
+
+            println!(
+                "cargo:info=Cross-compiling Opus for `{}` with `{}`.",
+                toolchain.host,
+                toolchain.c_compiler.display()
+            );
+
+            for flags in &mut [&mut c_flags, &mut linker_flags] {
+                flags.push(' ');
+                flags.push_str(&toolchain.flags);
+            }
+
+            command_builder
+                .env("CC", wrapped_compiler(&toolchain.c_compiler))
+                .env("AR", &toolchain.archiver)
+                .env("RANLIB", &toolchain.ranlib)
+                .arg(format!("--host={}", toolchain.host));
+        } else {
+            for tool in &["CC", "AR", "RANLIB"] {
+                if let Some(path) = target_tool(tool) {
+                    println!("cargo:info=Using `{}` as `{}`.", path.display(), tool);
+
+                    if *tool == "CC" {
+                        command_builder.env(tool, wrapped_compiler(&path));
+                    } else {
+                        command_builder.env(tool, path);
+                    }
+                }
+            }
+
+            if is_lto_defaulting_to_clang(lto_mode) {
+                println!("cargo:info=Building Opus for ThinLTO with `clang` and `llvm-ar`.");
+
+                command_builder
+                    .env("CC", wrapped_compiler(Path::new("clang")))
+                    .env("AR", "llvm-ar")
+                    .env("RANLIB", "llvm-ranlib");
+            } else if target_tool("CC").is_none() && compiler_wrapper().is_some() {
+                let compiler = env::var_os("CC").unwrap_or_else(|| "cc".into());
+
+                command_builder.env("CC", wrapped_compiler(Path::new(&compiler)));
+            }
+        }
+    }
+
+    command_builder
+        .env("CFLAGS", c_flags.trim())
+        .env("LDFLAGS", linker_flags.trim());
+
+    command_builder
+        .arg("--disable-doc")
+        .arg("--disable-extra-programs")
+        .arg("--with-pic")
+        .arg("--prefix")
+        .arg(configure_path(build_directory))
+        .current_dir(&opus_path);
+
+    for flag in extra_flags("CONFIGURE_FLAGS") {
+        println!("cargo:info=Passing `configure`-flag: {}.", flag);
+
+        command_builder.arg(flag);
+    }
+
+    run_step("`configure`", &mut command_builder)?;
+
+    Ok(())
+}
+
+/// Configures, unless skipped, builds, and installs Opus in `build_directory`
+/// via Meson and Ninja, as requested by the `meson`-feature.
+///
+/// Opus ships a `meson.build` since 1.4, hence the bundled sources require
+/// the `v1_4`-feature or an external source tree. The target, compiler, and
+/// flags are passed via a machine file, see `write_meson_machine_file`.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+fn meson_opus(
+    source_path: &Path,
+    build_directory: &Path,
+    architecture: &str,
+    is_static: bool,
+) -> Result<(), BuildError> {
+    let meson_path = build_directory.join("meson");
+    let meson_program = program("MESON", "meson");
+    let ninja_program = program("NINJA", "ninja");
+
+    for tool in &[&meson_program, &ninja_program] {
+        if Command::new(tool).arg("--version").output().is_err() {
+            return Err(BuildError::new(
+                "Locating Meson",
+                format!(
+                    "Failed to run `{} --version`, make sure Meson and Ninja are \
+                     installed, e.g. via `pip install meson ninja`, or point the \
+                     `MESON` and `NINJA` environment variables to them.",
+                    tool
+                ),
+            ));
+        }
+    }
+
+    let is_skipping_configure = opus_env("SKIP_CONFIGURE").is_some();
+
+    if is_skipping_configure && meson_path.join("build.ninja").is_file() {
+        println!("cargo:info=Found configured Opus, skipping `meson setup`.");
+    } else {
+        if is_skipping_configure {
+            println!("cargo:info=Found no configured Opus, configuring nonetheless.");
+        }
+
+        let mut command_builder = Command::new(&meson_program);
+
+        command_builder.arg("setup");
+
+        // Meson refuses to set up a build directory configured before.
+        if meson_path.join("build.ninja").is_file() {
+            command_builder.arg("--wipe");
+        }
+
+        let simd_options = simd_options(architecture)?;
+        let mut options = vec!["docs=disabled", "extra-programs=disabled", "tests=disabled"];
+
+        for option in feature_options() {
+            options.push(match option {
+                FeatureOption::CustomModes => "custom-modes=true",
+                FeatureOption::FixedPoint => "fixed-point=true",
+                FeatureOption::DisableFloatApi => "float-api=false",
+                FeatureOption::DeepPlc => "deep-plc=enabled",
+                FeatureOption::Dred => "dred=enabled",
+                FeatureOption::Osce => "osce=enabled",
+                FeatureOption::Assertions => "assertions=true",
+                FeatureOption::Hardening => "hardening=true",
+                FeatureOption::Fuzzing => "fuzzing=true",
+            });
+        }
+
+        if simd_options.is_checking_asm {
+            options.push("check-asm=true");
+        }
+
+        match simd_options.is_using_intrinsics {
+            Some(true) => options.push("intrinsics=enabled"),
+            Some(false) => options.push("intrinsics=disabled"),
+            None => {}
+        }
+
+        match simd_options.is_detecting_cpu {
+            Some(true) => options.push("rtcd=enabled"),
+            Some(false) => options.push("rtcd=disabled"),
+            None => {}
+        }
+
+        let (machine_file, is_cross) = write_meson_machine_file(
+            build_directory,
+            architecture,
+            is_static,
+            &simd_options.flags,
+        )?;
+
+        command_builder
+            .arg(&meson_path)
+            .arg(source_path)
+            .arg("--prefix")
+            .arg(build_directory)
+            .args(&["--libdir", "lib", "--buildtype", "plain", "-Db_staticpic=true"])
+            .arg("--default-library")
+            .arg(if is_static { "static" } else { "shared" })
+            .arg(if is_cross { "--cross-file" } else { "--native-file" })
+            .arg(&machine_file)
+            .env_remove("CFLAGS")
+            .env_remove("LDFLAGS");
+
+        for option in options {
+            command_builder.arg(format!("-D{}", option));
+        }
+
+        for flag in extra_flags("MESON_ARGS") {
+            println!("cargo:info=Passing Meson-argument: {}.", flag);
+
+            command_builder.arg(flag);
+        }
+
+        run_step("`meson setup`", &mut command_builder)?;
+    }
+
+    let mut ninja_command = Command::new(&ninja_program);
+
+    ninja_command.arg("-C").arg(&meson_path);
+
+    if let Ok(jobs) = env::var("NUM_JOBS") {
+        ninja_command.arg(format!("-j{}", jobs));
+    }
+
+    run_step("`ninja`", &mut ninja_command)?;
+    run_step(
+        "`ninja install`",
+        Command::new(&ninja_program)
+            .arg("-C")
+            .arg(&meson_path)
+            .arg("install"),
+    )?;
+
+    Ok(())
+}
+
+/// Writes the Meson machine file passing the C compiler, archiver, and flags
+/// of the build, including `CFLAGS` and `LDFLAGS`, which Meson ignores when
+/// cross-compiling.
+///
+/// When cross-compiling, e.g. via the Android NDK, an Apple SDK, or a GNU
+/// cross-toolchain, it is a cross file describing the target as
+/// `host_machine`, a native file otherwise. Returns its path and whether it is
+/// a cross file.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+fn write_meson_machine_file(
+    build_directory: &Path,
+    architecture: &str,
+    is_static: bool,
+    simd_flags: &[&str],
+) -> Result<(PathBuf, bool), BuildError> {
+    let lto_mode = lto_mode()?;
+    let sanitizer_flags = sanitizer_c_flags();
+
+    let mut c_flags: Vec<String> = profile_c_flags()?
+        .split_whitespace()
+        .map(str::to_string)
+        .collect();
+    c_flags.extend(arm_float_c_flags().iter().map(|flag| flag.to_string()));
+    c_flags.extend(cpu_tuning_c_flags(architecture)?);
+    c_flags.extend(simd_flags.iter().map(|flag| flag.to_string()));
+    c_flags.extend(sanitizer_flags.iter().cloned());
+    c_flags.extend(lto_c_flags(lto_mode).iter().map(|flag| flag.to_string()));
+    c_flags.extend(visibility_c_flags(is_static)?.iter().map(|flag| flag.to_string()));
+
+    let mut linker_flags = sanitizer_flags;
+    linker_flags.extend(lto_c_flags(lto_mode).iter().map(|flag| flag.to_string()));
+
+    let host = env::var("HOST").ok();
+    let target = env::var("TARGET").unwrap_or_default();
+    let mut is_cross = is_cross_compiled(host.as_ref().map(String::as_str), &target);
+    let mut target_flags = Vec::new();
+    let mut tools = None;
+
+    if let Some(sysroot) = fuchsia_sysroot()? {
+        target_flags.push(format!("--target={}", target));
+        target_flags.push(format!("--sysroot={}", sysroot.display()));
+    }
+
+    if let Some(apple_architecture) = apple_architecture(architecture) {
+        target_flags.push("-arch".to_string());
+        target_flags.push(apple_architecture.to_string());
+
+        if let Some(sdk) = apple_sdk() {
+            target_flags.push("-isysroot".to_string());
+            target_flags.push(apple_sdk_path(sdk.name)?);
+            target_flags.extend(sdk.version_flags().split_whitespace().map(str::to_string));
+
+            is_cross = true;
+        }
+    } else if let Some(ndk) = find_android_ndk()? {
+        let toolchain = android_toolchain(&ndk)?;
+
+        tools = Some((toolchain.c_compiler, toolchain.archiver));
+        is_cross = true;
+    } else if is_cross {
+        let toolchain = if let Some(native) = find_ohos_sdk()? {
+            ohos_toolchain(&native, &target)
+        } else if let Some(toolchain) =
+            multilib_toolchain(host.as_ref().map(String::as_str), &target)?
+        {
+            toolchain
+        } else {
+            cross_toolchain(&target)
+        };
+
+        target_flags.extend(toolchain.flags.split_whitespace().map(str::to_string));
+        tools = Some((toolchain.c_compiler, toolchain.archiver));
+    } else if is_lto_defaulting_to_clang(lto_mode) {
+        println!("cargo:info=Building Opus for ThinLTO with `clang` and `llvm-ar`.");
+
+        tools = Some((PathBuf::from("clang"), PathBuf::from("llvm-ar")));
+    } else if let Some(c_compiler) = target_tool("CC") {
+        let archiver = target_tool("AR").unwrap_or_else(|| PathBuf::from("ar"));
+
+        tools = Some((c_compiler, archiver));
+    } else if compiler_wrapper().is_some() {
+        let c_compiler = PathBuf::from(env::var_os("CC").unwrap_or_else(|| "cc".into()));
+        let archiver = PathBuf::from(env::var_os("AR").unwrap_or_else(|| "ar".into()));
+
+        tools = Some((c_compiler, archiver));
+    }
+
+    c_flags.extend(target_flags.iter().cloned());
+    linker_flags.extend(target_flags);
+
+    if let Ok(user_c_flags) = env::var("CFLAGS") {
+        c_flags.extend(user_c_flags.split_whitespace().map(str::to_string));
+    }
+
+    if let Ok(user_linker_flags) = env::var("LDFLAGS") {
+        linker_flags.extend(user_linker_flags.split_whitespace().map(str::to_string));
+    }
+
+    let mut machine_file = String::from("[binaries]\n");
+
+    if let Some((c_compiler, archiver)) = tools {
+        let compiler_command: Vec<String> = compiler_wrapper()
+            .into_iter()
+            .chain(Some(c_compiler.display().to_string()))
+            .collect();
+
+        println!(
+            "cargo:info=Building Opus via Meson with `{}`.",
+            compiler_command.join(" ")
+        );
+
+        machine_file.push_str(&format!(
+            "c = {}\nar = {}\n",
+            meson_array(&compiler_command),
+            meson_array(&[archiver.display().to_string()])
+        ));
+    }
+
+    if is_cross {
+        let target_os = cargo_cfg("TARGET_OS");
+        let (system, cpu_family) = meson_machine(&target_os, architecture);
+
+        machine_file.push_str(&format!(
+            "\n[host_machine]\nsystem = '{}'\ncpu_family = '{}'\ncpu = '{}'\nendian = '{}'\n",
+            system,
+            cpu_family,
+            architecture,
+            cargo_cfg("TARGET_ENDIAN")
+        ));
+    }
+
+    machine_file.push_str(&format!(
+        "\n[built-in options]\nc_args = {}\nc_link_args = {}\n",
+        meson_array(&c_flags),
+        meson_array(&linker_flags)
+    ));
+
+    let machine_file_path = build_directory.join(if is_cross {
+        "meson-cross.ini"
+    } else {
+        "meson-native.ini"
+    });
+
+    std::fs::create_dir_all(build_directory)
+        .and_then(|_| std::fs::write(&machine_file_path, machine_file))
+        .map_err(|error| {
+            BuildError::new(
+                "Writing the Meson machine file",
+                format!(
+                    "Could not write `{}`: {}",
+                    machine_file_path.display(),
+                    error
+                ),
+            )
+        })?;
+
+    Ok((machine_file_path, is_cross))
+}
+
+#[cfg(all(windows, target_env = "msvc", not(feature = "cmake")))]
+fn build_opus(_build_directory: &Path, is_static: bool) -> Result<(), BuildError> {
+    link_prebuilt_opus(is_static)
+}
+
+/// Whether the target is the Universal Windows Platform, such as
+/// `x86_64-uwp-windows-msvc`, forbidding the desktop APIs.
+fn is_uwp() -> bool {
+    cargo_cfg("TARGET_VENDOR") == "uwp"
+}
+
+/// Links to prebuilt Windows library-files of Opus.
+///
+/// They are looked up in `msvc/<architecture>/` for static and
+/// `msvc/<architecture>/dy/` for dynamic linking, where the base directory
+/// `msvc` can be overridden via `LIBOPUS_MSVC_DIR` or `OPUS_MSVC_DIR`.
+#[cfg(all(windows, target_env = "msvc", not(feature = "cmake")))]
+fn link_prebuilt_opus(is_static: bool) -> Result<(), BuildError> {
+    const STEP: &str = "Linking the prebuilt Opus";
+
+    if cfg!(any(feature = "fixed-point", feature = "disable-float-api")) {
+        return Err(BuildError::new(
+            STEP,
+            "The prebuilt library-files use floating-point arithmetic and \
+             provide the float API, unset `LIBOPUS_MSVC_PREBUILT` and \
+             `OPUS_MSVC_PREBUILT` to build Opus with the `fixed-point`- or \
+             `disable-float-api`-feature from source.",
+        ));
+    }
+
+    let is_static_text = rustc_linking_word(is_static);
+
+    const OPUS_DLL: &'static str = "opus.dll";
+
+    if is_uwp() {
+        return Err(BuildError::new(
+            STEP,
+            "The prebuilt Opus targets the Windows desktop, unset \
+             `LIBOPUS_MSVC_PREBUILT` and `OPUS_MSVC_PREBUILT` to build Opus for \
+             the Universal Windows Platform from source.",
+        ));
+    }
+
+    if cfg!(feature = "custom") {
+        return Err(BuildError::new(
+            STEP,
+            "The prebuilt Opus lacks custom modes, build Opus via the \
+             `cmake`-feature or point `OPUS_LIB_DIR` to your own.",
+        ));
+    }
+
+    if cfg!(feature = "v1_3_1") {
+        return Err(BuildError::new(
+            STEP,
+            format!(
+                "The prebuilt Opus is {}, unset `LIBOPUS_MSVC_PREBUILT` and \
+                 `OPUS_MSVC_PREBUILT` to build the version selected by the \
+                 `v1_*`-features from source.",
+                BUNDLED_OPUS_VERSION
+            ),
+        ));
+    }
+
+    let architecture = match cargo_cfg("TARGET_ARCH").as_str() {
+        "x86" => "x86",
+        "x86_64" => "x64",
+        "aarch64" => "arm64",
+        architecture => {
+            return Err(BuildError::new(
+                STEP,
+                format!(
+                    "No prebuilt Opus exists for the `{}`-architecture, unset \
+                     `LIBOPUS_MSVC_PREBUILT` and `OPUS_MSVC_PREBUILT` to build Opus \
+                     from source instead.",
+                    architecture
+                ),
+            ))
+        }
+    };
+
+    let base_directory = opus_env("MSVC_DIR").unwrap_or_else(|| "msvc".to_string());
+    let mut building_path = Path::new(&base_directory).join(architecture);
+
+    let is_crt_static = is_msvc_crt_static()?;
+
+    // Linking the `/MD`-compiled `opus.lib` into a `crt-static` binary mixes
+    // both runtimes.
+    if is_static && is_crt_static {
+        building_path = building_path.join("mt");
+
+        if !building_path.is_dir() {
+            return Err(BuildError::new(
+                STEP,
+                format!(
+                    "No prebuilt `opus.lib` compiled with `/MT` for the static \
+                     C runtime exists in `{}`, either provide one there or unset \
+                     `LIBOPUS_MSVC_PREBUILT` and `OPUS_MSVC_PREBUILT` to build \
+                     Opus from source with the matching runtime.",
+                    building_path.display()
+                ),
+            ));
+        }
+    } else if is_crt_static {
+        building_path = building_path.join("dy-mt");
+
+        if !building_path.is_dir() {
+            return Err(BuildError::new(
+                STEP,
+                format!(
+                    "No prebuilt `opus.dll` linking the C runtime statically \
+                     exists in `{}`, either provide one there, build Opus \
+                     via the `cmake`-feature, or point `OPUS_LIB_DIR` to your own.",
+                    building_path.display()
+                ),
+            ));
+        }
+    } else if !is_static {
+        building_path = building_path.join("dy");
+    }
+
+    let library_path = building_path.canonicalize().map_err(|_| {
+        BuildError::new(
+            STEP,
+            format!(
+                "Prebuilt Opus library-files are missing, expected: {}. Unset \
+                 `LIBOPUS_MSVC_PREBUILT` and `OPUS_MSVC_PREBUILT` to build Opus \
+                 from source instead.",
+                building_path.display()
+            ),
+        )
+    })?;
+
+    verify_prebuilt_checksums(Path::new(&base_directory), &building_path)?;
+
+    if let Some((library_file, is_import)) = windows_library_kind("opus", &library_path) {
+        if is_import == is_static {
+            return Err(BuildError::new(
+                STEP,
+                format!(
+                    "{} is {}, but {} linking is requested, provide the matching \
+                     library-file there or select the other linking via `OPUS_LINKAGE`.",
+                    library_file.display(),
+                    library_kind_text(is_import),
+                    if is_static { "static" } else { "dynamic" }
+                ),
+            ));
+        }
+    }
+
+    println!("cargo:info=Try to build {} library.", is_static_text);
+    emit_link("opus", is_static, &library_path)?;
+    probe_opus("opus", is_static, &library_path)?;
+
+    // The prebuilt library-files ship without headers, hence the bundled
+    // sources' are checked if present.
+    generate_bindings(&[])?;
+
+    let source_include_directory = Path::new("opus").join("include");
+
+    if source_include_directory.is_dir() {
+        check_constants(&[source_include_directory.clone()])?;
+    }
+
+    let include_directory = install_found_headers(&[source_include_directory])?;
+
+    emit_metadata(
+        is_static,
+        "opus",
+        &library_path,
+        include_directory.as_ref().map(PathBuf::as_path),
+        None,
+        Some(BUNDLED_OPUS_VERSION),
+    );
+
+    if !is_static {
+        copy_dlls(&[building_path.join(OPUS_DLL)])?;
+    }
+
+    Ok(())
+}
+
+/// Verifies every file in `building_path` against the SHA-256 listed in the
+/// manifest `SHA256SUMS` of `base_directory`, failing the build if a file is
+/// missing from it or differs, as the prebuilt library-files are linked into
+/// dependents unseen.
+///
+/// The bundled `msvc`-directory must have a manifest, whereas one passed via
+/// `LIBOPUS_MSVC_DIR` or `OPUS_MSVC_DIR` is only verified if it has one.
+#[cfg(all(windows, target_env = "msvc", not(feature = "cmake")))]
+fn verify_prebuilt_checksums(
+    base_directory: &Path,
+    building_path: &Path,
+) -> Result<(), BuildError> {
+    use sha2::{Digest, Sha256};
+
+    const STEP: &str = "Verifying the prebuilt Opus";
+
+    let manifest_path = base_directory.join("SHA256SUMS");
+
+    let manifest = match std::fs::read_to_string(&manifest_path) {
+        Ok(manifest) => manifest,
+        Err(_) if opus_env("MSVC_DIR").is_some() => {
+            println!(
+                "cargo:info=No `SHA256SUMS` in `{}`, skipping verifying the prebuilt Opus.",
+                base_directory.display()
+            );
+
+            return Ok(());
+        }
+        Err(error) => {
+            return Err(BuildError::new(
+                STEP,
+                format!("Could not read {}: {}", manifest_path.display(), error),
+            ))
+        }
+    };
+
+    let checksums = parse_checksums(&manifest)
+        .map_err(|error| BuildError::new(STEP, format!("{} {}", manifest_path.display(), error)))?;
+    let relative_directory = building_path
+        .strip_prefix(base_directory)
+        .unwrap_or(building_path)
+        .to_string_lossy()
+        .replace('\\', "/");
+
+    let entries = std::fs::read_dir(building_path).map_err(|error| {
+        BuildError::new(
+            STEP,
+            format!("Could not read {}: {}", building_path.display(), error),
+        )
+    })?;
+
+    for entry in entries.filter_map(Result::ok) {
+        if !entry.path().is_file() {
+            continue;
+        }
+
+        let relative_path = format!(
+            "{}/{}",
+            relative_directory,
+            entry.file_name().to_string_lossy()
+        );
+        let expected_checksum = checksums
+            .iter()
+            .find(|(path, _)| *path == relative_path)
+            .map(|(_, checksum)| checksum)
+            .ok_or_else(|| {
+                BuildError::new(
+                    STEP,
+                    format!(
+                        "`{}` is not listed in {}.",
+                        relative_path,
+                        manifest_path.display()
+                    ),
+                )
+            })?;
+
+        let file = std::fs::read(entry.path()).map_err(|error| {
+            BuildError::new(
+                STEP,
+                format!("Could not read {}: {}", entry.path().display(), error),
+            )
+        })?;
+        let checksum = format!("{:x}", Sha256::digest(&file));
+
+        if checksum != *expected_checksum {
+            return Err(BuildError::new(
+                STEP,
+                format!(
+                    "The SHA-256 of {} is {}, expected {} as listed in {}.",
+                    entry.path().display(),
+                    checksum,
+                    expected_checksum,
+                    manifest_path.display()
+                ),
+            ));
+        }
+    }
+
+    println!(
+        "cargo:info=Verified the prebuilt Opus in `{}` against {}.",
+        building_path.display(),
+        manifest_path.display()
+    );
+
+    Ok(())
+}
+
+/// Whether Opus shall use the static C runtime (`/MT`), as requested via
+/// `LIBOPUS_MSVC_CRT` or `OPUS_MSVC_CRT` set to `static` or `dynamic`,
+/// defaulting to whether Rust links it statically via
+/// `-C target-feature=+crt-static`, as mixing both runtimes fails to link.
+///
+/// Otherwise, `opus.dll` depends on `VCRUNTIME140.dll` and the Universal CRT,
+/// which must be redistributed alongside the application.
+#[cfg(all(windows, target_env = "msvc"))]
+fn is_msvc_crt_static() -> Result<bool, BuildError> {
+    let (variable, value) = match opus_env_var("MSVC_CRT") {
+        Some(variable) => variable,
+        None => {
+            return Ok(cargo_cfg("TARGET_FEATURE")
+                .split(',')
+                .any(|feature| feature == "crt-static"))
+        }
+    };
+
+    match value.as_str() {
+        "dynamic" => Ok(false),
+        "static" => Ok(true),
+        _ => Err(BuildError::new(
+            "Selecting the C runtime",
+            format!(
+                "Invalid value for `{}`: `{}`, expected `static` or `dynamic`.",
+                variable, value
+            ),
+        )),
+    }
+}
+
+/// Copies `dll_paths` into the directory of the built binaries, where Windows
+/// finds them when running them, see `dll_out_directory`.
+#[cfg(all(windows, target_env = "msvc"))]
+fn copy_dlls(dll_paths: &[PathBuf]) -> Result<(), BuildError> {
+    let dll_out_directory = dll_out_directory()?;
+
+    println!(
+        "cargo:info=Copying the DLLs of Opus into {}.",
+        dll_out_directory.display()
+    );
+
+    // Tests and examples run from `deps` and `examples` respectively, hence
+    // they need their own copy. Cargo adds `deps` to the `PATH` of doctests.
+    let dll_directories = [
+        dll_out_directory.clone(),
+        dll_out_directory.join("deps"),
+        dll_out_directory.join("examples"),
+    ];
+
+    for dll_path in dll_paths {
+        let dll_name = match dll_path.file_name() {
+            Some(dll_name) => dll_name,
+            None => continue,
+        };
+
+        for dll_directory in &dll_directories {
+            let dll_destination = dll_directory.join(dll_name);
+
+            std::fs::create_dir_all(dll_directory)
+                .and_then(|_| std::fs::copy(dll_path, &dll_destination))
+                .map_err(|error| {
+                    BuildError::new(
+                        &format!("Copying `{}`", dll_name.to_string_lossy()),
+                        format!(
+                            "Could not copy `{}` to `{}`: {}",
+                            dll_path.to_string_lossy(),
+                            dll_destination.to_string_lossy(),
+                            error
+                        ),
+                    )
+                })?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the directory of the binaries built with the current profile,
+/// such as `target/debug` or `target/x86_64-pc-windows-msvc/release`, or the
+/// one set via `LIBOPUS_DLL_OUT_DIR` or `OPUS_DLL_OUT_DIR`.
+///
+/// Cargo places `OUT_DIR` at `<profile directory>/build/<package>-<hash>/out`,
+/// regardless of `CARGO_TARGET_DIR`, workspaces, and `--target`.
+#[cfg(all(windows, target_env = "msvc"))]
+fn dll_out_directory() -> Result<PathBuf, BuildError> {
+    if let Some(directory) = opus_env("DLL_OUT_DIR") {
+        return Ok(PathBuf::from(directory));
+    }
+
+    let out_directory =
+        PathBuf::from(env::var("OUT_DIR").expect("Environment variable `OUT_DIR` is missing."));
+
+    let build_directory = out_directory
+        .parent()
+        .and_then(Path::parent)
+        .filter(|directory| directory.file_name().map_or(false, |name| name == "build"));
+
+    match build_directory.and_then(Path::parent) {
+        Some(profile_directory) => Ok(profile_directory.to_path_buf()),
+        None => Err(BuildError::new(
+            "Finding the directory of the built binaries",
+            format!(
+                "Unexpected `OUT_DIR`: {}. Set `LIBOPUS_DLL_OUT_DIR` or \
+                 `OPUS_DLL_OUT_DIR` to the directory the DLLs of Opus shall be \
+                 copied to.",
+                out_directory.display()
+            ),
+        )),
+    }
+}
+
+/// Whether the target is WebAssembly without an operating system, such as
+/// `wasm32-unknown-unknown`, lacking the tools and C library autotools, CMake,
+/// and the probe need. Opus is built via the `cc`-crate for it.
+fn is_bare_wasm() -> bool {
+    cargo_cfg("TARGET_ARCH") == "wasm32" && cargo_cfg("TARGET_OS") == "unknown"
+}
+
+/// Set if autotools lack a tool, such as on fresh macOS machines or minimal
+/// containers, building Opus via the `cc`-crate instead of failing halfway.
+static IS_FALLING_BACK_TO_CC: AtomicBool = AtomicBool::new(false);
+
+/// Whether Opus is built via the `cc`-crate, as requested by the
+/// `cc-build`-feature or required by WebAssembly targets, for which `emcc`
+/// is used when targeting Emscripten.
+///
+/// MSVC builds via the `cc`-crate as well, unless the `cmake`-feature is
+/// enabled or the prebuilt library-files are requested via
+/// `LIBOPUS_MSVC_PREBUILT` or `OPUS_MSVC_PREBUILT`, and so do autotools
+/// builds lacking their tools, see `IS_FALLING_BACK_TO_CC`.
+///
+/// Windows' GNU-toolchain builds via the `cc`-crate outside of MSYS2, e.g.
+/// from PowerShell with only MinGW-w64's `gcc` installed, and so does any
+/// target compiled by `zig cc`, see `is_using_zig`.
+fn is_building_with_cc() -> bool {
+    let is_msvc_from_source = cargo_cfg("TARGET_ENV") == "msvc"
+        && !cfg!(feature = "cmake")
+        && opus_env("MSVC_PREBUILT").is_none();
+    let is_mingw_without_msys = cfg!(windows)
+        && cargo_cfg("TARGET_ENV") == "gnu"
+        && !cfg!(feature = "cmake")
+        && env::var_os("MSYSTEM").is_none();
+
+    cfg!(feature = "cc-build")
+        || is_bare_wasm()
+        || cargo_cfg("TARGET_OS") == "emscripten"
+        || is_msvc_from_source
+        || is_mingw_without_msys
+        || (!cfg!(feature = "cmake") && is_using_zig())
+        || IS_FALLING_BACK_TO_CC.load(Ordering::Relaxed)
+}
+
+/// Fails if dynamic linking is requested while Opus is built via the
+/// `cc`-crate, which only builds static libraries, rather than linking
+/// statically anyway. A dynamic linking merely defaulted to links statically.
+fn check_cc_linkage(is_static: bool) -> Result<(), BuildError> {
+    if is_static {
+        return Ok(());
+    }
+
+    if is_linkage_requested() {
+        // MSVC builds via the `cc`-crate by default, lacking autotools.
+        let alternatives = if cargo_cfg("TARGET_ENV") == "msvc" {
+            "the `cmake`-feature or link the prebuilt `opus.dll` via \
+             `OPUS_MSVC_PREBUILT`"
+        } else {
+            "autotools or the `cmake`-feature"
+        };
+
+        return Err(BuildError::new(
+            "Compiling Opus via `cc`",
+            format!(
+                "The `cc`-crate only builds static libraries, but dynamic linking is \
+                 requested. Request static linking, or build Opus via {} instead.",
+                alternatives
+            ),
+        ));
+    }
+
+    println!("cargo:info=The `cc`-crate only builds static libraries, linking statically.");
+
+    Ok(())
+}
+
+/// Whether the target's C compiler is `zig cc`, such as set by
+/// `cargo-zigbuild` via `CC_<target>`, or else `TARGET_CC` or `CC`.
+///
+/// Zig cross-compiles for any target by itself, but neither matches the GNU
+/// cross-toolchains autotools are given nor accepts Apple's `-arch`, hence
+/// Opus is built via the `cc`-crate with exactly that compiler.
+fn is_using_zig() -> bool {
+    let target = env::var("TARGET").unwrap_or_default();
+
+    [
+        format!("CC_{}", target.replace('-', "_")),
+        format!("CC_{}", target),
+        "TARGET_CC".to_string(),
+        "CC".to_string(),
+    ]
+    .iter()
+    .filter_map(|variable| env::var(variable).ok())
+    .next()
+    .map_or(false, |compiler| is_zig_compiler(&compiler))
+}
+
+/// Builds Opus by compiling its sources directly via the `cc`-crate, using
+/// neither `sh`, `cp`, `make`, nor CMake, hence only a C compiler is needed.
+///
+/// The sources are listed by the `*_sources.mk`-files Opus ships for its own
+/// build systems, the floating-point or, with the `fixed-point`-feature, the
+/// fixed-point implementation is built without intrinsics. The `cc`-crate only
+/// produces static libraries, see `check_cc_linkage`.
+fn build_opus_with_cc(build_directory: &Path) -> Result<(), BuildError> {
+    const STEP: &str = "Compiling Opus via `cc`";
+
+    let opus_path = opus_source_path()?;
+
+    println!(
+        "cargo:info=Opus source path: {:?}.",
+        &opus_path.to_string_lossy()
+    );
+
+    println!("cargo:info=Opus will be built as static-library via `cc`.");
+
+    let (silk_implementation, silk_sources) = if cfg!(feature = "fixed-point") {
+        ("fixed", "SILK_SOURCES_FIXED")
+    } else {
+        ("float", "SILK_SOURCES_FLOAT")
+    };
+    let opus_sources = if cfg!(feature = "disable-float-api") {
+        &["OPUS_SOURCES"][..]
+    } else {
+        &["OPUS_SOURCES", "OPUS_SOURCES_FLOAT"][..]
+    };
+    let mut dnn_sources = Vec::new();
+
+    if cfg!(feature = "deep-plc") {
+        dnn_sources.push("DEEP_PLC_SOURCES");
+    }
+
+    if cfg!(feature = "dred") {
+        dnn_sources.push("DRED_SOURCES");
+    }
+
+    if cfg!(feature = "osce") {
+        dnn_sources.push("OSCE_SOURCES");
+    }
+
+    let silk_variables = ["SILK_SOURCES", silk_sources];
+    let mut makefiles = vec![
+        ("celt_sources.mk", &["CELT_SOURCES"][..]),
+        ("silk_sources.mk", &silk_variables[..]),
+        ("opus_sources.mk", opus_sources),
+    ];
+
+    if !dnn_sources.is_empty() {
+        makefiles.push(("lpcnet_sources.mk", &dnn_sources[..]));
+    }
+
+    let mut sources = Vec::new();
+
+    for (makefile, variables) in &makefiles {
+        let makefile_path = opus_path.join(makefile);
+        let content = std::fs::read_to_string(&makefile_path).map_err(|error| {
+            BuildError::new(
+                STEP,
+                format!("Could not read `{}`: {}", makefile_path.display(), error),
+            )
+        })?;
+
+        for variable in variables.iter() {
+            sources.extend(
+                make_variable(&content, variable)
+                    .iter()
+                    .map(|source| opus_path.join(source)),
+            );
+        }
+    }
+
+    let version = opus_source_version().unwrap_or_else(|| "unknown".to_string());
+    let mut build = cc::Build::new();
+
+    if let Some(wrapper) = compiler_wrapper() {
+        let compiler = build.get_compiler();
+
+        println!("cargo:info=Compiling Opus via `{}`.", wrapper);
+
+        build.compiler(compiler_launcher(
+            build_directory,
+            &wrapped_compiler(compiler.path()),
+        )?);
+    }
+
+    build
+        .files(&sources)
+        .include(opus_path.join("include"))
+        .include(opus_path.join("celt"))
+        .include(opus_path.join("silk"))
+        .include(opus_path.join("silk").join(silk_implementation))
+        .define("OPUS_BUILD", None)
+        .define(
+            "PACKAGE_VERSION",
+            Some(format!("\"{}\"", version).as_str()),
+        )
+        .warnings(false)
+        .out_dir(build_directory.join("lib"))
+        .cargo_metadata(false);
+
+    if cargo_cfg("TARGET_ENV") == "msvc" {
+        build.define("USE_ALLOCA", None);
+    } else {
+        build.define("VAR_ARRAYS", None);
+    }
+
+    if is_uwp() {
+        build.define("WINAPI_FAMILY", "WINAPI_FAMILY_APP");
+    }
+
+    if let Some(sysroot) = fuchsia_sysroot()? {
+        let sysroot_flag = format!("--sysroot={}", sysroot.display());
+
+        build.flag(&sysroot_flag);
+    }
+
+    // Bare WebAssembly lacks `lrint`, hence Opus rounds via `floor` instead.
+    if cargo_cfg("TARGET_ENV") != "msvc" && !is_bare_wasm() {
+        build
+            .define("HAVE_LRINT", None)
+            .define("HAVE_LRINTF", None);
+    }
+
+    if !dnn_sources.is_empty() {
+        build.include(opus_path.join("dnn"));
+    }
+
+    for option in feature_options() {
+        let define = match option {
+            FeatureOption::CustomModes => "CUSTOM_MODES",
+            FeatureOption::FixedPoint => "FIXED_POINT",
+            FeatureOption::DisableFloatApi => "DISABLE_FLOAT_API",
+            FeatureOption::DeepPlc => "ENABLE_DEEP_PLC",
+            FeatureOption::Dred => "ENABLE_DRED",
+            FeatureOption::Osce => "ENABLE_OSCE",
+            FeatureOption::Assertions => "ENABLE_ASSERTIONS",
+            FeatureOption::Hardening => "ENABLE_HARDENING",
+            FeatureOption::Fuzzing => "FUZZING",
+        };
+
+        build.define(define, None);
+    }
+
+    for flag in arm_float_c_flags() {
+        build.flag(flag);
+    }
+
+    for flag in cpu_tuning_c_flags(&cargo_cfg("TARGET_ARCH"))? {
+        build.flag(&flag);
+    }
+
+    for flag in sanitizer_c_flags() {
+        build.flag(&flag);
+    }
+
+    let lto_mode = lto_mode()?;
+
+    for flag in lto_c_flags(lto_mode) {
+        build.flag(flag);
+    }
+
+    if is_lto_defaulting_to_clang(lto_mode) {
+        build.compiler("clang").archiver("llvm-ar");
+    }
+
+    for flag in visibility_c_flags(true)? {
+        build.flag(flag);
+    }
+
+    if opus_env("OPT_LEVEL").is_some() {
+        build.opt_level_str(&opt_level()?);
+    }
+
+    // Ignores `OPUS_GC_SECTIONS`, the `cc`-crate places every function and
+    // datum in its own section anyway.
+    if size_options()?.1 {
+        build.debug(false);
+    }
+
+    build
+        .try_compile("opus")
+        .map_err(|error| BuildError::new(STEP, format!("{:?}", error)))?;
+
+    #[cfg(any(unix, target_env = "gnu", feature = "cmake"))]
+    prefix_symbols(&build_directory.join("lib"), true)?;
+
+    let header_directory = install_headers(&opus_path.join("include"))?;
+
+    emit_link("opus", true, &build_directory.join("lib"))?;
+    probe_opus("opus", true, &build_directory.join("lib"))?;
+    generate_bindings(std::slice::from_ref(&header_directory))?;
+    check_constants(std::slice::from_ref(&header_directory))?;
+    emit_pkg_config_file(build_directory)?;
+    emit_metadata(
+        true,
+        "opus",
+        &build_directory.join("lib"),
+        Some(&header_directory),
+        Some(build_directory),
+        opus_source_version().as_ref().map(String::as_str),
+    );
+
+    Ok(())
+}
+
+/// Builds Opus via CMake on any platform, replacing both the autotools
+/// pipeline and the prebuilt Windows library-files.
+///
+/// A toolchain file can be passed via `LIBOPUS_CMAKE_TOOLCHAIN_FILE`,
+/// `OPUS_CMAKE_TOOLCHAIN_FILE`, or `CMAKE_TOOLCHAIN_FILE`. Additional cache
+/// entries are read from `LIBOPUS_CMAKE_ARGS` or `OPUS_CMAKE_ARGS`.
+#[cfg(feature = "cmake")]
+fn build_opus(build_directory: &Path, is_static: bool) -> Result<(), BuildError> {
+    let is_static_text = rustc_linking_word(is_static);
+
+    let opus_path = opus_source_path()?;
+
+    println!(
+        "cargo:info=Opus source path: {:?}.",
+        &opus_path.to_string_lossy()
+    );
+    println!(
+        "cargo:info=Opus will be built as {}-library via CMake.",
+        is_static_text
+    );
+
+    let cmake_program = env::var("CMAKE").unwrap_or_else(|_| "cmake".to_string());
+
+    if Command::new(&cmake_program).arg("--version").output().is_err() {
+        return Err(BuildError::new(
+            "Locating CMake",
+            format!(
+                "Failed to run `{} --version`, make sure CMake is installed or \
+                 point the `CMAKE` environment variable to it.",
+                cmake_program
+            ),
+        ));
+    }
+
+    let mut config = cmake::Config::new(&opus_path);
+
+    config
+        .out_dir(build_directory)
+        .define("BUILD_SHARED_LIBS", if is_static { "OFF" } else { "ON" })
+        .define("BUILD_TESTING", "OFF")
+        .define("OPUS_BUILD_PROGRAMS", "OFF")
+        .define("CMAKE_INSTALL_LIBDIR", "lib");
+
+    for option in feature_options() {
+        let (key, value) = match option {
+            FeatureOption::CustomModes => ("OPUS_CUSTOM_MODES", "ON"),
+            FeatureOption::FixedPoint => ("OPUS_FIXED_POINT", "ON"),
+            FeatureOption::DisableFloatApi => ("OPUS_ENABLE_FLOAT_API", "OFF"),
+            FeatureOption::DeepPlc => ("OPUS_DEEP_PLC", "ON"),
+            FeatureOption::Dred => ("OPUS_DRED", "ON"),
+            FeatureOption::Osce => ("OPUS_OSCE", "ON"),
+            FeatureOption::Assertions => ("OPUS_ASSERTIONS", "ON"),
+            FeatureOption::Hardening => ("OPUS_HARDENING", "ON"),
+            FeatureOption::Fuzzing => ("OPUS_FUZZING", "ON"),
+        };
+
+        config.define(key, value);
+    }
+
+    for flag in arm_float_c_flags() {
+        config.cflag(flag);
+    }
+
+    let architecture = cargo_cfg("TARGET_ARCH");
+
+    for flag in cpu_tuning_c_flags(&architecture)? {
+        config.cflag(flag);
+    }
+
+    for flag in sanitizer_c_flags() {
+        config.cflag(flag);
+    }
+
+    let lto_mode = lto_mode()?;
+
+    for flag in lto_c_flags(lto_mode) {
+        config.cflag(flag);
+    }
+
+    if is_lto_defaulting_to_clang(lto_mode) {
+        config
+            .define("CMAKE_C_COMPILER", "clang")
+            .define("CMAKE_AR", "llvm-ar")
+            .define("CMAKE_RANLIB", "llvm-ranlib");
+    }
+
+    for flag in visibility_c_flags(is_static)? {
+        config.cflag(flag);
+    }
+
+    let (is_splitting_sections, is_stripping_debug) = size_options()?;
+
+    if opus_env("OPT_LEVEL").is_some() {
+        config.profile(match opt_level()?.as_str() {
+            "0" => "Debug",
+            "s" | "z" => "MinSizeRel",
+            _ => "Release",
+        });
+    }
+
+    if is_splitting_sections && cargo_cfg("TARGET_ENV") != "msvc" {
+        config.cflag("-ffunction-sections").cflag("-fdata-sections");
+    }
+
+    // The configurations' own flags follow `CMAKE_C_FLAGS`, hence `-g` is
+    // removed from them instead.
+    if is_stripping_debug && cargo_cfg("TARGET_ENV") != "msvc" {
+        config
+            .define("CMAKE_C_FLAGS_DEBUG", "-O0")
+            .define("CMAKE_C_FLAGS_RELWITHDEBINFO", "-O2 -DNDEBUG");
+    }
+
+    let (is_using_intrinsics, is_detecting_cpu, is_checking_asm) = cpu_options()?;
+    let is_using_neon = neon_option(&architecture)?;
+
+    if is_using_intrinsics == Some(false) {
+        config.define("OPUS_DISABLE_INTRINSICS", "ON");
+    } else if is_using_neon == Some(false) {
+        config
+            .define("OPUS_MAY_HAVE_NEON", "OFF")
+            .define("OPUS_PRESUME_NEON", "OFF");
+    } else if let Some(level) = max_simd_level(&architecture)? {
+        for (index, (_, _, option)) in X86_SIMD_LEVELS.iter().enumerate() {
+            let value = if index < level { "ON" } else { "OFF" };
+
+            config
+                .define(format!("OPUS_X86_PRESUME_{}", option), value)
+                .define(format!("OPUS_X86_MAY_HAVE_{}", option), value);
+        }
+    } else {
+        if is_using_neon == Some(true) && architecture == "arm" {
+            config.cflag("-mfpu=neon");
+        }
+
+        let simd_options = if is_inheriting_cpu()? {
+            simd_cmake_options(&architecture, is_using_neon)
+        } else {
+            Vec::new()
+        };
+
+        for option in simd_options {
+            println!("cargo:info=Target guarantees SIMD, enabling `{}`.", option);
+
+            config.define(option, "ON");
+        }
+    }
+
+    // Without runtime detection, Opus only uses the extensions it presumes.
+    if is_detecting_cpu == Some(false) {
+        for option in &[
+            "OPUS_X86_MAY_HAVE_SSE",
+            "OPUS_X86_MAY_HAVE_SSE2",
+            "OPUS_X86_MAY_HAVE_SSE4_1",
+            "OPUS_X86_MAY_HAVE_AVX",
+            "OPUS_MAY_HAVE_NEON",
+        ] {
+            config.define(option, "OFF");
+        }
+    }
+
+    if let Some(is_checking_asm) = is_checking_asm {
+        config.define("OPUS_CHECK_ASM", if is_checking_asm { "ON" } else { "OFF" });
+    }
+
+    #[cfg(all(windows, target_env = "msvc"))]
+    {
+        if is_msvc_crt_static()? {
+            config.static_crt(true);
+        }
+    }
+
+    if let Some(apple_architecture) = apple_architecture(&architecture) {
+        config.define("CMAKE_OSX_ARCHITECTURES", apple_architecture);
+    }
+
+    if is_uwp() {
+        config
+            .define("CMAKE_SYSTEM_NAME", "WindowsStore")
+            .define("CMAKE_SYSTEM_VERSION", "10.0");
+    }
+
+    if let Some(sysroot) = fuchsia_sysroot()? {
+        config
+            .define("CMAKE_SYSTEM_NAME", "Fuchsia")
+            .define("CMAKE_SYSROOT", sysroot)
+            .define(
+                "CMAKE_C_COMPILER_TARGET",
+                env::var("TARGET").unwrap_or_default(),
+            );
+    }
+
+    if let Some(sdk) = apple_sdk().filter(|sdk| sdk.is_catalyst) {
+        config
+            .define("CMAKE_OSX_SYSROOT", sdk.name)
+            .cflag(sdk.version_flags());
+    } else if let Some(sdk) = apple_sdk() {
+        config
+            .define("CMAKE_SYSTEM_NAME", sdk.platform)
+            .define("CMAKE_OSX_SYSROOT", sdk.name)
+            .define("CMAKE_OSX_DEPLOYMENT_TARGET", sdk.version);
+    }
+
+    if let Some(toolchain_file) = find_cmake_toolchain_file()? {
+        println!(
+            "cargo:info=Using CMake toolchain file: {:?}.",
+            toolchain_file.to_string_lossy()
+        );
+
+        config.define("CMAKE_TOOLCHAIN_FILE", toolchain_file);
+    } else if let Some(ndk) = find_android_ndk()? {
+        let (_, _, abi) = android_triples()?;
+
+        config
+            .define(
+                "CMAKE_TOOLCHAIN_FILE",
+                ndk.join("build").join("cmake").join("android.toolchain.cmake"),
+            )
+            .define("ANDROID_ABI", abi)
+            .define("ANDROID_PLATFORM", format!("android-{}", android_api_level()));
+    } else if let Some(native) = find_ohos_sdk()? {
+        let architecture = match cargo_cfg("TARGET_ARCH").as_str() {
+            "aarch64" => "arm64-v8a",
+            "arm" => "armeabi-v7a",
+            _ => "x86_64",
+        };
+
+        config
+            .define(
+                "CMAKE_TOOLCHAIN_FILE",
+                native
+                    .join("build")
+                    .join("cmake")
+                    .join("ohos.toolchain.cmake"),
+            )
+            .define("OHOS_ARCH", architecture);
+    }
+
+    if let Some(wrapper) = compiler_wrapper() {
+        println!("cargo:info=Compiling Opus via `{}`.", wrapper);
+
+        config.define("CMAKE_C_COMPILER_LAUNCHER", wrapper);
+    }
+
+    for (key, value) in cmake_cache_entries()? {
+        println!("cargo:info=Passing CMake cache entry: {}={}.", key, value);
+
+        config.define(key, value);
+    }
+
+    for flag in extra_flags("CMAKE_FLAGS") {
+        println!("cargo:info=Passing CMake flag: {}.", flag);
+
+        config.configure_arg(flag);
+    }
+
+    let build_result =
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| config.build()));
+
+    let install_directory = match build_result {
+        Ok(install_directory) => install_directory,
+        Err(_) => {
+            return Err(BuildError::new(
+                "Building Opus via CMake",
+                format!(
+                    "CMake failed to configure or build Opus, see the output above \
+                     and `{}` for details.",
+                    build_directory
+                        .join("build")
+                        .join("CMakeFiles")
+                        .join("CMakeError.log")
+                        .display()
+                ),
+            ))
+        }
+    };
+
+    prefix_symbols(&install_directory.join("lib"), is_static)?;
+
+    if !is_static {
+        verify_exports(&install_directory)?;
+    }
+
+    emit_link("opus", is_static, &install_directory.join("lib"))?;
+    probe_opus("opus", is_static, &install_directory.join("lib"))?;
+    generate_bindings(&[install_directory.join("include").join("opus")])?;
+    check_constants(&[install_directory.join("include").join("opus")])?;
+    emit_pkg_config_file(&install_directory)?;
+    emit_metadata(
+        is_static,
+        "opus",
+        &install_directory.join("lib"),
+        Some(&install_directory.join("include").join("opus")),
+        Some(&install_directory),
+        opus_source_version().as_ref().map(String::as_str),
+    );
+
+    Ok(())
+}
+
+/// Returns whether the target guarantees the given `CARGO_CFG_TARGET_FEATURE`,
+/// such as `sse4.1` when building with `-C target-feature=+sse4.1`.
+#[cfg(any(unix, target_env = "gnu", feature = "cmake"))]
+fn has_target_feature(feature: &str) -> bool {
+    cargo_cfg("TARGET_FEATURE")
+        .split(',')
+        .any(|target_feature| target_feature == feature)
+}
+
+/// Selects the float ABI of 32-bit ARM Linux targets, which the C code must
+/// share with the Rust code or the link fails, as a toolchain may default to
+/// the other one: `-mfloat-abi=hard` with a VFP for the `*eabihf`-targets,
+/// `vfpv3-d16` on ARMv7 and `vfp` on ARMv6, and `-mfloat-abi=soft` for the
+/// `*eabi`-targets.
+fn arm_float_c_flags() -> Vec<&'static str> {
+    let target = env::var("TARGET").unwrap_or_default();
+
+    if cargo_cfg("TARGET_ARCH") != "arm" || cargo_cfg("TARGET_OS") != "linux" {
+        return Vec::new();
+    }
+
+    if target.ends_with("eabihf") {
+        let fpu = if target.starts_with("armv7") || target.starts_with("thumbv7") {
+            "-mfpu=vfpv3-d16"
+        } else {
+            "-mfpu=vfp"
+        };
+
+        vec!["-mfloat-abi=hard", fpu]
+    } else if target.ends_with("eabi") {
+        vec!["-mfloat-abi=soft"]
+    } else {
+        Vec::new()
+    }
+}
+
+/// Returns `-march` and `-mtune` for the values of `LIBOPUS_MARCH` and
+/// `LIBOPUS_MTUNE` or their `OPUS_`-prefixed forms, such as `x86-64-v2`,
+/// letting the build host's compiler defaults not decide the CPU.
+///
+/// Without `-march`, the CPU Rust compiles for via `-C target-cpu`, such as
+/// `native`, is passed on as `-march` on x86 and `-mcpu` on ARM, PowerPC, and
+/// RISC-V, see `is_inheriting_cpu`.
+fn cpu_tuning_c_flags(architecture: &str) -> Result<Vec<String>, BuildError> {
+    let mut flags = Vec::new();
+
+    if let Some(cpu) = opus_env("MARCH") {
+        flags.push(format!("-march={}", cpu));
+    } else if let Some(cpu) = inherited_target_cpu(architecture)? {
+        let option = match architecture {
+            "x86" | "x86_64" => "-march",
+            _ => "-mcpu",
+        };
+
+        println!(
+            "cargo:info=Building Opus for Rust's target CPU via `{}={}`.",
+            option, cpu
+        );
+
+        flags.push(format!("{}={}", option, cpu));
+    }
+
+    if let Some(cpu) = opus_env("MTUNE") {
+        flags.push(format!("-mtune={}", cpu));
+    }
+
+    Ok(flags)
+}
+
+/// Whether the CPU and SIMD-extensions Rust compiles for are passed on to the
+/// C compiler, unless `LIBOPUS_INHERIT_CPU` or `OPUS_INHERIT_CPU` is false.
+fn is_inheriting_cpu() -> Result<bool, BuildError> {
+    match opus_env_var("INHERIT_CPU") {
+        Some((variable, value)) => parse_bool(&variable, &value)
+            .map_err(|error| BuildError::new("Configuring the CPU options", error)),
+        None => Ok(true),
+    }
+}
+
+/// Returns the CPU of `-C target-cpu` in `CARGO_ENCODED_RUSTFLAGS` if the C
+/// compiler understands it, i.e. unless MSVC, the Rust `architecture` lacks a
+/// matching option, or it is the `generic` default.
+fn inherited_target_cpu(architecture: &str) -> Result<Option<String>, BuildError> {
+    if !is_inheriting_cpu()? || cargo_cfg("TARGET_ENV") == "msvc" {
+        return Ok(None);
+    }
+
+    match architecture {
+        "x86" | "x86_64" | "arm" | "aarch64" | "powerpc" | "powerpc64" | "riscv32"
+        | "riscv64" => {}
+        _ => return Ok(None),
+    }
+
+    let encoded_rustflags = env::var("CARGO_ENCODED_RUSTFLAGS").unwrap_or_default();
+
+    Ok(rustflags_target_cpu(&encoded_rustflags).filter(|cpu| cpu != "generic"))
+}
+
+/// Reads the link-time optimisation of the Opus built from source from
+/// `LIBOPUS_LTO` or `OPUS_LTO`, either `fat` or `thin`.
+///
+/// `fat` emits objects containing both machine code and the compiler's IR,
+/// hence any linker can link Opus, while LTO-capable ones optimise across it.
+/// `thin` emits Clang's bitcode only, for cross-language LTO via Rust's
+/// `-C linker-plugin-lto`.
+fn lto_mode() -> Result<Option<&'static str>, BuildError> {
+    const STEP: &str = "Configuring link-time optimisation";
+
+    let (variable, value) = match opus_env_var("LTO") {
+        Some(variable) => variable,
+        None => return Ok(None),
+    };
+    let mode = parse_lto(&variable, &value).map_err(|error| BuildError::new(STEP, error))?;
+
+    if let Some(mode) = mode {
+        if cargo_cfg("TARGET_ENV") == "msvc" {
+            return Err(BuildError::new(
+                STEP,
+                format!("`{}` requires GCC or Clang, MSVC is not supported.", variable),
+            ));
+        }
+
+        println!("cargo:info=Building Opus with {} LTO.", mode);
+    }
+
+    Ok(mode)
+}
+
+/// Returns the C flags building Opus with the link-time optimisation `mode`,
+/// also passed when linking a shared Opus.
+fn lto_c_flags(mode: Option<&str>) -> &'static [&'static str] {
+    match mode {
+        Some("fat") => &["-flto", "-ffat-lto-objects"],
+        Some("thin") => &["-flto=thin"],
+        _ => &[],
+    }
+}
+
+/// Returns the C flags hiding every symbol of a static Opus, hence a `cdylib`
+/// or shared library linking it does not export Opus' API, unless
+/// `LIBOPUS_HIDE_SYMBOLS` or `OPUS_HIDE_SYMBOLS` is false.
+///
+/// Hidden symbols still resolve when linked statically. Defining `OPUS_EXPORT`
+/// as empty drops the default visibility Opus' headers would otherwise give
+/// its API. Windows' libraries only export what is marked for export anyway.
+fn visibility_c_flags(is_static: bool) -> Result<&'static [&'static str], BuildError> {
+    if !is_static || cargo_cfg("TARGET_OS") == "windows" {
+        return Ok(&[]);
+    }
+
+    let is_hiding_symbols = match opus_env_var("HIDE_SYMBOLS") {
+        Some((variable, value)) => parse_bool(&variable, &value)
+            .map_err(|error| BuildError::new("Configuring the symbol visibility", error))?,
+        None => true,
+    };
+
+    if is_hiding_symbols {
+        Ok(&["-fvisibility=hidden", "-DOPUS_EXPORT="])
+    } else {
+        println!("cargo:info=Keeping the default visibility of Opus' symbols.");
+
+        Ok(&[])
+    }
+}
+
+/// Whether ThinLTO falls back to `clang` and `llvm-ar`, as neither `CC`,
+/// `CC_<target>`, nor `TARGET_CC` select a compiler emitting LLVM's bitcode.
+fn is_lto_defaulting_to_clang(mode: Option<&str>) -> bool {
+    let target = env::var("TARGET").unwrap_or_default();
+    let compiler_variables = [
+        "CC".to_string(),
+        "TARGET_CC".to_string(),
+        format!("CC_{}", target),
+        format!("CC_{}", target.replace('-', "_")),
+    ];
+
+    mode == Some("thin")
+        && compiler_variables
+            .iter()
+            .all(|variable| env::var_os(variable).is_none())
+}
+
+/// An option of Opus' build systems selected by a feature, which each backend
+/// maps to its own `configure`-argument, Meson-option, C-define, or
+/// CMake-option.
+#[derive(Clone, Copy)]
+enum FeatureOption {
+    CustomModes,
+    FixedPoint,
+    DisableFloatApi,
+    DeepPlc,
+    Dred,
+    Osce,
+    Assertions,
+    Hardening,
+    Fuzzing,
+}
+
+/// Returns the options of Opus' build systems selected by the enabled
+/// features, such as `CustomModes` for the `custom`-feature.
+fn feature_options() -> Vec<FeatureOption> {
+    [
+        (cfg!(feature = "custom"), FeatureOption::CustomModes),
+        (cfg!(feature = "fixed-point"), FeatureOption::FixedPoint),
+        (cfg!(feature = "disable-float-api"), FeatureOption::DisableFloatApi),
+        (cfg!(feature = "deep-plc"), FeatureOption::DeepPlc),
+        (cfg!(feature = "dred"), FeatureOption::Dred),
+        (cfg!(feature = "osce"), FeatureOption::Osce),
+        (cfg!(feature = "assertions"), FeatureOption::Assertions),
+        (cfg!(feature = "hardening"), FeatureOption::Hardening),
+        (cfg!(feature = "fuzzing"), FeatureOption::Fuzzing),
+    ]
+    .iter()
+    .filter(|(is_enabled, _)| *is_enabled)
+    .map(|(_, option)| *option)
+    .collect()
+}
+
+/// Reads the highest x86 SIMD-level Opus may use from `LIBOPUS_MAX_SIMD` or
+/// `OPUS_MAX_SIMD`, one of `none`, `sse`, `sse2`, `sse4.1`, or `avx2`, as
+/// the number of `X86_SIMD_LEVELS` up to it.
+///
+/// Opus then presumes exactly these levels without detecting the CPU at
+/// runtime, hence the binary does not depend on the build host and runs on
+/// any CPU supporting the level. `None` if unset or the Rust `architecture` is
+/// not x86.
+#[cfg(any(unix, target_env = "gnu", feature = "cmake"))]
+fn max_simd_level(architecture: &str) -> Result<Option<usize>, BuildError> {
+    let (variable, value) = match opus_env_var("MAX_SIMD") {
+        Some(variable) => variable,
+        None => return Ok(None),
+    };
+
+    match architecture {
+        "x86" | "x86_64" => {}
+        _ => return Ok(None),
+    }
+
+    let level = parse_simd_level(&variable, &value)
+        .map_err(|error| BuildError::new("Configuring the CPU options", error))?;
+
+    println!(
+        "cargo:info=Limiting Opus to the x86 SIMD-level `{}` without runtime detection.",
+        value.trim()
+    );
+
+    Ok(Some(level))
+}
+
+/// Whether Opus uses intrinsics, detects the CPU's extensions at runtime, and
+/// checks its assembly, see `cpu_options`.
+#[cfg(any(unix, target_env = "gnu", feature = "cmake"))]
+type CpuOptions = (Option<bool>, Option<bool>, Option<bool>);
+
+/// Reads whether Opus uses intrinsics, detects the CPU's extensions at
+/// runtime, and checks its assembly against the C implementation from
+/// `LIBOPUS_INTRINSICS`, `LIBOPUS_RTCD`, and `LIBOPUS_CHECK_ASM` or their
+/// `OPUS_`-prefixed forms.
+///
+/// Unset variables are `None`, leaving the choice to Opus' build system.
+#[cfg(any(unix, target_env = "gnu", feature = "cmake"))]
+fn cpu_options() -> Result<CpuOptions, BuildError> {
+    Ok((
+        cpu_option("INTRINSICS")?,
+        cpu_option("RTCD")?,
+        cpu_option("CHECK_ASM")?,
+    ))
+}
+
+/// Reads the boolean CPU option `name`, such as `RTCD`, see `cpu_options`.
+#[cfg(any(unix, target_env = "gnu", feature = "cmake"))]
+fn cpu_option(name: &str) -> Result<Option<bool>, BuildError> {
+    opus_env(name)
+        .map(|value| {
+            parse_bool(&format!("OPUS_{}", name), &value)
+                .map_err(|error| BuildError::new("Configuring the CPU options", error))
+        })
+        .transpose()
+}
+
+/// Reads whether Opus presumes NEON on 32-bit ARM and AArch64 from
+/// `LIBOPUS_NEON` or `OPUS_NEON`, as detecting it on the build host goes
+/// wrong when cross-compiling and some ARMv7 chips lack NEON.
+///
+/// Defaults to whether the target guarantees NEON, `None` if the Rust
+/// `architecture` is another one.
+#[cfg(any(unix, target_env = "gnu", feature = "cmake"))]
+fn neon_option(architecture: &str) -> Result<Option<bool>, BuildError> {
+    match architecture {
+        "arm" | "aarch64" => Ok(Some(
+            cpu_option("NEON")?.unwrap_or_else(|| has_target_feature("neon")),
+        )),
+        _ => Ok(None),
+    }
+}
+
+/// Translates SIMD-extensions guaranteed by the target into C compiler flags,
+/// letting Opus use them without detecting them at runtime.
+///
+/// Opus' runtime detection stays in charge for all other extensions.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+fn simd_c_flags(architecture: &str, is_using_neon: Option<bool>) -> Vec<&'static str> {
+    let mut flags = Vec::new();
+
+    match architecture {
+        "x86" | "x86_64" => {
+            for &(feature, flag) in &[
+                ("sse4.1", "-msse4.1"),
+                ("avx", "-mavx"),
+                ("fma", "-mfma"),
+                ("avx2", "-mavx2"),
+            ] {
+                if has_target_feature(feature) {
+                    flags.push(flag);
+                }
+            }
+        }
+        // NEON is mandatory on AArch64, hence only 32-bit ARM needs the flag.
+        "arm" if is_using_neon == Some(true) => flags.push("-mfpu=neon"),
+        _ => {}
+    }
+
+    flags
+}
+
+/// The SIMD-options of Opus built via autotools or Meson, see `simd_options`.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+struct SimdOptions {
+    /// Whether Opus uses intrinsics, `None` leaving it to Opus.
+    is_using_intrinsics: Option<bool>,
+    /// Whether Opus detects the CPU's extensions at runtime, `None` leaving
+    /// it to Opus.
+    is_detecting_cpu: Option<bool>,
+    /// Whether Opus checks its assembly against the C implementation.
+    is_checking_asm: bool,
+    /// The C flags presuming SIMD-extensions.
+    flags: Vec<&'static str>,
+}
+
+/// Selects the SIMD-options of Opus built for the Rust `architecture` from
+/// `cpu_options`, `neon_option`, and `max_simd_level`, presuming the
+/// extensions the target guarantees, see `simd_c_flags`.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+fn simd_options(architecture: &str) -> Result<SimdOptions, BuildError> {
+    let (is_using_intrinsics, is_detecting_cpu, is_checking_asm) = cpu_options()?;
+    let is_using_neon = neon_option(architecture)?;
+    let max_simd_level = max_simd_level(architecture)?;
+    let is_detecting_cpu = if max_simd_level.is_some() {
+        Some(false)
+    } else {
+        is_detecting_cpu
+    };
+
+    // On ARM, NEON is the only extension Opus uses intrinsics for.
+    let is_disabling_intrinsics = is_using_intrinsics == Some(false)
+        || is_using_neon == Some(false)
+        || max_simd_level == Some(0);
+
+    let flags = if is_disabling_intrinsics {
+        Vec::new()
+    } else if let Some(level) = max_simd_level {
+        X86_SIMD_LEVELS[..level]
+            .iter()
+            .flat_map(|(_, flags, _)| flags.iter().cloned())
+            .collect()
+    } else if is_inheriting_cpu()? {
+        simd_c_flags(architecture, is_using_neon)
+    } else {
+        Vec::new()
+    };
+
+    if !flags.is_empty() {
+        println!(
+            "cargo:info=Presuming SIMD, building Opus with: {}.",
+            flags.join(" ")
+        );
+    }
+
+    let is_using_intrinsics = if is_disabling_intrinsics {
+        Some(false)
+    } else if is_using_intrinsics == Some(true)
+        || is_using_neon == Some(true)
+        || !flags.is_empty()
+    {
+        Some(true)
+    } else {
+        None
+    };
+
+    Ok(SimdOptions {
+        is_using_intrinsics,
+        is_detecting_cpu,
+        is_checking_asm: is_checking_asm == Some(true),
+        flags,
+    })
+}
+
+/// Translates SIMD-extensions guaranteed by the target into Opus' CMake
+/// options presuming them, see `simd_c_flags`.
+#[cfg(feature = "cmake")]
+fn simd_cmake_options(architecture: &str, is_using_neon: Option<bool>) -> Vec<&'static str> {
+    let mut options = Vec::new();
+
+    match architecture {
+        "x86" | "x86_64" => {
+            if has_target_feature("sse4.1") {
+                options.push("OPUS_X86_PRESUME_SSE4_1");
+            }
+
+            if has_target_feature("avx2") {
+                options.push("OPUS_X86_PRESUME_AVX");
+            }
+        }
+        "arm" | "aarch64" if is_using_neon == Some(true) => options.push("OPUS_PRESUME_NEON"),
+        _ => {}
+    }
+
+    options
+}
+
+/// Translates the sanitizers enabled via `-Zsanitizer`, which Cargo passes as
+/// `CARGO_CFG_SANITIZE`, into C compiler flags instrumenting Opus alike.
+fn sanitizer_c_flags() -> Vec<String> {
+    cargo_cfg("SANITIZE")
+        .split(',')
+        .filter(|sanitizer| {
+            ["address", "hwaddress", "leak", "memory", "thread"].contains(sanitizer)
+        })
+        .map(|sanitizer| format!("-fsanitize={}", sanitizer))
+        .collect()
+}
+
+/// Whether the C compiler building Opus is Clang, as Apple's always is.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+fn is_clang_compiler() -> bool {
+    if cargo_cfg("TARGET_VENDOR") == "apple" {
+        return true;
+    }
+
+    let compiler = target_tool("CC")
+        .or_else(|| env::var_os("CC").map(PathBuf::from))
+        .unwrap_or_else(|| PathBuf::from("cc"));
+
+    Command::new(compiler)
+        .arg("--version")
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).contains("clang"))
+        .unwrap_or(false)
+}
+
+/// Translates Cargo's `OPT_LEVEL` and `DEBUG` of the current profile into
+/// C compiler flags, see `opt_level` and `size_options` for overriding them.
+///
+/// `opt-level = "z"` maps to Clang's `-Oz`, other compilers fall back to `-Os`.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+fn profile_c_flags() -> Result<String, BuildError> {
+    let optimisation_flag = match opt_level()?.as_str() {
+        "0" => "-O0",
+        "1" => "-O1",
+        "3" => "-O3",
+        "z" if is_clang_compiler() => "-Oz",
+        "s" | "z" => "-Os",
+        _ => "-O2",
+    };
+
+    let is_debug = match env::var("DEBUG").as_ref().map(String::as_str) {
+        Ok("false") | Ok("0") | Ok("none") | Err(_) => false,
+        Ok(_) => true,
+    };
+    let (is_splitting_sections, is_stripping_debug) = size_options()?;
+
+    let mut c_flags = optimisation_flag.to_string();
+
+    if is_debug && !is_stripping_debug {
+        c_flags.push_str(" -g");
+    }
+
+    if is_splitting_sections {
+        c_flags.push_str(" -ffunction-sections -fdata-sections");
+    }
+
+    Ok(c_flags)
+}
+
+/// Reads the optimisation level of Opus from `LIBOPUS_OPT_LEVEL` or
+/// `OPUS_OPT_LEVEL`, one of `0` to `3`, `s`, or `z`, overriding Cargo's
+/// `OPT_LEVEL`, e.g. to optimise Opus for size in firmware otherwise
+/// optimised for speed.
+fn opt_level() -> Result<String, BuildError> {
+    let (variable, level) = match opus_env_var("OPT_LEVEL") {
+        Some((variable, level)) => (variable, level.trim().to_string()),
+        None => return Ok(env::var("OPT_LEVEL").unwrap_or_default()),
+    };
+
+    match level.as_str() {
+        "0" | "1" | "2" | "3" | "s" | "z" => {
+            println!("cargo:info=Building Opus with `opt-level = {}`.", level);
+
+            Ok(level)
+        }
+        _ => Err(BuildError::new(
+            "Configuring the optimisation",
+            format!(
+                "Invalid value for `{}`: `{}`, expected `0` to `3`, `s`, or `z`.",
+                variable, level
+            ),
+        )),
+    }
+}
+
+/// Reads whether Opus places every function and datum in its own section and
+/// whether it omits debug information from `LIBOPUS_GC_SECTIONS` and
+/// `LIBOPUS_STRIP_DEBUG` or their `OPUS_`-prefixed forms, both off by default.
+///
+/// Separate sections let the linker's `--gc-sections`, which Rust passes by
+/// default, drop the parts of Opus never called.
+fn size_options() -> Result<(bool, bool), BuildError> {
+    let mut options = [false; 2];
+
+    for (option, name) in options.iter_mut().zip(&["GC_SECTIONS", "STRIP_DEBUG"]) {
+        if let Some(value) = opus_env(name) {
+            *option = parse_bool(&format!("OPUS_{}", name), &value)
+                .map_err(|error| BuildError::new("Configuring the optimisation", error))?;
+        }
+    }
+
+    Ok((options[0], options[1]))
+}
+
+/// Returns the `-arch`-name of the Rust `architecture` when targeting an Apple
+/// platform, as Apple's compilers build for the host's architecture unless
+/// told otherwise.
+#[cfg(any(unix, target_env = "gnu", feature = "cmake"))]
+fn apple_architecture(architecture: &str) -> Option<&'static str> {
+    if env::var("CARGO_CFG_TARGET_VENDOR").ok()? != "apple" {
+        return None;
+    }
+
+    let target = env::var("TARGET").unwrap_or_default();
+
+    // watchOS' and older iOS' architectures are not told apart by Cargo.
+    for architecture in &["arm64_32", "armv7k", "armv7s"] {
+        if target.starts_with(architecture) {
+            return Some(architecture);
+        }
+    }
+
+    match architecture {
+        "x86_64" => Some("x86_64"),
+        "aarch64" => Some("arm64"),
+        "x86" => Some("i386"),
+        _ => None,
+    }
+}
+
+/// An Xcode SDK of Apple's embedded platforms iOS, tvOS, and watchOS, or of
+/// Mac Catalyst running iOS' APIs on macOS.
+#[cfg(any(unix, target_env = "gnu", feature = "cmake"))]
+struct AppleSdk {
+    /// The platform's name as known to CMake, such as `tvOS`.
+    platform: &'static str,
+    /// The SDK's name as known to `xcrun`, such as `appletvsimulator`.
+    name: &'static str,
+    /// The compiler flag selecting the minimum OS version, CMake derives it
+    /// by itself except for Mac Catalyst.
+    version_flag: &'static str,
+    /// The minimum OS version, read from the deployment target like `rustc`.
+    version: String,
+    /// Whether the SDK builds for Mac Catalyst, such as for
+    /// `aarch64-apple-ios-macabi`.
+    is_catalyst: bool,
+}
+
+#[cfg(any(unix, target_env = "gnu", feature = "cmake"))]
+impl AppleSdk {
+    /// The compiler flags selecting the minimum OS version, for Mac Catalyst
+    /// the whole target, e.g. `-target arm64-apple-ios13.1-macabi`.
+    fn version_flags(&self) -> String {
+        if self.is_catalyst {
+            format!(
+                "{} {}-apple-ios{}-macabi",
+                self.version_flag,
+                apple_architecture(&cargo_cfg("TARGET_ARCH")).unwrap_or("arm64"),
+                self.version
+            )
+        } else {
+            format!("{}={}", self.version_flag, self.version)
+        }
+    }
+}
+
+/// Returns the Xcode SDK when targeting iOS, tvOS, or watchOS,
+/// distinguishing devices from the simulator.
+///
+/// The minimum OS version is read from `IPHONEOS_DEPLOYMENT_TARGET`,
+/// `TVOS_DEPLOYMENT_TARGET`, or `WATCHOS_DEPLOYMENT_TARGET` respectively.
+///
+/// Mac Catalyst's `*-apple-ios-macabi`-targets build with the macOS SDK for
+/// iOS 13.1 or the `IPHONEOS_DEPLOYMENT_TARGET`.
+#[cfg(any(unix, target_env = "gnu", feature = "cmake"))]
+fn apple_sdk() -> Option<AppleSdk> {
+    if env::var("TARGET").unwrap_or_default().ends_with("-macabi") {
+        return Some(AppleSdk {
+            platform: "Mac Catalyst",
+            name: "macosx",
+            version_flag: "-target",
+            version: env::var("IPHONEOS_DEPLOYMENT_TARGET").unwrap_or_else(|_| "13.1".to_string()),
+            is_catalyst: true,
+        });
+    }
+
+    let (platform, device, simulator, version_variable, default_version) =
+        match cargo_cfg("TARGET_OS").as_str() {
+            "ios" => (
+                "iOS",
+                ("iphoneos", "-miphoneos-version-min"),
+                ("iphonesimulator", "-mios-simulator-version-min"),
+                "IPHONEOS_DEPLOYMENT_TARGET",
+                "10.0",
+            ),
+            "tvos" => (
+                "tvOS",
+                ("appletvos", "-mtvos-version-min"),
+                ("appletvsimulator", "-mtvos-simulator-version-min"),
+                "TVOS_DEPLOYMENT_TARGET",
+                "10.0",
+            ),
+            "watchos" => (
+                "watchOS",
+                ("watchos", "-mwatchos-version-min"),
+                ("watchsimulator", "-mwatchos-simulator-version-min"),
+                "WATCHOS_DEPLOYMENT_TARGET",
+                "5.0",
+            ),
+            _ => return None,
+        };
+
+    let target = env::var("TARGET").unwrap_or_default();
+    let is_simulator = target.ends_with("-sim")
+        || cargo_cfg("TARGET_ARCH") == "x86_64"
+        || cargo_cfg("TARGET_ARCH") == "x86";
+    let (name, version_flag) = if is_simulator { simulator } else { device };
+
+    Some(AppleSdk {
+        platform,
+        name,
+        version_flag,
+        version: env::var(version_variable).unwrap_or_else(|_| default_version.to_string()),
+        is_catalyst: false,
+    })
+}
+
+/// Locates the given Xcode SDK via `xcrun`.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+fn apple_sdk_path(sdk: &str) -> Result<String, BuildError> {
+    let output = run_step(
+        &format!("Locating the `{}`-SDK via `xcrun`", sdk),
+        Command::new("xcrun")
+            .arg("--sdk")
+            .arg(sdk)
+            .arg("--show-sdk-path"),
+    )?;
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Locates the Android NDK via `ANDROID_NDK_HOME`, `ANDROID_NDK_ROOT`, or the
+/// legacy `ANDROID_NDK` when targeting Android.
+///
+/// Fails if none is set or points to an existing directory.
+#[cfg(any(unix, target_env = "gnu", feature = "cmake"))]
+fn find_android_ndk() -> Result<Option<PathBuf>, BuildError> {
+    const STEP: &str = "Locating the Android NDK";
+
+    if cargo_cfg("TARGET_OS") != "android" {
+        return Ok(None);
+    }
+
+    let ndk = env::var("ANDROID_NDK_HOME")
+        .or_else(|_| env::var("ANDROID_NDK_ROOT"))
+        .or_else(|_| env::var("ANDROID_NDK"))
+        .map(PathBuf::from)
+        .map_err(|_| {
+            BuildError::new(
+                STEP,
+                "Targeting Android requires `ANDROID_NDK_HOME` to point to the Android NDK.",
+            )
+        })?;
+
+    if !ndk.is_dir() {
+        return Err(BuildError::new(
+            STEP,
+            format!(
+                "`ANDROID_NDK_HOME` does not point to a directory: {}",
+                ndk.display()
+            ),
+        ));
+    }
+
+    Ok(Some(ndk))
+}
+
+/// Locates the OpenHarmony SDK's `native` directory via `OHOS_SDK_NATIVE`, or
+/// via `OHOS_NDK_HOME` containing it, when targeting OpenHarmony.
+///
+/// Returns `None` if neither is set, leaving the compiler to `CC_<target>`.
+#[cfg(any(unix, target_env = "gnu", feature = "cmake"))]
+fn find_ohos_sdk() -> Result<Option<PathBuf>, BuildError> {
+    if cargo_cfg("TARGET_ENV") != "ohos" {
+        return Ok(None);
+    }
+
+    let native = match env::var("OHOS_SDK_NATIVE") {
+        Ok(native) => PathBuf::from(native),
+        Err(_) => match env::var("OHOS_NDK_HOME") {
+            Ok(sdk) => Path::new(&sdk).join("native"),
+            Err(_) => return Ok(None),
+        },
+    };
+
+    if !native.join("sysroot").is_dir() {
+        return Err(BuildError::new(
+            "Locating the OpenHarmony SDK",
+            format!(
+                "The OpenHarmony SDK's `native` directory lacks a sysroot: {}",
+                native.display()
+            ),
+        ));
+    }
+
+    Ok(Some(native))
+}
+
+/// Locates the Fuchsia SDK's sysroot for the targeted architecture, such as
+/// `<sdk>/arch/x64/sysroot`, via `LIBOPUS_FUCHSIA_SYSROOT` or
+/// `OPUS_FUCHSIA_SYSROOT` when targeting Fuchsia.
+///
+/// Fails if neither is set or points to an existing directory.
+fn fuchsia_sysroot() -> Result<Option<PathBuf>, BuildError> {
+    const STEP: &str = "Locating the Fuchsia sysroot";
+
+    if cargo_cfg("TARGET_OS") != "fuchsia" {
+        return Ok(None);
+    }
+
+    let sysroot = match opus_env("FUCHSIA_SYSROOT") {
+        Some(sysroot) => PathBuf::from(sysroot),
+        None => {
+            return Err(BuildError::new(
+                STEP,
+                "Targeting Fuchsia requires `LIBOPUS_FUCHSIA_SYSROOT` or \
+                 `OPUS_FUCHSIA_SYSROOT` to point to the Fuchsia SDK's sysroot.",
+            ))
+        }
+    };
+
+    if !sysroot.is_dir() {
+        return Err(BuildError::new(
+            STEP,
+            format!(
+                "The Fuchsia sysroot is not a directory: {}",
+                sysroot.display()
+            ),
+        ));
+    }
+
+    println!(
+        "cargo:info=Building Opus with the Fuchsia sysroot {}.",
+        sysroot.display()
+    );
+
+    Ok(Some(sysroot))
+}
+
+/// Returns the Android API level to build for, read from `ANDROID_PLATFORM`,
+/// `ANDROID_API_LEVEL`, or `CARGO_NDK_ANDROID_PLATFORM` set by `cargo-ndk`,
+/// and defaulting to 21.
+#[cfg(any(unix, target_env = "gnu", feature = "cmake"))]
+fn android_api_level() -> String {
+    env::var("ANDROID_PLATFORM")
+        .or_else(|_| env::var("ANDROID_API_LEVEL"))
+        .or_else(|_| env::var("CARGO_NDK_ANDROID_PLATFORM"))
+        .map(|level| level.trim_start_matches("android-").to_string())
+        .unwrap_or_else(|_| "21".to_string())
+}
+
+/// Returns the NDK's compiler prefix, the GNU host triple, and the ABI-name of
+/// the targeted Android architecture.
+#[cfg(any(unix, target_env = "gnu", feature = "cmake"))]
+fn android_triples() -> Result<(&'static str, &'static str, &'static str), BuildError> {
+    match cargo_cfg("TARGET_ARCH").as_str() {
+        "aarch64" => Ok(("aarch64-linux-android", "aarch64-linux-android", "arm64-v8a")),
+        "arm" => Ok((
+            "armv7a-linux-androideabi",
+            "arm-linux-androideabi",
+            "armeabi-v7a",
+        )),
+        "x86" => Ok(("i686-linux-android", "i686-linux-android", "x86")),
+        "x86_64" => Ok(("x86_64-linux-android", "x86_64-linux-android", "x86_64")),
+        architecture => Err(BuildError::new(
+            "Targeting Android",
+            format!("Unsupported Android architecture: {}", architecture),
+        )),
+    }
+}
+
+/// The NDK's LLVM-tools building Opus for Android.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+struct AndroidToolchain {
+    c_compiler: PathBuf,
+    archiver: PathBuf,
+    ranlib: PathBuf,
+    host: &'static str,
+}
+
+/// Returns the compiler launcher caching the C compilation, such as `ccache`
+/// or `sccache`, set via `LIBOPUS_CC_WRAPPER` or `OPUS_CC_WRAPPER`.
+///
+/// Defaults to `RUSTC_WRAPPER` if it is `sccache`, which caches C as well,
+/// unless the variable is set but empty.
+fn compiler_wrapper() -> Option<String> {
+    match opus_env("CC_WRAPPER") {
+        Some(wrapper) => Some(wrapper).filter(|wrapper| !wrapper.is_empty()),
+        None => env::var("RUSTC_WRAPPER").ok().filter(|wrapper| {
+            Path::new(wrapper)
+                .file_stem()
+                .map_or(false, |name| name == "sccache")
+        }),
+    }
+}
+
+/// Prefixes the C `compiler` with the `compiler_wrapper`, if any.
+fn wrapped_compiler(compiler: &Path) -> std::ffi::OsString {
+    match compiler_wrapper() {
+        Some(wrapper) => prefixed_path(&format!("{} ", wrapper), compiler),
+        None => compiler.as_os_str().to_os_string(),
+    }
+}
+
+/// Writes a script to `build_directory` running the `wrapped_compiler`, as the
+/// `cc`-crate runs a compiler passed via `cc::Build::compiler` as is, unlike
+/// one prefixed by a launcher in `CC`.
+fn compiler_launcher(
+    build_directory: &Path,
+    wrapped_compiler: &std::ffi::OsStr,
+) -> Result<PathBuf, BuildError> {
+    let (launcher_path, launcher) = if cfg!(windows) {
+        (
+            build_directory.join("opus-cc.cmd"),
+            format!("@{} %*\r\n", wrapped_compiler.to_string_lossy()),
+        )
+    } else {
+        (
+            build_directory.join("opus-cc"),
+            format!("#!/bin/sh\nexec {} \"$@\"\n", wrapped_compiler.to_string_lossy()),
+        )
+    };
+    let write_error = |error: std::io::Error| {
+        BuildError::new(
+            "Wrapping the compiler",
+            format!("Could not write `{}`: {}", launcher_path.display(), error),
+        )
+    };
+
+    std::fs::write(&launcher_path, launcher).map_err(write_error)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        std::fs::set_permissions(&launcher_path, std::fs::Permissions::from_mode(0o755))
+            .map_err(write_error)?;
+    }
+
+    Ok(launcher_path)
+}
+
+/// Reads a target-specific tool following the `cc`-crate's conventions, such
+/// as `CC_aarch64_linux_android`, which `cargo-ndk` uses to select the NDK's
+/// compiler, or `TARGET_CC` when the target differs from the host.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+fn target_tool(tool: &str) -> Option<PathBuf> {
+    let target = env::var("TARGET").ok()?;
+    let is_targeting_host = env::var("HOST").ok().as_ref() == Some(&target);
+
+    env::var(format!("{}_{}", tool, target.replace('-', "_")))
+        .or_else(|_| env::var(format!("{}_{}", tool, target)))
+        .or_else(|error| {
+            if is_targeting_host {
+                Err(error)
+            } else {
+                env::var(format!("TARGET_{}", tool))
+            }
+        })
+        .ok()
+        .map(PathBuf::from)
+}
+
+/// Locates the NDK's tools for the targeted Android architecture and API
+/// level.
+///
+/// Compilers and archivers configured per target, e.g. by `cargo-ndk`, take
+/// precedence over the ones derived from the NDK's layout.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+fn android_toolchain(ndk: &Path) -> Result<AndroidToolchain, BuildError> {
+    let host = env::var("HOST").unwrap_or_default();
+
+    let (host_tag, script_extension) = if host.contains("windows") {
+        ("windows-x86_64", ".cmd")
+    } else if host.contains("darwin") {
+        ("darwin-x86_64", "")
+    } else {
+        ("linux-x86_64", "")
+    };
+
+    let bin_directory = ndk
+        .join("toolchains")
+        .join("llvm")
+        .join("prebuilt")
+        .join(host_tag)
+        .join("bin");
+
+    if !bin_directory.is_dir() {
+        return Err(BuildError::new(
+            "Locating the Android NDK's toolchain",
+            format!(
+                "The Android NDK misses its LLVM-toolchain, expected: {}",
+                bin_directory.display()
+            ),
+        ));
+    }
+
+    let (compiler_prefix, host_triple, _) = android_triples()?;
+
+    let c_compiler = target_tool("CC").unwrap_or_else(|| {
+        bin_directory.join(format!(
+            "{}{}-clang{}",
+            compiler_prefix,
+            android_api_level(),
+            script_extension
+        ))
+    });
+
+    if !c_compiler.is_file() {
+        return Err(BuildError::new(
+            "Locating the Android NDK's toolchain",
+            format!(
+                "The Android NDK lacks a compiler for API level {}, expected: {}",
+                android_api_level(),
+                c_compiler.display()
+            ),
+        ));
+    }
+
+    Ok(AndroidToolchain {
+        c_compiler,
+        archiver: target_tool("AR").unwrap_or_else(|| bin_directory.join("llvm-ar")),
+        ranlib: bin_directory.join("llvm-ranlib"),
+        host: host_triple,
+    })
+}
+
+/// A GNU cross-toolchain building Opus for a target other than the host.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+struct CrossToolchain {
+    c_compiler: PathBuf,
+    archiver: PathBuf,
+    ranlib: PathBuf,
+    host: String,
+    /// Flags selecting the target and sysroot for compiling and linking.
+    flags: String,
+}
+
+/// Derives the cross-toolchain for `target` from `CROSS_COMPILE`, e.g.
+/// `aarch64-linux-gnu-`, or else its GNU host triple, e.g. resulting in
+/// `aarch64-linux-gnu-gcc` for `aarch64-unknown-linux-gnu`.
+///
+/// The `*-windows-gnullvm`-targets use [llvm-mingw]'s Clang, e.g.
+/// `aarch64-w64-mingw32-clang`, instead of GCC. Fuchsia uses the unprefixed
+/// `clang`, `llvm-ar`, and `llvm-ranlib`, as its Clang handles every target.
+/// QNX Neutrino uses the tools of its SDP in `QNX_HOST`, e.g.
+/// `ntoaarch64-gcc`, which find the target's headers and libraries via
+/// `QNX_TARGET`, both set by `qnxsdp-env.sh`.
+///
+/// Tools configured per target, e.g. via `CC_aarch64_unknown_linux_gnu` or
+/// `TARGET_CC`, take precedence.
+///
+/// [llvm-mingw]: https://github.com/mstorsjo/llvm-mingw
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+fn cross_toolchain(target: &str) -> CrossToolchain {
+    let host = gnu_host_triple(target);
+
+    if target.ends_with("-fuchsia") {
+        return CrossToolchain {
+            c_compiler: target_tool("CC").unwrap_or_else(|| PathBuf::from("clang")),
+            archiver: target_tool("AR").unwrap_or_else(|| PathBuf::from("llvm-ar")),
+            ranlib: target_tool("RANLIB").unwrap_or_else(|| PathBuf::from("llvm-ranlib")),
+            host,
+            flags: String::new(),
+        };
+    }
+
+    let qnx_prefix = qnx_tool_prefix(&cargo_cfg("TARGET_ARCH")).filter(|_| target.contains("-nto-"));
+
+    if let Some(qnx_prefix) = qnx_prefix {
+        let bin_directory = env::var_os("QNX_HOST")
+            .map(|qnx_host| Path::new(&qnx_host).join("usr").join("bin"))
+            .unwrap_or_default();
+
+        if env::var_os("QNX_HOST").is_none() || env::var_os("QNX_TARGET").is_none() {
+            println!(
+                "cargo:info=`QNX_HOST` or `QNX_TARGET` is unset, source `qnxsdp-env.sh` \
+                 of the QNX SDP to locate its toolchain."
+            );
+        }
+
+        let tool = |name: &str| bin_directory.join(format!("{}-{}", qnx_prefix, name));
+
+        return CrossToolchain {
+            c_compiler: target_tool("CC").unwrap_or_else(|| tool("gcc")),
+            archiver: target_tool("AR").unwrap_or_else(|| tool("ar")),
+            ranlib: target_tool("RANLIB").unwrap_or_else(|| tool("ranlib")),
+            host,
+            flags: String::new(),
+        };
+    }
+
+    let prefix = env::var("CROSS_COMPILE").unwrap_or_else(|_| format!("{}-", host));
+    let tool = |name: &str| PathBuf::from(format!("{}{}", prefix, name));
+    let c_compiler = if target.ends_with("-windows-gnullvm") {
+        "clang"
+    } else {
+        "gcc"
+    };
+
+    CrossToolchain {
+        c_compiler: target_tool("CC").unwrap_or_else(|| tool(c_compiler)),
+        archiver: target_tool("AR").unwrap_or_else(|| tool("ar")),
+        ranlib: target_tool("RANLIB").unwrap_or_else(|| tool("ranlib")),
+        host,
+        flags: String::new(),
+    }
+}
+
+/// Builds 32-bit x86 on a 64-bit x86 host with its multilib compiler, `CC` or
+/// `cc`, passing `-m32` along with the user's `CFLAGS` and `LDFLAGS`, unless
+/// `CROSS_COMPILE` or a compiler for the target is configured or the GNU
+/// cross-compiler, e.g. `i686-linux-gnu-gcc`, is installed.
+///
+/// A compiler lacking the 32-bit C library, e.g. without `gcc-multilib`, is
+/// rejected here, as `configure` would fail obscurely or link 64-bit objects.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+fn multilib_toolchain(
+    host: Option<&str>,
+    target: &str,
+) -> Result<Option<CrossToolchain>, BuildError> {
+    if !is_multilib_target(host, target)
+        || env::var_os("CROSS_COMPILE").is_some()
+        || target_tool("CC").is_some()
+    {
+        return Ok(None);
+    }
+
+    let gnu_host = gnu_host_triple(target);
+    let cross_compiler = format!("{}-gcc", gnu_host);
+
+    if Command::new(&cross_compiler).arg("--version").output().is_ok() {
+        return Ok(None);
+    }
+
+    let c_compiler = PathBuf::from(env::var_os("CC").unwrap_or_else(|| "cc".into()));
+
+    // Compilers print the bare file-name for files they cannot find.
+    let c_library = Command::new(&c_compiler)
+        .args(&["-m32", "-print-file-name=crt1.o"])
+        .output()
+        .ok()
+        .map(|output| PathBuf::from(String::from_utf8_lossy(&output.stdout).trim()))
+        .filter(|c_library| c_library.is_absolute() && c_library.is_file());
+
+    if c_library.is_none() {
+        return Err(BuildError::new(
+            "Locating the multilib toolchain",
+            format!(
+                "`{} -m32` found no 32-bit C library, install the multilib toolchain, \
+                 e.g. `gcc-multilib` (Debian, Ubuntu) or `glibc-devel.i686` (Fedora), \
+                 or the cross-compiler `{}`.",
+                c_compiler.display(),
+                cross_compiler
+            ),
+        ));
+    }
+
+    Ok(Some(CrossToolchain {
+        c_compiler,
+        archiver: target_tool("AR").unwrap_or_else(|| PathBuf::from("ar")),
+        ranlib: target_tool("RANLIB").unwrap_or_else(|| PathBuf::from("ranlib")),
+        host: gnu_host,
+        flags: "-m32".to_string(),
+    }))
+}
+
+/// Derives the toolchain for an OpenHarmony `target` from the SDK's `native`
+/// directory: its Clang, e.g. targeting `aarch64-linux-ohos`, and sysroot.
+///
+/// Tools configured per target take precedence, then only the sysroot is
+/// passed on.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+fn ohos_toolchain(native: &Path, target: &str) -> CrossToolchain {
+    let host = gnu_host_triple(target);
+    let bin_directory = native.join("llvm").join("bin");
+    let compiler = target_tool("CC");
+    let target_flag = if compiler.is_none() {
+        format!("--target={} ", host)
+    } else {
+        String::new()
+    };
+
+    CrossToolchain {
+        c_compiler: compiler.unwrap_or_else(|| bin_directory.join("clang")),
+        archiver: target_tool("AR").unwrap_or_else(|| bin_directory.join("llvm-ar")),
+        ranlib: target_tool("RANLIB").unwrap_or_else(|| bin_directory.join("llvm-ranlib")),
+        flags: format!(
+            "{}--sysroot={}",
+            target_flag,
+            native.join("sysroot").display()
+        ),
+        host,
+    }
+}
+
+/// Describes the machine `library_path` was built for along with the one
+/// expected for the targeted architecture if they differ, e.g.
+/// `ELF machine 0x3e instead of 0xb7`, or for ELF their word sizes, e.g.
+/// `64-bit instead of 32-bit`.
+///
+/// ELF, Mach-O, and COFF library-files are verified, others as well as
+/// universal Mach-O binaries are not.
+fn foreign_machine(library_path: &Path) -> Option<String> {
+    let architecture = cargo_cfg("TARGET_ARCH");
+    let library = std::fs::read(library_path).ok()?;
+
+    let (format, machine, expected_machine) = if let Some(machine) = elf_machine(&library) {
+        // A matching machine may still differ in word size, e.g. x32 objects.
+        let word_size = elf_word_size(&library)?;
+        let expected_word_size = cargo_cfg("TARGET_POINTER_WIDTH").parse().ok()?;
+
+        if word_size != expected_word_size {
+            return Some(format!(
+                "{}-bit instead of {}-bit",
+                word_size, expected_word_size
+            ));
+        }
+
+        let expected_machine = expected_elf_machine(&architecture)?;
+
+        ("ELF machine", u32::from(machine), u32::from(expected_machine))
+    } else if let Some(cpu_type) = macho_cpu_type(&library) {
+        ("Mach-O CPU-type", cpu_type, expected_macho_cpu_type(&architecture)?)
+    } else {
+        let machine = coff_machine(&library)?;
+        let expected_machine = expected_coff_machine(&architecture)?;
+
+        ("COFF machine", u32::from(machine), u32::from(expected_machine))
+    };
+
+    if machine != expected_machine {
+        Some(format!(
+            "{} {:#x} instead of {:#x}",
+            format, machine, expected_machine
+        ))
+    } else {
+        None
+    }
+}
+
+/// Verifies the Opus `lib_name` in `lib_directory`, whether built or found,
+/// was built for the targeted architecture, catching a host compiler used in
+/// place of the cross-compiler or a prebuilt Opus of another architecture
+/// before the final link fails with cryptic errors.
+fn verify_architecture(
+    lib_name: &str,
+    is_static: bool,
+    lib_directory: &Path,
+) -> Result<(), BuildError> {
+    let library_path = library_file_names(
+        lib_name,
+        is_static,
+        &cargo_cfg("TARGET_OS"),
+        &cargo_cfg("TARGET_ENV"),
+    )
+    .into_iter()
+    .map(|file_name| lib_directory.join(file_name))
+    .find(|library_path| library_path.is_file());
+
+    let library_path = match library_path {
+        Some(library_path) => library_path,
+        None => return Ok(()),
+    };
+
+    match foreign_machine(&library_path) {
+        Some(machine) => Err(BuildError::new(
+            "Verifying the architecture of Opus",
+            format!(
+                "{} was built for {} (`{}`), make sure the cross-compiler for the \
+                 target is installed, set `CC_<target>`, or point `OPUS_LIB_DIR` to an \
+                 Opus built for the target.",
+                library_path.display(),
+                machine,
+                cargo_cfg("TARGET_ARCH")
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Symbols of the bindings a dynamic Opus must export.
+const EXPECTED_EXPORTS: &[&str] = &[
+    "opus_strerror",
+    "opus_get_version_string",
+    "opus_encoder_get_size",
+    "opus_encoder_create",
+    "opus_encoder_init",
+    "opus_encode",
+    "opus_encode_float",
+    "opus_encoder_destroy",
+    "opus_encoder_ctl",
+    "opus_decoder_get_size",
+    "opus_decoder_create",
+    "opus_decoder_init",
+    "opus_decode",
+    "opus_decode_float",
+    "opus_decoder_ctl",
+    "opus_decoder_destroy",
+    "opus_packet_parse",
+    "opus_packet_get_bandwidth",
+    "opus_packet_get_samples_per_frame",
+    "opus_packet_get_nb_channels",
+    "opus_packet_get_nb_frames",
+    "opus_packet_get_nb_samples",
+    "opus_decoder_get_nb_samples",
+    "opus_pcm_soft_clip",
+    "opus_repacketizer_get_size",
+    "opus_repacketizer_init",
+    "opus_repacketizer_create",
+    "opus_repacketizer_destroy",
+    "opus_repacketizer_cat",
+    "opus_repacketizer_out_range",
+    "opus_repacketizer_get_nb_frames",
+    "opus_repacketizer_out",
+    "opus_packet_pad",
+    "opus_packet_unpad",
+    "opus_multistream_packet_pad",
+    "opus_multistream_packet_unpad",
+    "opus_multistream_encoder_get_size",
+    "opus_multistream_surround_encoder_get_size",
+    "opus_multistream_encoder_create",
+    "opus_multistream_surround_encoder_create",
+    "opus_multistream_encoder_init",
+    "opus_multistream_surround_encoder_init",
+    "opus_multistream_encode",
+    "opus_multistream_encode_float",
+    "opus_multistream_encoder_destroy",
+    "opus_multistream_encoder_ctl",
+    "opus_multistream_decoder_get_size",
+    "opus_multistream_decoder_create",
+    "opus_multistream_decoder_init",
+    "opus_multistream_decode",
+    "opus_multistream_decode_float",
+    "opus_multistream_decoder_ctl",
+    "opus_multistream_decoder_destroy",
+];
+
+/// Symbols of the `opus_custom_*`-functions bound with the `custom`-feature,
+/// which only an Opus built with custom modes exports.
+const CUSTOM_EXPORTS: &[&str] = &[
+    "opus_custom_mode_create",
+    "opus_custom_mode_destroy",
+    "opus_custom_encoder_get_size",
+    "opus_custom_encoder_init",
+    "opus_custom_encoder_create",
+    "opus_custom_encoder_destroy",
+    "opus_custom_encode_float",
+    "opus_custom_encode",
+    "opus_custom_encoder_ctl",
+    "opus_custom_decoder_get_size",
+    "opus_custom_decoder_init",
+    "opus_custom_decoder_create",
+    "opus_custom_decoder_destroy",
+    "opus_custom_decode_float",
+    "opus_custom_decode",
+    "opus_custom_decoder_ctl",
+];
+
+/// Symbols of the functions Opus exports since 1.5, bound with the
+/// `v1_5`-feature.
+const V1_5_EXPORTS: &[&str] = &["opus_packet_has_lbrr"];
+
+/// Symbols of the `opus_projection_*`-functions bound with the
+/// `projection`-feature, which Opus exports since 1.3.
+const PROJECTION_EXPORTS: &[&str] = &[
+    "opus_projection_ambisonics_encoder_get_size",
+    "opus_projection_ambisonics_encoder_create",
+    "opus_projection_ambisonics_encoder_init",
+    "opus_projection_encode",
+    "opus_projection_encode_float",
+    "opus_projection_encoder_destroy",
+    "opus_projection_encoder_ctl",
+    "opus_projection_decoder_get_size",
+    "opus_projection_decoder_create",
+    "opus_projection_decoder_init",
+    "opus_projection_decode",
+    "opus_projection_decode_float",
+    "opus_projection_decoder_ctl",
+    "opus_projection_decoder_destroy",
+];
+
+/// Symbols of the `opus_dred_*`-functions bound with the `dred`-feature.
+const DRED_EXPORTS: &[&str] = &[
+    "opus_dred_decoder_get_size",
+    "opus_dred_decoder_create",
+    "opus_dred_decoder_init",
+    "opus_dred_decoder_destroy",
+    "opus_dred_decoder_ctl",
+    "opus_dred_get_size",
+    "opus_dred_alloc",
+    "opus_dred_free",
+    "opus_dred_parse",
+    "opus_dred_process",
+    "opus_decoder_dred_decode",
+    "opus_decoder_dred_decode_float",
+];
+
+/// The `EXPECTED_EXPORTS` of the bound functions, including the
+/// `V1_5_EXPORTS`, `CUSTOM_EXPORTS`, `PROJECTION_EXPORTS`, and `DRED_EXPORTS`
+/// with the `v1_5`-, `custom`-, `projection`-, and `dred`-feature and lacking
+/// the float API with the `disable-float-api`-feature.
+fn expected_exports() -> Vec<&'static str> {
+    let v1_5_exports = if cfg!(feature = "v1_5") {
+        V1_5_EXPORTS
+    } else {
+        &[]
+    };
+    let custom_exports = if cfg!(feature = "custom") {
+        CUSTOM_EXPORTS
+    } else {
+        &[]
+    };
+    let projection_exports = if cfg!(feature = "projection") {
+        PROJECTION_EXPORTS
+    } else {
+        &[]
+    };
+    let dred_exports = if cfg!(feature = "dred") {
+        DRED_EXPORTS
+    } else {
+        &[]
+    };
+
+    EXPECTED_EXPORTS
+        .iter()
+        .chain(v1_5_exports)
+        .chain(custom_exports)
+        .chain(projection_exports)
+        .chain(dred_exports)
+        .filter(|symbol| {
+            !cfg!(feature = "disable-float-api")
+                || !(symbol.ends_with("_float") || **symbol == "opus_pcm_soft_clip")
+        })
+        .cloned()
+        .collect()
+}
+
+/// Returns the name of the Opus-function `symbol` in the linked library,
+/// prefixed with `SYMBOL_PREFIX` by the `prefix-symbols`-feature.
+fn linked_symbol(symbol: &str) -> String {
+    if cfg!(feature = "prefix-symbols") {
+        format!("{}{}", SYMBOL_PREFIX, symbol)
+    } else {
+        symbol.to_string()
+    }
+}
+
+/// Rejects the `prefix-symbols`-feature along with features linking or loading
+/// an Opus not built by `prefix_symbols`.
+fn check_symbol_prefixing() -> Result<(), BuildError> {
+    const STEP: &str = "Prefixing the symbols of Opus";
+
+    if !cfg!(feature = "prefix-symbols") {
+        return Ok(());
+    }
+
+    let conflict = if cfg!(feature = "dlopen") {
+        Some("the `dlopen`-feature loading a system Opus")
+    } else if cfg!(feature = "raw-dylib") {
+        Some("the `raw-dylib`-feature linking a system Opus")
+    } else if cfg!(feature = "opusfile") || cfg!(feature = "opusenc") {
+        Some("the `opusfile`- and `opusenc`-feature, whose libraries call the unprefixed Opus")
+    } else if cargo_cfg("TARGET_ENV") == "msvc" {
+        Some("MSVC, lacking `objcopy`")
+    } else {
+        None
+    };
+
+    match conflict {
+        Some(conflict) => Err(BuildError::new(
+            STEP,
+            format!(
+                "The `prefix-symbols`-feature does not support {}.",
+                conflict
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Prefixes every symbol the static Opus in `lib_directory` defines with
+/// `SYMBOL_PREFIX` via `objcopy --redefine-syms`, if the `prefix-symbols`-
+/// feature is enabled, hence other copies of Opus in the same binary do not
+/// clash with it.
+///
+/// The symbols are listed via `nm`, overridable via the `NM` environment
+/// variable, and `OBJCOPY` overrides `objcopy`, or `llvm-objcopy` for Apple.
+#[cfg(any(unix, target_env = "gnu", feature = "cmake"))]
+fn prefix_symbols(lib_directory: &Path, is_static: bool) -> Result<(), BuildError> {
+    const STEP: &str = "Prefixing the symbols of Opus";
+
+    if !cfg!(feature = "prefix-symbols") {
+        return Ok(());
+    }
+
+    if !is_static {
+        return Err(BuildError::new(
+            STEP,
+            "The `prefix-symbols`-feature requires linking Opus statically.",
+        ));
+    }
+
+    let archive_path = lib_directory.join("libopus.a");
+    let is_apple = cargo_cfg("TARGET_VENDOR") == "apple";
+
+    let mut nm_command = Command::new(env::var("NM").unwrap_or_else(|_| "nm".to_string()));
+
+    if is_apple {
+        nm_command.arg("-gU");
+    } else {
+        nm_command.arg("-g").arg("--defined-only");
+    }
+
+    let output = run_step(STEP, nm_command.arg(&archive_path))?;
+    let symbols = defined_symbols(&String::from_utf8_lossy(&output.stdout));
+
+    if symbols.is_empty() {
+        return Err(BuildError::new(
+            STEP,
+            format!("`nm` listed no symbols of `{}`.", archive_path.display()),
+        ));
+    }
+
+    let has_underscore =
+        is_apple || (cargo_cfg("TARGET_OS") == "windows" && cargo_cfg("TARGET_ARCH") == "x86");
+    // `objcopy` rejects a last line lacking its newline.
+    let redefinitions: String = symbols
+        .iter()
+        .map(|symbol| symbol_redefinition(symbol, SYMBOL_PREFIX, has_underscore) + "\n")
+        .collect();
+
+    let redefinitions_path = lib_directory.join("opus_symbols.txt");
+
+    std::fs::write(&redefinitions_path, redefinitions).map_err(|error| {
+        BuildError::new(
+            STEP,
+            format!(
+                "Could not write `{}`: {}",
+                redefinitions_path.display(),
+                error
+            ),
+        )
+    })?;
+
+    let objcopy_program = env::var("OBJCOPY")
+        .unwrap_or_else(|_| if is_apple { "llvm-objcopy" } else { "objcopy" }.to_string());
+
+    run_step(
+        STEP,
+        Command::new(objcopy_program)
+            .arg(prefixed_path("--redefine-syms=", &redefinitions_path))
+            .arg(&archive_path),
+    )?;
+
+    println!(
+        "cargo:info=Prefixed {} symbols of Opus with `{}`.",
+        symbols.len(),
+        SYMBOL_PREFIX
+    );
+
+    Ok(())
+}
+
+/// Verifies the dynamic Opus installed in `install_directory` exports all
+/// `EXPECTED_EXPORTS` if `LIBOPUS_VERIFY_EXPORTS` or `OPUS_VERIFY_EXPORTS` is
+/// set.
+///
+/// Exports are listed via `dumpbin /exports` for MSVC and `nm` otherwise,
+/// the latter can be overridden via the `NM` environment variable.
+#[cfg(any(unix, target_env = "gnu", feature = "cmake"))]
+fn verify_exports(install_directory: &Path) -> Result<(), BuildError> {
+    const STEP: &str = "Verifying the exports of Opus";
+
+    if opus_env("VERIFY_EXPORTS").is_none() {
+        return Ok(());
+    }
+
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+    let target_env = env::var("CARGO_CFG_TARGET_ENV").unwrap_or_default();
+    let nm_program = env::var("NM").unwrap_or_else(|_| "nm".to_string());
+
+    let (library_directory, is_library): (_, fn(&str) -> bool) = match target_os.as_str() {
+        "windows" => (install_directory.join("bin"), |name| {
+            name.ends_with(".dll") && (name.starts_with("opus") || name.starts_with("libopus"))
+        }),
+        "macos" | "ios" => (install_directory.join("lib"), |name| {
+            name.starts_with("libopus") && name.ends_with(".dylib")
+        }),
+        _ => (install_directory.join("lib"), |name| name == "libopus.so"),
+    };
+
+    let library_path = std::fs::read_dir(&library_directory)
+        .ok()
+        .and_then(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .find(|entry| is_library(&entry.file_name().to_string_lossy()))
+        })
+        .map(|entry| entry.path())
+        .ok_or_else(|| {
+            BuildError::new(
+                STEP,
+                format!(
+                    "Could not find the dynamic Opus to verify in `{}`.",
+                    library_directory.display()
+                ),
+            )
+        })?;
+
+    let mut command = if target_env == "msvc" {
+        let mut command = Command::new("dumpbin");
+        command.arg("/exports");
+
+        command
+    } else if target_os == "macos" || target_os == "ios" {
+        let mut command = Command::new(&nm_program);
+        command.arg("-gU");
+
+        command
+    } else if target_os == "windows" {
+        let mut command = Command::new(&nm_program);
+        command.arg("-g").arg("--defined-only");
+
+        command
+    } else {
+        let mut command = Command::new(&nm_program);
+        command.arg("-D").arg("--defined-only");
+
+        command
+    };
+
+    let output = run_step(STEP, command.arg(&library_path))?;
+
+    let listing = String::from_utf8_lossy(&output.stdout);
+    let exports: std::collections::HashSet<&str> = listing
+        .split_whitespace()
+        .map(|symbol| symbol.trim_start_matches('_'))
+        .collect();
+
+    let missing_exports: Vec<&str> = expected_exports()
+        .iter()
+        .filter(|symbol| !exports.contains(symbol.trim_start_matches('_')))
+        .cloned()
+        .collect();
+
+    if !missing_exports.is_empty() {
+        return Err(BuildError::new(
+            STEP,
+            format!(
+                "`{}` does not export the following symbols: {}",
+                library_path.display(),
+                missing_exports.join(", ")
+            ),
+        ));
+    }
+
+    println!(
+        "cargo:info=Verified exports of dynamic Opus: {:?}.",
+        library_path.to_string_lossy()
+    );
+
+    Ok(())
+}
+
+/// Creates the directory in `OUT_DIR` holding the C programs probing Opus.
+fn create_probe_directory(step: &str) -> Result<PathBuf, BuildError> {
+    let probe_directory = PathBuf::from(
+        env::var("OUT_DIR").expect("Environment variable `OUT_DIR` is missing."),
+    )
+    .join("probe");
+
+    std::fs::create_dir_all(&probe_directory).map_err(|error| {
+        BuildError::new(step, format!("Could not create `{}`: {}", probe_directory.display(), error))
+    })?;
+
+    Ok(probe_directory)
+}
+
+/// Compiles and links a C program against the resolved Opus to ensure it
+/// provides all `EXPECTED_EXPORTS`, failing the build instead of the final
+/// link of dependents otherwise.
+///
+/// Unless cross-compiling, the program is run to print
+/// `opus_get_version_string`, whose `-fixed`-suffix reveals a fixed-point
+/// Opus. Setting `LIBOPUS_NO_PROBE` or `OPUS_NO_PROBE` skips the probe, it is
+/// skipped as well if no C compiler can be found. The library-file's
+/// architecture is verified beforehand either way, see `verify_architecture`.
+fn probe_opus(lib_name: &str, is_static: bool, lib_directory: &Path) -> Result<(), BuildError> {
+    const STEP: &str = "Probing the linked Opus";
+
+    verify_architecture(lib_name, is_static, lib_directory)?;
+
+    if opus_env("NO_PROBE").is_some() {
+        println!("cargo:info=Bypassed probing Opus.");
+
+        return Ok(());
+    }
+
+    if is_bare_wasm() {
+        println!("cargo:info=Targeting bare WebAssembly, skipping probing Opus.");
+
+        return Ok(());
+    }
+
+    let compiler = match cc::Build::new().cargo_metadata(false).try_get_compiler() {
+        Ok(compiler) => compiler,
+        Err(error) => {
+            println!("cargo:info=Found no C compiler, skipping probing Opus: {}", error);
+
+            return Ok(());
+        }
+    };
+
+    let probe_directory = create_probe_directory(STEP)?;
+
+    let mut declarations = String::new();
+    let mut references = String::new();
+
+    for symbol in expected_exports()
+        .iter()
+        .filter(|&&symbol| symbol != "opus_get_version_string")
+    {
+        // Like autoconf's checks, declaring an incorrect prototype suffices
+        // to take a symbol's address.
+        let symbol = linked_symbol(symbol);
+
+        declarations.push_str(&format!("extern char {}(void);\n", symbol));
+        references.push_str(&format!("        (void *) &{},\n", symbol));
+    }
+
+    let source_path = probe_directory.join("opus_probe.c");
+    let source = format!(
+        "#include <stdio.h>\n\
+         extern const char *{0}(void);\n\
+         {1}\n\
+         int main(void) {{\n    \
+             void *volatile symbols[] = {{\n{2}    }};\n    \
+             (void) symbols;\n    \
+             puts({0}());\n    \
+             return 0;\n\
+         }}\n",
+        linked_symbol("opus_get_version_string"),
+        declarations,
+        references
+    );
+
+    std::fs::write(&source_path, source).map_err(|error| {
+        BuildError::new(STEP, format!("Could not write `{}`: {}", source_path.display(), error))
+    })?;
+
+    let executable_path = probe_directory.join(if cargo_cfg("TARGET_OS") == "windows" {
+        "opus_probe.exe"
+    } else {
+        "opus_probe"
+    });
+
+    // `pkg-config` omits the directories the linker searches anyway.
+    let is_searched_by_default = lib_directory.as_os_str().is_empty();
+
+    let mut command = compiler.to_command();
+    command.current_dir(&probe_directory).arg(&source_path);
+
+    if compiler.is_like_msvc() {
+        command
+            .arg(prefixed_path("/Fe", &executable_path))
+            .arg("/link");
+
+        if !is_searched_by_default {
+            command.arg(prefixed_path("/LIBPATH:", lib_directory));
+        }
+
+        command.arg(format!("{}.lib", lib_name));
+    } else {
+        command
+            .arg("-o")
+            .arg(&executable_path)
+            .args(sanitizer_c_flags());
+
+        if is_searched_by_default {
+            command.arg(format!("-l{}", lib_name));
+        } else if is_static {
+            // Naming the archive prevents the linker from preferring a
+            // shared Opus in the same directory.
+            command.arg(lib_directory.join(format!("lib{}.a", lib_name)));
+        } else {
+            command
+                .arg(prefixed_path("-L", lib_directory))
+                .arg(format!("-l{}", lib_name));
+        }
+
+        if cargo_cfg("TARGET_FAMILY").split(',').any(|family| family == "unix")
+            && cargo_cfg("TARGET_VENDOR") != "apple"
+        {
+            command.arg("-lm");
+        }
+    }
+
+    let output = match command.output() {
+        Ok(output) => output,
+        Err(error) => {
+            println!("cargo:info=Could not run the C compiler, skipping probing Opus: {}", error);
+
+            return Ok(());
+        }
+    };
+
+    if !output.status.success() {
+        // MSVC's tools report errors on the standard output.
+        let mut linker_output = output.stdout;
+        linker_output.extend_from_slice(&output.stderr);
+
+        return Err(BuildError::new(
+            STEP,
+            format!(
+                "The Opus in `{}` lacks symbols used by the bindings or cannot \
+                 be linked.",
+                lib_directory.display()
+            ),
+        )
+        .with_stderr(&linker_output));
+    }
+
+    let host = env::var("HOST").ok();
+    let target = env::var("TARGET").unwrap_or_default();
+
+    if is_cross_compiled(host.as_ref().map(String::as_str), &target) {
+        println!("cargo:info=Cross-compiling, skipping running the Opus probe.");
+
+        if minimum_opus_version().is_some() {
+            println!("cargo:info=Cross-compiling, the minimum Opus version cannot be verified.");
+        }
+
+        return Ok(());
+    }
+
+    let library_variable = match cargo_cfg("TARGET_OS").as_str() {
+        "windows" => "PATH",
+        "macos" | "ios" => "DYLD_LIBRARY_PATH",
+        _ => "LD_LIBRARY_PATH",
+    };
+
+    let existing_paths = env::var_os(library_variable).unwrap_or_default();
+    let library_paths = env::join_paths(
+        std::iter::once(lib_directory.to_path_buf()).chain(env::split_paths(&existing_paths)),
+    )
+    .unwrap_or(existing_paths);
+
+    match Command::new(&executable_path)
+        .env(library_variable, library_paths)
+        .output()
+    {
+        Ok(ref output) if output.status.success() => {
+            let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            let arithmetic = if version.contains("-fixed") {
+                "fixed-point"
+            } else {
+                "floating-point"
+            };
+
+            println!(
+                "cargo:info=Probed Opus `{}` using {} arithmetic.",
+                version, arithmetic
+            );
+
+            if let Some(minimum) = minimum_opus_version() {
+                if is_at_least_version(&version, &minimum) == Some(false) {
+                    return Err(BuildError::new(
+                        STEP,
+                        format!(
+                            "The Opus in `{}` is `{}`, older than the required {}. Point \
+                             `OPUS_LIB_DIR` to a newer Opus, bypass it via \
+                             `OPUS_NO_PKG`, or lower `OPUS_MIN_VERSION`.",
+                            lib_directory.display(),
+                            version,
+                            minimum
+                        ),
+                    ));
+                }
+            }
+        }
+        _ => println!("cargo:info=Could not run the Opus probe, skipping detecting its arithmetic."),
+    }
+
+    Ok(())
+}
+
+/// Compiles a C file asserting each `OPUS_*`-constant of the bindings, and of
+/// the projection bindings with the `projection`-feature, equals the macro of
+/// the Opus headers in `include_directories`, failing the build on mismatch
+/// as `_ctl`-calls would silently misbehave otherwise.
+///
+/// The assertions declare an array of negative size on mismatch, as
+/// `_Static_assert` is not supported by every C compiler. The check is skipped
+/// like `probe_opus` and when the bindings are generated via `bindgen`.
+fn check_constants(include_directories: &[PathBuf]) -> Result<(), BuildError> {
+    const STEP: &str = "Checking the constants of Opus";
+
+    if opus_env("NO_PROBE").is_some() || cfg!(feature = "bindgen") {
+        return Ok(());
+    }
+
+    let compiler = match cc::Build::new().cargo_metadata(false).try_get_compiler() {
+        Ok(compiler) => compiler,
+        Err(_) => return Ok(()),
+    };
+
+    let mut bindings = std::fs::read_to_string("src/bindings.rs").map_err(|error| {
+        BuildError::new(STEP, format!("Could not read the bindings: {}", error))
+    })?;
+
+    let mut source = String::from("#include <opus.h>\n#include <opus_multistream.h>\n");
+
+    if cfg!(feature = "projection") {
+        let projection = std::fs::read_to_string("src/projection.rs").map_err(|error| {
+            BuildError::new(STEP, format!("Could not read the projection bindings: {}", error))
+        })?;
+
+        bindings.push_str(&projection);
+        source.push_str("#include <opus_projection.h>\n");
+    }
+
+    source.push('\n');
+
+    let mut is_enabled = true;
+
+    for line in bindings.lines() {
+        // Constants of newer Opus are gated by the features enabling them,
+        // such as `#[cfg(any(feature = "deep-plc", feature = "osce"))]`.
+        if line.starts_with("#[cfg(feature = ") || line.starts_with("#[cfg(any(feature = ") {
+            is_enabled = line.split('"').skip(1).step_by(2).any(|feature| {
+                env::var(format!(
+                    "CARGO_FEATURE_{}",
+                    feature.to_uppercase().replace('-', "_")
+                ))
+                .is_ok()
+            });
+
+            continue;
+        }
+
+        let is_constant_enabled = is_enabled;
+        is_enabled = true;
+
+        if !line.starts_with("pub const OPUS_") || !is_constant_enabled {
+            continue;
+        }
+
+        let name = line["pub const ".len()..].split(':').next().unwrap_or_default();
+        let value = line.rsplit('=').next().unwrap_or_default().trim().trim_end_matches(';');
+
+        source.push_str(&format!(
+            "typedef char check_{0}[({0}) == ({1}) ? 1 : -1];\n",
+            name, value
+        ));
+    }
+
+    let source_path = create_probe_directory(STEP)?.join("opus_constants.c");
+
+    std::fs::write(&source_path, source).map_err(|error| {
+        BuildError::new(STEP, format!("Could not write `{}`: {}", source_path.display(), error))
+    })?;
+
+    let mut command = compiler.to_command();
+
+    for include_directory in include_directories {
+        command.arg(if compiler.is_like_msvc() { "/I" } else { "-I" });
+        command.arg(include_directory);
+    }
+
+    command
+        .arg(if compiler.is_like_msvc() { "/Zs" } else { "-fsyntax-only" })
+        .arg(&source_path);
+
+    let output = match command.output() {
+        Ok(output) => output,
+        Err(_) => return Ok(()),
+    };
+
+    if !output.status.success() {
+        // MSVC's tools report errors on the standard output.
+        let mut compiler_output = output.stdout;
+        compiler_output.extend_from_slice(&output.stderr);
+
+        return Err(BuildError::new(
+            STEP,
+            "Constants of the bindings differ from the Opus headers, the \
+             `check_`-prefixed names below reveal which ones.",
+        )
+        .with_stderr(&compiler_output));
+    }
+
+    println!("cargo:info=Constants of the bindings match the Opus headers.");
+
+    Ok(())
+}
+
+/// Lets `bindgen` treat every integer macro as `c_int`, matching the types of
+/// the hand-written bindings.
+#[cfg(feature = "bindgen")]
+#[derive(Debug)]
+struct IntMacros;
+
+#[cfg(feature = "bindgen")]
+impl bindgen::callbacks::ParseCallbacks for IntMacros {
+    fn int_macro(&self, _name: &str, _value: i64) -> Option<bindgen::callbacks::IntKind> {
+        Some(bindgen::callbacks::IntKind::Int)
+    }
+}
+
+/// Generates the bindings from the Opus headers in `include_directories` into
+/// `OUT_DIR`, replacing the hand-written ones with the `bindgen`-feature.
+///
+/// The opaque states are declared by the crate itself and hence excluded.
+/// Without known headers of the linked Opus, the bundled ones are used. Clang
+/// parses them for the target being built, making these bindings per-target.
+#[cfg(feature = "bindgen")]
+fn generate_bindings(include_directories: &[PathBuf]) -> Result<(), BuildError> {
+    const STEP: &str = "Generating the bindings";
+
+    let bundled_include_directory = [Path::new("opus").join("include")];
+    let include_directories = if include_directories.is_empty() {
+        &bundled_include_directory[..]
+    } else {
+        include_directories
+    };
+
+    let mut builder = bindgen::Builder::default()
+        .header_contents(
+            "opus_bindings.h",
+            "#include <opus.h>\n#include <opus_multistream.h>\n",
+        )
+        .whitelist_function("opus_.*")
+        .whitelist_var("OPUS_.*")
+        .blacklist_type("Opus(Encoder|Decoder|Repacketizer|MSEncoder|MSDecoder)")
+        .raw_line("use crate::{OpusDecoder, OpusEncoder, OpusMSDecoder, OpusMSEncoder, OpusRepacketizer};")
+        .parse_callbacks(Box::new(IntMacros))
+        .use_core()
+        .ctypes_prefix("crate::ctypes")
+        .layout_tests(false);
+
+    if cfg!(feature = "projection") {
+        builder = builder
+            .header_contents("opus_projection_bindings.h", "#include <opus_projection.h>\n");
+    }
+
+    if cfg!(feature = "disable-float-api") {
+        builder = builder.blacklist_function("opus_(.*_float|pcm_soft_clip)");
+    }
+
+    for include_directory in include_directories {
+        builder = builder.clang_arg(format!("-I{}", include_directory.display()));
+    }
+
+    if let Ok(target) = env::var("TARGET") {
+        builder = builder.clang_arg(format!("--target={}", clang_target(&target)));
+    }
+
+    let bindings = builder.generate().map_err(|_| {
+        BuildError::new(
+            STEP,
+            format!(
+                "`bindgen` failed to parse the Opus headers in {:?}, make sure \
+                 `libclang` is installed.",
+                include_directories
+            ),
+        )
+    })?;
+
+    let bindings_path =
+        PathBuf::from(env::var("OUT_DIR").expect("Environment variable `OUT_DIR` is missing."))
+            .join("bindings.rs");
+
+    let mut bindings = wrap_extern_blocks(&bindings.to_string());
+
+    if cfg!(feature = "prefix-symbols") {
+        bindings = prefix_link_names(&bindings, SYMBOL_PREFIX);
+    }
+
+    std::fs::write(&bindings_path, bindings).map_err(|error| {
+        BuildError::new(
+            STEP,
+            format!("Could not write `{}`: {}", bindings_path.display(), error),
+        )
+    })?;
+
+    println!(
+        "cargo:info=Generated the bindings from the Opus headers in {:?}.",
+        include_directories
+    );
+
+    Ok(())
+}
+
+#[cfg(not(feature = "bindgen"))]
+fn generate_bindings(_include_directories: &[PathBuf]) -> Result<(), BuildError> {
+    Ok(())
+}
+
+/// Returns the user-provided CMake toolchain file, if any.
+///
+/// Fails if the given file does not exist.
+#[cfg(feature = "cmake")]
+fn find_cmake_toolchain_file() -> Result<Option<PathBuf>, BuildError> {
+    let toolchain_file = match opus_env("CMAKE_TOOLCHAIN_FILE")
+        .or_else(|| env::var("CMAKE_TOOLCHAIN_FILE").ok())
+    {
+        Some(toolchain_file) => PathBuf::from(toolchain_file),
+        None => return Ok(None),
+    };
+
+    if !toolchain_file.is_file() {
+        return Err(BuildError::new(
+            "Locating the CMake toolchain file",
+            format!(
+                "CMake toolchain file does not exist: {}",
+                toolchain_file.display()
+            ),
+        ));
+    }
+
+    Ok(Some(toolchain_file))
+}
+
+/// Splits the whitespace-separated flags of `LIBOPUS_{name}` or `OPUS_{name}`,
+/// such as `CONFIGURE_FLAGS`, appended verbatim after the build script's own
+/// ones to enable options it does not model.
+#[cfg(any(unix, target_env = "gnu", feature = "cmake"))]
+fn extra_flags(name: &str) -> Vec<String> {
+    opus_env(name)
+        .map(|flags| flags.split_whitespace().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Parses the whitespace-separated `-DKEY=VALUE` entries of
+/// `LIBOPUS_CMAKE_ARGS` or `OPUS_CMAKE_ARGS` into key-value-pairs.
+///
+/// The key may carry a type, such as `-DOPUS_FIXED_POINT:BOOL=ON`.
+#[cfg(feature = "cmake")]
+fn cmake_cache_entries() -> Result<Vec<(String, String)>, BuildError> {
+    let arguments = match opus_env("CMAKE_ARGS") {
+        Some(arguments) => arguments,
+        None => return Ok(Vec::new()),
+    };
+
+    arguments
+        .split_whitespace()
+        .map(|argument| {
+            let entry = if argument.starts_with("-D") {
+                &argument[2..]
+            } else {
+                ""
+            };
+            let mut entry = entry.splitn(2, '=');
+
+            match (entry.next(), entry.next()) {
+                (Some(key), Some(value)) if !key.is_empty() => {
+                    Ok((key.to_string(), value.to_string()))
+                }
+                _ => Err(BuildError::new(
+                    "Reading `OPUS_CMAKE_ARGS`",
+                    format!(
+                        "Invalid CMake cache entry `{}`, expected `-DKEY=VALUE`.",
+                        argument
+                    ),
+                )),
+            }
+        })
+        .collect()
+}
+
+#[cfg(any(unix, target_env = "gnu"))]
+fn find_via_pkg_config(is_static: bool) -> Result<bool, BuildError> {
+    // A requested soname, whole-archive, or bundled linking replaces the plain
+    // link-directive of `pkg-config`, hence it must not emit its own.
+    let is_emitting_own_link = if is_static {
+        is_whole_archive()? || is_bundled_archive()
+    } else {
+        opus_env("LINK_SONAME").is_some()
+    };
+
+    let mut config = pkg_config::Config::new();
+
+    if let Some(version) = minimum_opus_version() {
+        config.atleast_version(&version);
+    }
+
+    config.statik(is_static);
+
+    // When cross-compiling, `pkg-config` may still find the host's Opus, e.g.
+    // with `PKG_CONFIG_ALLOW_CROSS` but no sysroot, hence it is vetted before
+    // `pkg-config` may emit any directive.
+    let host = env::var("HOST").ok();
+    let target = env::var("TARGET").unwrap_or_default();
+
+    if is_multilib_target(host.as_ref().map(String::as_str), &target) {
+        use_multilib_pkg_config(&target);
+    }
+
+    if is_cross_compiled(host.as_ref().map(String::as_str), &target)
+        && !is_target_pkg_config(config.cargo_metadata(false), is_static)
+    {
+        return Ok(false);
+    }
+
+    match config.cargo_metadata(!is_emitting_own_link).probe("opus") {
+        Ok(library) => {
+            let lib_directory = library
+                .link_paths
+                .first()
+                .map(PathBuf::as_path)
+                .unwrap_or_else(|| Path::new(""));
+
+            if is_emitting_own_link {
+                emit_link("opus", is_static, lib_directory)?;
+                emit_c_deps(is_static)?;
+            }
+
+            probe_opus("opus", is_static, lib_directory)?;
+            generate_bindings(&library.include_paths)?;
+            check_constants(&library.include_paths)?;
+
+            let include_directory = install_found_headers(&library.include_paths)?;
+
+            emit_metadata(
+                is_static,
+                "opus",
+                lib_directory,
+                include_directory.as_ref().map(PathBuf::as_path),
+                None,
+                Some(&library.version),
+            );
+
+            Ok(true)
+        }
+        Err(error) => {
+            if let pkg_config::Error::Failure { .. } = error {
+                println!(
+                    "cargo:info=`pkg-config` rejected Opus: {}",
+                    error.to_string().lines().next().unwrap_or_default()
+                );
+            }
+
+            Ok(false)
+        }
+    }
+}
+
+/// Whether `pkg-config`, configured via `config`, finds an Opus built for the
+/// target when cross-compiling.
+///
+/// The `pkg-config`-crate only runs it if `PKG_CONFIG_SYSROOT_DIR`,
+/// `PKG_CONFIG`, or `PKG_CONFIG_ALLOW_CROSS` is set, either plain, with the
+/// `TARGET_`-prefix, or suffixed with the target, honouring
+/// `PKG_CONFIG_PATH_<target>` and `PKG_CONFIG_LIBDIR_<target>` the same way.
+/// An Opus whose library-file was built for another architecture, usually
+/// the host's, is rejected so that Opus is built from source instead.
+#[cfg(any(unix, target_env = "gnu"))]
+fn is_target_pkg_config(config: &pkg_config::Config, is_static: bool) -> bool {
+    let library = match config.probe("opus") {
+        Ok(library) => library,
+        Err(pkg_config::Error::CrossCompilation) => {
+            println!(
+                "cargo:info=Bypassed `pkg-config` when cross-compiling, set \
+                 `PKG_CONFIG_SYSROOT_DIR` or `PKG_CONFIG_PATH` for the target to use it."
+            );
+
+            return false;
+        }
+        Err(_) => return false,
+    };
+
+    let file_names = library_file_names(
+        "opus",
+        is_static,
+        &cargo_cfg("TARGET_OS"),
+        &cargo_cfg("TARGET_ENV"),
+    );
+
+    for lib_directory in &library.link_paths {
+        for file_name in &file_names {
+            let library_path = lib_directory.join(file_name);
+
+            if let Some(machine) = foreign_machine(&library_path) {
+                println!(
+                    "cargo:info=`pkg-config` found {} built for {}, ignoring it.",
+                    library_path.display(),
+                    machine
+                );
+
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+/// Points `pkg-config` to the 32-bit `.pc`-files of a multilib host, such as
+/// `/usr/lib/i386-linux-gnu/pkgconfig` or `/usr/lib32/pkgconfig`, for `target`
+/// only, as it otherwise finds the host's 64-bit Opus.
+///
+/// Search paths, a sysroot, or a `pkg-config` configured for the target take
+/// precedence, as does `PKG_CONFIG_ALLOW_CROSS=0`.
+#[cfg(any(unix, target_env = "gnu"))]
+fn use_multilib_pkg_config(target: &str) {
+    let target_variable = |variable: &str| {
+        env::var_os(format!("{}_{}", variable, target))
+            .or_else(|| env::var_os(format!("{}_{}", variable, target.replace('-', "_"))))
+            .or_else(|| env::var_os(format!("TARGET_{}", variable)))
+    };
+
+    let is_configured = ["PKG_CONFIG", "PKG_CONFIG_PATH", "PKG_CONFIG_LIBDIR", "PKG_CONFIG_SYSROOT_DIR"]
+        .iter()
+        .any(|variable| target_variable(variable).is_some());
+    let is_forbidden = target_variable("PKG_CONFIG_ALLOW_CROSS")
+        .or_else(|| env::var_os("PKG_CONFIG_ALLOW_CROSS"))
+        .map_or(false, |value| value == "0");
+
+    if is_configured || is_forbidden {
+        return;
+    }
+
+    // Fedora keeps the 32-bit files in `/usr/lib`, Debian's are architecture-
+    // independent there, hence it comes last.
+    let directories: Vec<&str> = [
+        "/usr/lib/i386-linux-gnu/pkgconfig",
+        "/usr/lib32/pkgconfig",
+        "/usr/local/lib32/pkgconfig",
+        "/usr/lib/pkgconfig",
+    ]
+    .iter()
+    .cloned()
+    .filter(|directory| Path::new(directory).is_dir())
+    .collect();
+
+    if directories.is_empty() {
+        return;
+    }
+
+    println!(
+        "cargo:info=Searching the multilib `pkg-config`-directories: {}.",
+        directories.join(", ")
+    );
+
+    env::set_var(format!("PKG_CONFIG_LIBDIR_{}", target), directories.join(":"));
+    env::set_var(format!("PKG_CONFIG_ALLOW_CROSS_{}", target), "1");
+}
+
+/// Probes for an Opus installed via `vcpkg`, whose triplet decides between
+/// static and dynamic linking.
+#[cfg(all(windows, target_env = "msvc"))]
+fn find_via_vcpkg() -> Result<bool, BuildError> {
+    match vcpkg::Config::new().emit_includes(false).find_package("opus") {
+        Ok(library) => {
+            let lib_directory = library
+                .link_paths
+                .first()
+                .map(PathBuf::as_path)
+                .unwrap_or_else(|| Path::new(""));
+
+            println!(
+                "cargo:info=The `vcpkg`-triplet provides a {} Opus.",
+                rustc_linking_word(library.is_static)
+            );
+
+            probe_opus("opus", library.is_static, lib_directory)?;
+
+            if !library.is_static {
+                copy_dlls(&library.dll_paths)?;
+            }
+
+            let include_directories: Vec<PathBuf> = library
+                .include_paths
+                .iter()
+                .map(|include_directory| include_directory.join("opus"))
+                .collect();
+
+            generate_bindings(&include_directories)?;
+            check_constants(&include_directories)?;
+            let include_directory = install_found_headers(&include_directories)?;
+
+            emit_metadata(
+                library.is_static,
+                "opus",
+                lib_directory,
+                include_directory.as_ref().map(PathBuf::as_path),
+                None,
+                None,
+            );
+
+            Ok(true)
+        }
+        Err(error) => {
+            println!("cargo:info=`vcpkg` failed: {}", error);
+
+            Ok(false)
+        }
+    }
+}
+
+/// Emits the directives linking `lib_name` found in `search_directory`,
+/// statically or dynamically.
+///
+/// A dynamic library is linked by its versioned soname if requested, see
+/// `linked_soname`, a static library as whole archive if requested, see
+/// `is_whole_archive`.
+fn emit_link(lib_name: &str, is_static: bool, search_directory: &Path) -> Result<(), BuildError> {
+    let soname = if is_static {
+        None
+    } else {
+        linked_soname(lib_name, search_directory)?
+    };
+
+    match soname {
+        // GNU `ld` and LLVM's `lld` link the exact file-name following `-l:`.
+        Some(soname) => {
+            println!("cargo:info=Linking Opus by its soname `{}`.", soname);
+            println!("cargo:rustc-link-lib=dylib=:{}", soname);
+        }
+        None if is_static && (is_whole_archive()? || is_bundled_archive()) => {
+            let mut modifiers = Vec::new();
+
+            if is_bundled_archive() {
+                println!("cargo:info=Bundling `{}` into the Rust archive.", lib_name);
+                modifiers.push("+bundle");
+            }
+
+            if is_whole_archive()? {
+                println!("cargo:info=Linking `{}` as whole archive.", lib_name);
+                modifiers.push("+whole-archive");
+            }
+
+            println!(
+                "cargo:rustc-link-lib=static:{}={}",
+                modifiers.join(","),
+                lib_name
+            );
+        }
+        None => println!(
+            "cargo:rustc-link-lib={}={}",
+            rustc_linking_word(is_static),
+            lib_name
+        ),
+    }
+
+    // `pkg-config` omits the directories the linker searches anyway.
+    if !search_directory.as_os_str().is_empty() {
+        println!(
+            "cargo:rustc-link-search=native={}",
+            search_directory.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// The number of jobs building Opus in parallel, read from `LIBOPUS_JOBS` or
+/// `OPUS_JOBS` and defaulting to Cargo's `NUM_JOBS`.
+fn build_jobs() -> Result<Option<usize>, BuildError> {
+    match opus_env_var("JOBS") {
+        Some((variable, value)) => match value.trim().parse::<usize>() {
+            Ok(jobs) if jobs > 0 => Ok(Some(jobs)),
+            _ => Err(BuildError::new(
+                "Selecting the build jobs",
+                format!(
+                    "Invalid value for `{}`: `{}`, expected a positive number.",
+                    variable, value
+                ),
+            )),
+        },
+        None => Ok(env::var("NUM_JOBS").ok().and_then(|jobs| jobs.parse().ok())),
+    }
+}
+
+/// Whether a static Opus shall be linked as whole archive, as requested via
+/// `LIBOPUS_WHOLE_ARCHIVE` or `OPUS_WHOLE_ARCHIVE`, keeping sections the
+/// linker would otherwise discard.
+///
+/// This relies on the `+whole-archive`-modifier, requiring Rust 1.61.
+fn is_whole_archive() -> Result<bool, BuildError> {
+    match opus_env_var("WHOLE_ARCHIVE") {
+        Some((variable, value)) => parse_bool(&variable, &value)
+            .map_err(|error| BuildError::new("Selecting the linking", error)),
+        None => Ok(false),
+    }
+}
+
+/// Whether static libraries shall be bundled into the rlib, and hence into a
+/// `staticlib` built from it, as requested via the `bundle-archive`-feature.
+///
+/// Rustc bundles static libraries by default, the explicit `+bundle`-modifier
+/// keeps it that way regardless of future defaults and requires Rust 1.61.
+fn is_bundled_archive() -> bool {
+    cfg!(feature = "bundle-archive")
+}
+
+/// Returns the versioned file-name of a dynamic `lib_name` to link by, as
+/// requested via `LIBOPUS_LINK_SONAME` or `OPUS_LINK_SONAME`.
+///
+/// A file-name such as `libopus.so.0` is used as is, whereas a boolean value
+/// enables picking the shortest versioned file-name in `search_directory`.
+/// Only ELF-targets know sonames, others link as usual.
+fn linked_soname(lib_name: &str, search_directory: &Path) -> Result<Option<String>, BuildError> {
+    const STEP: &str = "Selecting the soname of Opus";
+
+    let (variable, value) = match opus_env_var("LINK_SONAME") {
+        Some(variable) => variable,
+        None => return Ok(None),
+    };
+
+    let target_os = cargo_cfg("TARGET_OS");
+
+    if target_os == "windows" || target_os == "macos" || target_os == "ios" {
+        println!(
+            "cargo:info=The target does not use sonames, ignoring `{}`.",
+            variable
+        );
+
+        return Ok(None);
+    }
+
+    let unversioned_name = format!("lib{}.so.", lib_name);
+
+    if value.starts_with(&unversioned_name) {
+        return Ok(Some(value));
+    }
+
+    if !parse_bool(&variable, &value).map_err(|error| BuildError::new(STEP, error))? {
+        return Ok(None);
+    }
+
+    let soname = std::fs::read_dir(search_directory)
+        .ok()
+        .into_iter()
+        .flat_map(|entries| entries.filter_map(Result::ok))
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|name| {
+            name.starts_with(&unversioned_name)
+                && name.len() > unversioned_name.len()
+                && name[unversioned_name.len()..]
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || c == '.')
+        })
+        .min_by_key(String::len);
+
+    match soname {
+        Some(soname) => Ok(Some(soname)),
+        None => Err(BuildError::new(
+            STEP,
+            format!(
+                "No versioned `{}*` exists in `{}`, set `{}` to the file-name to \
+                 link instead.",
+                unversioned_name,
+                search_directory.display(),
+                variable
+            ),
+        )),
+    }
+}
+
+/// Writes an `opus.pc` describing the Opus built from source and installed in
+/// `install_directory` into its `lib/pkgconfig`, replacing the one of the
+/// install step, whose prefix may name another directory, e.g. of a cached
+/// build, and emits its directory as `DEP_OPUS_PKGCONFIG`.
+///
+/// If `LIBOPUS_EMIT_PC` or `OPUS_EMIT_PC` names a directory, it is copied
+/// there as well, letting outer build systems find it via `PKG_CONFIG_PATH`.
+fn emit_pkg_config_file(install_directory: &Path) -> Result<(), BuildError> {
+    const STEP: &str = "Writing `opus.pc`";
+
+    let is_unix = cargo_cfg("TARGET_FAMILY")
+        .split(',')
+        .any(|family| family == "unix");
+    let private_libraries = if is_unix && cargo_cfg("TARGET_VENDOR") != "apple" {
+        "-lm"
+    } else {
+        ""
+    };
+
+    let pc_file = format!(
+        "prefix={}\n\
+         exec_prefix=${{prefix}}\n\
+         libdir=${{exec_prefix}}/lib\n\
+         includedir=${{prefix}}/include\n\
+         \n\
+         Name: Opus\n\
+         Description: Opus IETF audio codec, built by audiopus_sys\n\
+         Version: {}\n\
+         Libs: -L${{libdir}} -lopus\n\
+         Libs.private: {}\n\
+         Cflags: -I${{includedir}}/opus\n",
+        install_directory.display().to_string().replace("\\", "/"),
+        opus_source_version().unwrap_or_else(|| "unknown".to_string()),
+        private_libraries
+    );
+
+    let mut pc_directories = vec![install_directory.join("lib").join("pkgconfig")];
+
+    if let Some(pc_directory) = opus_env("EMIT_PC") {
+        pc_directories.push(PathBuf::from(pc_directory));
+    }
+
+    for pc_directory in &pc_directories {
+        let pc_path = pc_directory.join("opus.pc");
+
+        std::fs::create_dir_all(pc_directory)
+            .and_then(|_| std::fs::write(&pc_path, &pc_file))
+            .map_err(|error| {
+                BuildError::new(STEP, format!("Could not write `{}`: {}", pc_path.display(), error))
+            })?;
+
+        println!("cargo:info=Wrote `{}`.", pc_path.display());
+    }
+
+    println!("cargo:pkgconfig={}", pc_directories[0].display());
+
+    Ok(())
+}
+
+/// Emits metadata about the linked Opus, readable by build scripts of
+/// dependents as `DEP_OPUS_STATIC`, `DEP_OPUS_LIB`, `DEP_OPUS_LIB_DIR`,
+/// `DEP_OPUS_INCLUDE`, `DEP_OPUS_ROOT`, and `DEP_OPUS_VERSION`, see
+/// `emit_pkg_config_file` for `DEP_OPUS_PKGCONFIG`.
+///
+/// The include directory contains `opus.h` itself, the root directory is the
+/// prefix of an Opus built from source. Unknown values are omitted, such as
+/// for a pre-installed Opus found via `LIBOPUS_LIB_DIR` or `OPUS_LIB_DIR`.
+fn emit_metadata(
+    is_static: bool,
+    lib_name: &str,
+    lib_directory: &Path,
+    include_directory: Option<&Path>,
+    root_directory: Option<&Path>,
+    version: Option<&str>,
+) {
+    println!("cargo:static={}", if is_static { 1 } else { 0 });
+    println!("cargo:lib={}", lib_name);
+    println!("cargo:lib_dir={}", lib_directory.display());
+
+    if let Some(include_directory) = include_directory {
+        println!("cargo:include={}", include_directory.display());
+        // Exposes the headers to the crate's own tests, which unlike
+        // dependents receive no `DEP_OPUS_INCLUDE`.
+        println!(
+            "cargo:rustc-env=DEP_OPUS_INCLUDE={}",
+            include_directory.display()
+        );
+    }
+
+    if let Some(root_directory) = root_directory {
+        println!("cargo:root={}", root_directory.display());
+    }
+
+    if let Some(version) = version {
+        println!("cargo:version={}", version);
+    }
+
+    write_build_info(is_static, root_directory.is_some(), version);
+}
+
+/// Writes the constants describing the linked Opus to `build_info.rs` in
+/// `OUT_DIR`, which the crate includes, e.g. `LINKED_STATICALLY`.
+///
+/// `main` writes them for an unlinked Opus first, such as when loading it at
+/// runtime, `emit_metadata` overwrites them once Opus is linked.
+fn write_build_info(is_static: bool, is_built_from_source: bool, version: Option<&str>) {
+    let out_directory =
+        PathBuf::from(env::var("OUT_DIR").expect("Environment variable `OUT_DIR` is missing."));
+    let build_info_path = out_directory.join("build_info.rs");
+
+    let build_info = format!(
+        "/// Whether Opus is linked statically, `false` if linked dynamically or\n\
+         /// loaded at runtime.\n\
+         pub const LINKED_STATICALLY: bool = {};\n\
+         \n\
+         /// Whether the build script built Opus from source instead of linking a\n\
+         /// pre-installed or prebuilt one.\n\
+         pub const BUILT_FROM_SOURCE: bool = {};\n\
+         \n\
+         /// The version of the linked Opus as reported by its build system or\n\
+         /// `pkg-config`, e.g. `1.3.1`, `None` if unknown.\n\
+         pub const LINKED_OPUS_VERSION: Option<&str> = {:?};\n\
+         \n\
+         /// The version of the Opus bundled as source and prebuilt library-files.\n\
+         pub const BUNDLED_OPUS_VERSION: &str = {:?};\n",
+        is_static, is_built_from_source, version, BUNDLED_OPUS_VERSION
+    );
+
+    std::fs::write(&build_info_path, build_info).unwrap_or_else(|error| {
+        panic!("Could not write {}: {}", build_info_path.display(), error)
+    });
+}
+
+/// Based on the OS or target environment we are building for,
+/// this function will return an expected default library linking method.
+///
+/// If we build for Windows, MacOS, iOS, tvOS, watchOS, Android, WebAssembly,
+/// Fuchsia, OpenHarmony, QNX Neutrino, or Linux with musl, we will link
+/// statically.
+/// However, if you build for Linux without musl, the BSDs, Haiku, illumos, or
+/// Solaris, we will link dynamically.
+/// Other Unix targets link dynamically to their system's Opus as well, any
+/// other target statically.
+///
+/// **Info**:
+/// This is a helper-function and may not be called if
+/// the environment variable `LIBOPUS_LINKAGE`, `OPUS_LINKAGE`,
+/// `LIBOPUS_STATIC`, or `OPUS_STATIC` is set or
+/// the `static`- or `dynamic`-feature is enabled.
+fn default_library_linking() -> Result<bool, BuildError> {
+    if is_bare_wasm() {
+        return Ok(true);
+    }
+
+    match (cargo_cfg("TARGET_OS").as_str(), cargo_cfg("TARGET_ENV").as_str()) {
+        ("windows", _)
+        | ("macos", _)
+        | ("ios", _)
+        | ("tvos", _)
+        | ("watchos", _)
+        | ("android", _)
+        | ("emscripten", _)
+        | ("fuchsia", _)
+        | ("nto", _)
+        | (_, "musl")
+        | (_, "ohos") => Ok(true),
+        ("linux", "gnu") => Ok(false),
+        ("haiku", _) | ("illumos", _) | ("solaris", _) => Ok(false),
+        (target_os, _) if is_bsd(target_os) => Ok(false),
+        (target_os, target_env) => {
+            let is_unix = cargo_cfg("TARGET_FAMILY")
+                .split(',')
+                .any(|family| family == "unix");
+
+            println!(
+                "cargo:info=No default linking for `{}` with environment `{}`, linking {}.",
+                target_os,
+                target_env,
+                if is_unix { "dynamically" } else { "statically" }
+            );
+
+            Ok(!is_unix)
+        }
+    }
+}
+
+/// Emits the C libraries a statically linked Opus depends on, as a dynamic
+/// Opus declares them itself.
+///
+/// Opus only requires `libm` for functions such as `sqrt` and `pow`. It is
+/// part of the C library on Apple's, Haiku's, and Windows targets, whereas
+/// other Unix targets, whether using glibc, musl, or Bionic, may fail to link
+/// without it. Opus does not use threads, hence `pthread` is never needed.
+///
+/// Toolchains needing more, such as `libssp` for MinGW's stack protector,
+/// can append them via `LIBOPUS_LINK_LIBS` or `OPUS_LINK_LIBS`.
+fn emit_c_deps(is_static: bool) -> Result<(), BuildError> {
+    if !is_static {
+        return Ok(());
+    }
+
+    let is_unix = cargo_cfg("TARGET_FAMILY")
+        .split(',')
+        .any(|family| family == "unix");
+    let is_apple = cargo_cfg("TARGET_VENDOR") == "apple";
+    let is_haiku = cargo_cfg("TARGET_OS") == "haiku";
+
+    let libraries: &[&str] = if is_unix && !is_apple && !is_haiku {
+        &["m"]
+    } else {
+        &[]
+    };
+
+    for library in libraries {
+        println!("cargo:rustc-link-lib=dylib={}", library);
+    }
+
+    if let Some((variable, value)) = opus_env_var("LINK_LIBS") {
+        let libraries = parse_link_libs(&variable, &value)
+            .map_err(|error| BuildError::new("Linking the C dependencies", error))?;
+
+        for library in libraries {
+            println!("cargo:info=Linking `{}` as requested.", library);
+            println!("cargo:rustc-link-lib={}", library);
+        }
+    }
+
+    Ok(())
+}
+
+/// Links the Opus installed as `lib_name` in `lib_directory`, whose headers
+/// are in the first of `include_directories` containing `opus.h`.
+fn link_installed_opus(
+    lib_name: &str,
+    is_static: bool,
+    lib_directory: &Path,
+    include_directories: &[PathBuf],
+) -> Result<(), BuildError> {
+    emit_link(lib_name, is_static, lib_directory)?;
+    probe_opus(lib_name, is_static, lib_directory)?;
+    generate_bindings(include_directories)?;
+    check_constants(include_directories)?;
+
+    let include_directory = install_found_headers(include_directories)?;
+
+    emit_metadata(
+        is_static,
+        lib_name,
+        lib_directory,
+        include_directory.as_ref().map(PathBuf::as_path),
+        None,
+        None,
+    );
+
+    emit_c_deps(is_static)
+}
+
+/// Links the DLL at `dll_path` given via `LIBOPUS_DLL` or `OPUS_DLL`, e.g. a
+/// third-party `opus.dll` shipped without import library, by generating one
+/// from its exports via `lib.exe` for MSVC or `dlltool` for GNU.
+///
+/// The DLL is copied next to the import library in `OUT_DIR/opus-dll`, where
+/// the probe finds it, and next to the built binaries for MSVC. With the
+/// `raw-dylib`-feature, no import library is needed.
+fn link_opus_dll(dll_path: &Path) -> Result<(), BuildError> {
+    const STEP: &str = "Linking the DLL of `OPUS_DLL`";
+
+    if cargo_cfg("TARGET_OS") != "windows" {
+        return Err(BuildError::new(
+            STEP,
+            "Linking a DLL requires a Windows target, unset `LIBOPUS_DLL` and \
+             `OPUS_DLL` or point `OPUS_LIB_DIR` to an Opus for the target instead.",
+        ));
+    }
+
+    if is_linkage_requested() && is_static_build()? {
+        return Err(BuildError::new(
+            STEP,
+            "A DLL can only be linked dynamically, select dynamic linking via \
+             `OPUS_LINKAGE` or unset `LIBOPUS_DLL` and `OPUS_DLL`.",
+        ));
+    }
+
+    let dll = std::fs::read(dll_path).map_err(|error| {
+        BuildError::new(STEP, format!("Could not read `{}`: {}", dll_path.display(), error))
+    })?;
+
+    if let Some(machine) = foreign_machine(dll_path) {
+        return Err(BuildError::new(
+            STEP,
+            format!(
+                "{} was built for {} (`{}`), provide a DLL built for the target.",
+                dll_path.display(),
+                machine,
+                cargo_cfg("TARGET_ARCH")
+            ),
+        ));
+    }
+
+    let exports = dll_exports(&dll).filter(|exports| !exports.is_empty()).ok_or_else(|| {
+        BuildError::new(
+            STEP,
+            format!("{} is no DLL or exports no symbols.", dll_path.display()),
+        )
+    })?;
+
+    let dll_name = dll_path
+        .file_name()
+        .map(|dll_name| dll_name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    if cfg!(feature = "raw-dylib") {
+        // The bindings' `raw-dylib`-attributes name `opus.dll`.
+        if !dll_name.eq_ignore_ascii_case("opus.dll") {
+            return Err(BuildError::new(
+                STEP,
+                format!(
+                    "The `raw-dylib`-feature links `opus.dll`, rename `{}` accordingly.",
+                    dll_name
+                ),
+            ));
+        }
+
+        #[cfg(all(windows, target_env = "msvc"))]
+        copy_dlls(&[dll_path.to_path_buf()])?;
+
+        return Ok(());
+    }
+
+    let lib_directory =
+        PathBuf::from(env::var("OUT_DIR").expect("Environment variable `OUT_DIR` is missing."))
+            .join("opus-dll");
+    let def_path = lib_directory.join("opus.def");
+
+    let mut definitions = format!("LIBRARY \"{}\"\nEXPORTS\n", dll_name);
+
+    for export in &exports {
+        definitions.push_str(&format!("    {}\n", export));
+    }
+
+    std::fs::create_dir_all(&lib_directory)
+        .and_then(|_| std::fs::write(&def_path, definitions))
+        .and_then(|_| std::fs::copy(dll_path, lib_directory.join(&dll_name)))
+        .map_err(|error| {
+            BuildError::new(
+                STEP,
+                format!("Could not write into `{}`: {}", lib_directory.display(), error),
+            )
+        })?;
+
+    let architecture = cargo_cfg("TARGET_ARCH");
+
+    let mut command = if cargo_cfg("TARGET_ENV") == "msvc" {
+        let machine = match architecture.as_str() {
+            "x86" => "X86",
+            "aarch64" => "ARM64",
+            _ => "X64",
+        };
+
+        let target = env::var("TARGET").unwrap_or_default();
+        let mut command =
+            cc::windows_registry::find(&target, "lib.exe").unwrap_or_else(|| Command::new("lib"));
+
+        command
+            .arg("/nologo")
+            .arg(prefixed_path("/DEF:", &def_path))
+            .arg(prefixed_path("/OUT:", &lib_directory.join("opus.lib")))
+            .arg(format!("/MACHINE:{}", machine));
+
+        command
+    } else {
+        let (prefix, machine) = match architecture.as_str() {
+            "x86" => ("i686-w64-mingw32", "i386"),
+            "aarch64" => ("aarch64-w64-mingw32", "arm64"),
+            _ => ("x86_64-w64-mingw32", "i386:x86-64"),
+        };
+
+        // Cross-toolchains prefix `dlltool` with the target, native MinGW-w64
+        // installations do not.
+        let dlltool = env::var("DLLTOOL").unwrap_or_else(|_| {
+            let prefixed = format!("{}-dlltool", prefix);
+
+            match Command::new(&prefixed).arg("--version").output() {
+                Ok(_) => prefixed,
+                Err(_) => "dlltool".to_string(),
+            }
+        });
+
+        let mut command = Command::new(dlltool);
+        command
+            .arg("-m")
+            .arg(machine)
+            .arg("-d")
+            .arg(&def_path)
+            .arg("-D")
+            .arg(&dll_name)
+            .arg("-l")
+            .arg(lib_directory.join("libopus.dll.a"));
+
+        command
+    };
+
+    run_step("Generating the import library of `OPUS_DLL`", &mut command)?;
+
+    println!(
+        "cargo:info=Generated an import library of {} exports from {}.",
+        exports.len(),
+        dll_path.display()
+    );
+
+    #[cfg(all(windows, target_env = "msvc"))]
+    copy_dlls(&[dll_path.to_path_buf()])?;
+
+    let include_directories: Vec<PathBuf> = find_installed_include()?.into_iter().collect();
+
+    link_installed_opus("opus", false, &lib_directory, &include_directories)
+}
+
+/// Locates an Opus installed via Homebrew or MacPorts when targeting macOS,
+/// as their prefixes are often missing from `pkg-config`'s search path, if
+/// it is installed at all. Returns its library and header directories.
+///
+/// Homebrew is asked via `brew --prefix opus` when building natively, else
+/// its default prefix for the targeted architecture is searched,
+/// `/opt/homebrew` on ARM64 and `/usr/local` on x86-64, followed by MacPorts'
+/// `/opt/local`.
+fn find_via_macos_prefixes(is_static: bool) -> Option<(PathBuf, PathBuf)> {
+    if cargo_cfg("TARGET_OS") != "macos" || !cfg!(target_os = "macos") {
+        return None;
+    }
+
+    let host = env::var("HOST").ok();
+    let target = env::var("TARGET").unwrap_or_default();
+    let mut prefixes = Vec::new();
+
+    if !is_cross_compiled(host.as_ref().map(String::as_str), &target) {
+        if let Ok(output) = Command::new("brew").args(&["--prefix", "opus"]).output() {
+            if output.status.success() {
+                let prefix = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+                prefixes.push(PathBuf::from(prefix));
+            }
+        }
+    }
+
+    match cargo_cfg("TARGET_ARCH").as_str() {
+        "aarch64" => prefixes.push(PathBuf::from("/opt/homebrew/opt/opus")),
+        "x86_64" => prefixes.push(PathBuf::from("/usr/local/opt/opus")),
+        _ => {}
+    }
+
+    prefixes.push(PathBuf::from("/opt/local"));
+
+    let lib_file = if is_static {
+        "libopus.a"
+    } else {
+        "libopus.dylib"
+    };
+
+    prefixes
+        .into_iter()
+        .map(|prefix| (prefix.join("lib"), prefix.join("include").join("opus")))
+        .find(|(lib_directory, include_directory)| {
+            lib_directory.join(lib_file).is_file() && include_directory.join("opus.h").is_file()
+        })
+}
+
+/// Whether `target_os` is one of the BSDs, which install packages outside of
+/// `/usr` and ship a `make` other than GNU's.
+fn is_bsd(target_os: &str) -> bool {
+    match target_os {
+        "dragonfly" | "freebsd" | "netbsd" | "openbsd" => true,
+        _ => false,
+    }
+}
+
+/// Locates an Opus installed via the system's packages when building natively,
+/// as `pkg-config` may be missing, e.g. in minimal containers or on Windows,
+/// or lack the prefix. Returns its library and header directories.
+///
+/// NetBSD's pkgsrc installs into `/usr/pkg`, the ports of FreeBSD, OpenBSD,
+/// and DragonFly into `/usr/local`, and Haiku's packages into
+/// `/boot/system/develop` with the headers in its `headers`. Linux searches
+/// `/usr/local` and `/usr`, including Debian's multiarch directories, and
+/// Windows' GNU-toolchain the MinGW-w64 prefix of MSYS2, `MINGW_PREFIX`, or
+/// its default installation in `C:\msys64`.
+fn find_via_package_prefixes(is_static: bool) -> Option<(PathBuf, PathBuf)> {
+    let target_os = cargo_cfg("TARGET_OS");
+    let host = env::var("HOST").ok();
+    let target = env::var("TARGET").unwrap_or_default();
+
+    if target_os != env::consts::OS || is_cross_compiled(host.as_ref().map(String::as_str), &target)
+    {
+        return None;
+    }
+
+    let architecture = cargo_cfg("TARGET_ARCH");
+    let target_env = cargo_cfg("TARGET_ENV");
+
+    let (prefixes, headers): (Vec<PathBuf>, _) = match target_os.as_str() {
+        "netbsd" => (vec![PathBuf::from("/usr/pkg")], "include"),
+        "haiku" => (vec![PathBuf::from("/boot/system/develop")], "headers"),
+        "linux" => (vec![PathBuf::from("/usr/local"), PathBuf::from("/usr")], "include"),
+        "windows" if target_env == "gnu" => {
+            let environments: &[&str] = match architecture.as_str() {
+                "x86" => &["mingw32"],
+                "aarch64" => &["clangarm64"],
+                _ => &["ucrt64", "mingw64", "clang64"],
+            };
+
+            let prefixes = env::var_os("MINGW_PREFIX")
+                .map(PathBuf::from)
+                .into_iter()
+                .chain(
+                    environments
+                        .iter()
+                        .map(|environment| Path::new("C:\\msys64").join(environment)),
+                )
+                .collect();
+
+            (prefixes, "include")
+        }
+        target_os if is_bsd(target_os) => (vec![PathBuf::from("/usr/local")], "include"),
+        _ => return None,
+    };
+
+    let file_names = library_file_names("opus", is_static, &target_os, &target_env);
+
+    // Debian and Ubuntu install into multiarch directories named after the
+    // GNU host, such as `/usr/lib/x86_64-linux-gnu`, except for `i386`,
+    // others into `lib64` or `lib`.
+    let multiarch = gnu_host_triple(&target);
+    let multiarch = if multiarch.starts_with("i686-") {
+        format!("i386{}", &multiarch["i686".len()..])
+    } else {
+        multiarch
+    };
+
+    prefixes.into_iter().find_map(|prefix| {
+        let include_directory = prefix.join(headers).join("opus");
+
+        if !include_directory.join("opus.h").is_file() {
+            return None;
+        }
+
+        [
+            prefix.join("lib").join(&multiarch),
+            prefix.join("lib64"),
+            prefix.join("lib"),
+        ]
+        .iter()
+        .find(|lib_directory| {
+            file_names
+                .iter()
+                .any(|file_name| lib_directory.join(file_name).is_file())
+        })
+        .map(|lib_directory| (lib_directory.clone(), include_directory.clone()))
+    })
+}
+
+/// Links the Opus described by the pkg-config file passed as
+/// `LIBOPUS_PC_FILE` or `OPUS_PC_FILE`, parsed without requiring `pkg-config`
+/// itself, see `parse_pc_file`. Returns `false` if none was passed or it is
+/// older than `minimum_opus_version`.
+fn link_via_pc_file(is_static: bool) -> Result<bool, BuildError> {
+    const STEP: &str = "Reading the pkg-config file of `OPUS_PC_FILE`";
+
+    let pc_path = match opus_env("PC_FILE") {
+        Some(pc_path) => PathBuf::from(pc_path),
+        None => return Ok(false),
+    };
+
+    let contents = std::fs::read_to_string(&pc_path).map_err(|error| {
+        BuildError::new(STEP, format!("Could not read `{}`: {}", pc_path.display(), error))
+    })?;
+
+    let pc_directory = pc_path.parent().unwrap_or_else(|| Path::new("."));
+    let pc_file = parse_pc_file(&contents, &pc_directory.to_string_lossy());
+
+    if let (Some(version), Some(minimum)) = (pc_file.version.as_ref(), minimum_opus_version()) {
+        if is_at_least_version(version, &minimum) == Some(false) {
+            println!(
+                "cargo:info=Skipped the Opus {} of {}, requiring at least {}.",
+                version,
+                pc_path.display(),
+                minimum
+            );
+
+            return Ok(false);
+        }
+    }
+
+    let lib_name = pc_file
+        .lib_names
+        .first()
+        .cloned()
+        .unwrap_or_else(|| "opus".to_string());
+    let lib_directories: Vec<PathBuf> =
+        pc_file.lib_directories.iter().map(PathBuf::from).collect();
+
+    if lib_directories.is_empty() {
+        return Err(BuildError::new(
+            STEP,
+            format!("{} lists no library directory via `-L` in `Libs`.", pc_path.display()),
+        ));
+    }
+
+    let lib_directory = find_library_directory(&lib_directories, &lib_name, is_static)?;
+
+    // `Cflags` usually points into `include/opus`, but may name `include`.
+    let include_directories: Vec<PathBuf> = pc_file
+        .include_directories
+        .iter()
+        .map(PathBuf::from)
+        .flat_map(|include_directory| vec![include_directory.join("opus"), include_directory])
+        .filter(|include_directory| include_directory.join("opus.h").is_file())
+        .collect();
+
+    println!(
+        "cargo:info=Found `Opus` via {}: {}",
+        pc_path.display(),
+        lib_directory.display()
+    );
+
+    link_installed_opus(&lib_name, is_static, &lib_directory, &include_directories)?;
+
+    Ok(true)
+}
+
+/// Returns the directories of a pre-installed Opus and its companion libraries,
+/// passed as `LIBOPUS_LIB_DIR` or `OPUS_LIB_DIR` and separated like `PATH`,
+/// e.g. to cover both `lib` and `lib64`.
+fn find_installed_libraries() -> Option<Vec<PathBuf>> {
+    opus_env("LIB_DIR").map(|lib_directories| {
+        env::split_paths(&lib_directories)
+            .filter(|lib_directory| !lib_directory.as_os_str().is_empty())
+            .collect()
+    })
+}
+
+/// Returns the first of `lib_directories` containing `lib_name` for the
+/// requested linking, see `library_file_names`, failing if none does, as the
+/// linker would otherwise pick another Opus or fail with a vaguer error.
+///
+/// A requested soname, see `linked_soname`, also accepts versioned files,
+/// such as `libopus.so.0`.
+fn find_library_directory(
+    lib_directories: &[PathBuf],
+    lib_name: &str,
+    is_static: bool,
+) -> Result<PathBuf, BuildError> {
+    let file_names = library_file_names(
+        lib_name,
+        is_static,
+        &cargo_cfg("TARGET_OS"),
+        &cargo_cfg("TARGET_ENV"),
+    );
+    let versioned_prefix = format!("lib{}.so.", lib_name);
+    let is_linking_soname = !is_static && opus_env("LINK_SONAME").is_some();
+
+    let has_library_file = |lib_directory: &PathBuf| {
+        file_names
+            .iter()
+            .any(|file_name| lib_directory.join(file_name).is_file())
+            || (is_linking_soname
+                && std::fs::read_dir(lib_directory)
+                    .map(|entries| {
+                        entries.filter_map(Result::ok).any(|entry| {
+                            entry
+                                .file_name()
+                                .to_string_lossy()
+                                .starts_with(&versioned_prefix)
+                        })
+                    })
+                    .unwrap_or(false))
+    };
+
+    match lib_directories
+        .iter()
+        .find(|lib_directory| has_library_file(lib_directory))
+    {
+        Some(lib_directory) => Ok(lib_directory.clone()),
+        None => Err(BuildError::new(
+            &format!("Finding `{}` in `OPUS_LIB_DIR`", lib_name),
+            format!(
+                "None of {} exists in {}, set `OPUS_LIB_NAME` if the \
+                 library-file is named differently or `OPUS_LINKAGE` to link it the other way.",
+                file_names
+                    .iter()
+                    .map(|file_name| format!("`{}`", file_name))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                lib_directories
+                    .iter()
+                    .map(|lib_directory| format!("`{}`", lib_directory.display()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        )),
+    }
+}
+
+/// Returns the headers of a pre-installed Opus, passed as
+/// `LIBOPUS_INCLUDE_DIR` or `OPUS_INCLUDE_DIR`.
+///
+/// Fails if the directory lacks `opus.h`.
+fn find_installed_include() -> Result<Option<PathBuf>, BuildError> {
+    let include_directory = match opus_env("INCLUDE_DIR") {
+        Some(include_directory) => PathBuf::from(include_directory),
+        None => return Ok(None),
+    };
+
+    if !include_directory.join("opus.h").is_file() {
+        return Err(BuildError::new(
+            "Locating the Opus headers",
+            format!(
+                "`OPUS_INCLUDE_DIR` does not contain `opus.h`: {}",
+                include_directory.display()
+            ),
+        ));
+    }
+
+    Ok(Some(include_directory))
+}
+
+/// Copies the Opus headers in `include_directory` into
+/// `OUT_DIR/include/opus`, so dependents find them at the same place
+/// wherever Opus comes from, and returns the latter.
+fn install_headers(include_directory: &Path) -> Result<PathBuf, BuildError> {
+    let header_directory =
+        PathBuf::from(env::var("OUT_DIR").expect("Environment variable `OUT_DIR` is missing."))
+            .join("include")
+            .join("opus");
+
+    if include_directory == header_directory.as_path() {
+        return Ok(header_directory);
+    }
+
+    std::fs::create_dir_all(&header_directory)
+        .and_then(|_| {
+            for entry in std::fs::read_dir(include_directory)? {
+                let header_path = entry?.path();
+
+                match (header_path.file_name(), header_path.extension()) {
+                    (Some(file_name), Some(extension)) if extension == "h" => {
+                        std::fs::copy(&header_path, header_directory.join(file_name))?;
+                    }
+                    _ => {}
+                }
+            }
+
+            Ok(())
+        })
+        .map_err(|error| {
+            BuildError::new(
+                "Installing the Opus headers",
+                format!(
+                    "Could not copy the headers of `{}`: {}",
+                    include_directory.display(),
+                    error
+                ),
+            )
+        })?;
+
+    Ok(header_directory)
+}
+
+/// Installs the headers of an external Opus from the first of
+/// `include_directories` containing `opus.h`, see `install_headers`.
+fn install_found_headers(include_directories: &[PathBuf]) -> Result<Option<PathBuf>, BuildError> {
+    match include_directories
+        .iter()
+        .find(|include_directory| include_directory.join("opus.h").is_file())
+    {
+        Some(include_directory) => install_headers(include_directory).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Returns the name of a pre-installed Opus library-file, passed as
+/// `LIBOPUS_LIB_NAME` or `OPUS_LIB_NAME` and defaulting to `opus`.
+///
+/// For instance, `libopus` links `libopus.lib` instead of `opus.lib` on MSVC.
+fn installed_lib_name() -> String {
+    opus_env("LIB_NAME").unwrap_or_else(|| "opus".to_string())
+}
+
+/// Whether the linking was requested via `OPUS_LINKAGE`, `OPUS_STATIC`, or a
+/// feature rather than left to `default_library_linking`.
+fn is_linkage_requested() -> bool {
+    opus_env_name("LINKAGE").is_some()
+        || opus_env_name("STATIC").is_some()
+        || cfg!(feature = "static")
+        || cfg!(feature = "dynamic")
+        || is_bundled_archive()
+}
+
+/// The library-file `lib_name.lib` or `liblib_name.a` in `lib_directory` when
+/// targeting Windows, where either name may be a static library or an import
+/// library alike, along with whether it is an import library.
+///
+/// Returns `None` for other targets, missing files, and unknown contents.
+fn windows_library_kind(lib_name: &str, lib_directory: &Path) -> Option<(PathBuf, bool)> {
+    if cargo_cfg("TARGET_OS") != "windows" {
+        return None;
+    }
+
+    [format!("{}.lib", lib_name), format!("lib{}.a", lib_name)]
+        .iter()
+        .map(|file_name| lib_directory.join(file_name))
+        .filter_map(|library_file| {
+            let is_import = is_import_library(&std::fs::read(&library_file).ok()?)?;
+
+            Some((library_file, is_import))
+        })
+        .next()
+}
+
+/// Describes a library-file as import library or static library.
+fn library_kind_text(is_import: bool) -> &'static str {
+    if is_import {
+        "an import library"
+    } else {
+        "a static library"
+    }
+}
+
+/// Resolves the linking of the Opus in `OPUS_LIB_DIR` by the kind of its
+/// library-file on Windows, see `windows_library_kind`.
+///
+/// A linking left to the target's default follows the library-file, whereas
+/// a requested one contradicting it fails the build, instead of the link with
+/// unresolved `__imp_`-symbols or at runtime lacking `opus.dll`.
+fn installed_linkage(lib_directories: &[PathBuf], is_static: bool) -> Result<bool, BuildError> {
+    let lib_name = installed_lib_name();
+    let kind = lib_directories
+        .iter()
+        .filter_map(|lib_directory| windows_library_kind(&lib_name, lib_directory))
+        .next();
+
+    let (library_file, is_import) = match kind {
+        Some((library_file, is_import)) if is_import == is_static => (library_file, is_import),
+        _ => return Ok(is_static),
+    };
+
+    if is_linkage_requested() {
+        return Err(BuildError::new(
+            "Verifying the library-file in `OPUS_LIB_DIR`",
+            format!(
+                "{} is {}, but {} linking is requested, set `OPUS_LINKAGE` to `{}` \
+                 or point `OPUS_LIB_DIR` to {} instead.",
+                library_file.display(),
+                library_kind_text(is_import),
+                if is_static { "static" } else { "dynamic" },
+                if is_import { "dynamic" } else { "static" },
+                library_kind_text(!is_import)
+            ),
+        ));
+    }
+
+    println!(
+        "cargo:info={} is {}, linking {}.",
+        library_file.display(),
+        library_kind_text(is_import),
+        if is_import { "dynamically" } else { "statically" }
+    );
+
+    Ok(!is_import)
+}
+
+/// Decides whether Opus is linked statically, in the following precedence:
+///
+/// 1. The value of `LIBOPUS_LINKAGE` or `OPUS_LINKAGE`, `static` or `dynamic`.
+/// 2. The value of `LIBOPUS_STATIC` or `OPUS_STATIC`, `0` or `false` selecting
+///    dynamic linking.
+/// 3. The `static`-feature, winning over the `dynamic`-feature with a warning
+///    if both are enabled, as Cargo unifies features across the workspace. The
+///    `strict-linkage`-feature turns the conflict into an error instead.
+/// 4. The target's default as decided by `default_library_linking`.
+///
+/// iOS, tvOS, and watchOS only permit static linking, hence it is always
+/// picked.
+fn is_static_build() -> Result<bool, BuildError> {
+    let target_os = cargo_cfg("TARGET_OS");
+
+    if target_os == "ios" || target_os == "tvos" || target_os == "watchos" {
+        println!("cargo:info=Targeting {}, linking statically.", target_os);
+
+        return Ok(true);
+    }
+
+    let requested = if let Some(variable) = opus_env_name("LINKAGE") {
+        let value = env::var(&variable).unwrap_or_default();
+        let is_static = parse_linkage(&variable, &value);
+
+        Some((variable, is_static))
+    } else if let Some(variable) = opus_env_name("STATIC") {
+        let value = env::var(&variable).unwrap_or_default();
+        let is_static = parse_bool(&variable, &value);
+
+        Some((variable, is_static))
+    } else {
+        None
+    };
+
+    if let Some((variable, is_static)) = requested {
+        let is_static =
+            is_static.map_err(|error| BuildError::new("Selecting the linking", error))?;
+
+        println!(
+            "cargo:info=Environment variable `{}` requests {} linking.",
+            variable,
+            rustc_linking_word(is_static)
+        );
+
+        if !is_static && is_bundled_archive() {
+            return Err(BuildError::new(
+                "Selecting the linking",
+                format!(
+                    "The `bundle-archive`-feature requires static linking, but `{}` requests \
+                     dynamic linking.",
+                    variable
+                ),
+            ));
+        }
+
+        Ok(is_static)
+    } else if is_bundled_archive() {
+        println!("cargo:info=Bundle-archive feature enabled, linking statically.");
+
+        Ok(true)
+    } else if cfg!(feature = "static") && cfg!(feature = "dynamic") {
+        if cfg!(feature = "strict-linkage") {
+            return Err(BuildError::new(
+                "Selecting the linking",
+                "Both the `static`- and `dynamic`-feature are enabled, e.g. by different \
+                 crates of the workspace, but the `strict-linkage`-feature forbids resolving \
+                 the conflict. Disable one or set `OPUS_LINKAGE` to `static` or `dynamic`.",
+            ));
+        }
+
+        println!(
+            "cargo:warning=Both the `static`- and `dynamic`-feature of `audiopus_sys` are \
+             enabled, linking statically. Set `OPUS_LINKAGE` to silence this."
+        );
+
+        Ok(true)
+    } else if cfg!(feature = "static") {
+        println!("cargo:info=Static feature enabled.");
+
+        Ok(true)
+    } else if cfg!(feature = "dynamic") {
+        println!("cargo:info=Dynamic feature enabled.");
+
+        Ok(false)
+    } else {
+        println!("cargo:info=No feature or environment variable found, linking by default.");
+
+        default_library_linking()
+    }
+}
+
+/// Whether Opus must be provided by the system instead of being built from
+/// the bundled sources or linked from the bundled library-files.
+///
+/// The `system-only`-feature cannot be disabled via `LIBOPUS_SYSTEM_ONLY=0`.
+fn is_system_only() -> Result<bool, BuildError> {
+    match opus_env_var("SYSTEM_ONLY") {
+        Some((variable, value)) => parse_bool(&variable, &value)
+            .map(|is_system_only| is_system_only || cfg!(feature = "system-only"))
+            .map_err(|error| BuildError::new("Finding a system Opus", error)),
+        None => Ok(cfg!(feature = "system-only")),
+    }
+}
+
+/// Whether Opus must be built from the bundled sources, bypassing
+/// `pkg-config`, `vcpkg`, and a pre-installed Opus, via the `vendored`-feature.
+fn is_vendored() -> bool {
+    cfg!(feature = "vendored")
+}
+
+/// Links the companion libraries `libopusfile` and `libopusenc` enabled via
+/// the `opusfile`- and `opusenc`-feature, as well as `libogg` they depend on.
+///
+/// They are not bundled, hence must be found via `pkg-config`, which resolves
+/// `libogg` too, or reside in `LIBOPUS_LIB_DIR` or `OPUS_LIB_DIR` alongside
+/// `libogg`, which is resolved once after both, see `ogg_source`. As they
+/// depend on Opus, they must be linked before it.
+fn link_companion_libraries(is_static: bool) -> Result<(), BuildError> {
+    let companions = [
+        (cfg!(feature = "opusfile"), "opusfile", "opusfile"),
+        (cfg!(feature = "opusenc"), "libopusenc", "opusenc"),
+    ];
+
+    let lib_directories = find_installed_libraries();
+    let mut sources = Vec::new();
+
+    for &(_, package, lib_name) in companions.iter().filter(|companion| companion.0) {
+        #[cfg(any(unix, target_env = "gnu"))]
+        {
+            if opus_env("NO_PKG").is_none()
+                && pkg_config::Config::new()
+                    .statik(is_static)
+                    .probe(package)
+                    .is_ok()
+            {
+                println!("cargo:info=Found `{}` via `pkg_config`.", package);
+                sources.push(CompanionSource::PkgConfig);
+
+                continue;
+            }
+        }
+
+        match lib_directories {
+            Some(ref lib_directories) => {
+                println!("cargo:info=Pre-installed `{}` will be linked.", package);
+
+                let lib_directory = find_library_directory(lib_directories, lib_name, is_static)?;
+
+                emit_link(lib_name, is_static, &lib_directory)?;
+                sources.push(CompanionSource::LibDir);
+            }
+            None => {
+                return Err(BuildError::new(
+                    &format!("Finding `{}`", package),
+                    format!(
+                        "`{}` could not be found via `pkg-config`, install it or \
+                         point `OPUS_LIB_DIR` to the directory containing it, \
+                         `libogg`, and Opus.",
+                        package
+                    ),
+                ))
+            }
+        }
+    }
+
+    match (ogg_source(&sources), lib_directories) {
+        (Some(CompanionSource::PkgConfig), _) => {
+            println!("cargo:info=Linking `libogg` as resolved via `pkg_config`.");
+        }
+        (Some(CompanionSource::LibDir), Some(ref lib_directories)) => {
+            let lib_directory = find_library_directory(lib_directories, "ogg", is_static)?;
+
+            emit_link("ogg", is_static, &lib_directory)?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Returns the entry of the build cache named by `LIBOPUS_BUILD_CACHE_DIR` or
+/// `OPUS_BUILD_CACHE_DIR` holding the Opus built for the current target,
+/// letting several crates and clean builds share it instead of rebuilding.
+///
+/// Entries are keyed by target, Opus version, and a hash of the backend,
+/// linking, features, profile, and the variables configuring the build.
+/// Sources of unknown version and the prebuilt MSVC library-files are never
+/// cached.
+fn build_cache_entry(is_static: bool) -> Option<PathBuf> {
+    let cache_directory = opus_env("BUILD_CACHE_DIR")?;
+
+    if cfg!(all(windows, target_env = "msvc", not(feature = "cmake"))) && !is_building_with_cc() {
+        return None;
+    }
+
+    let version = opus_source_version()?;
+    let target = env::var("TARGET").unwrap_or_default();
+
+    let backend = if is_building_with_cc() {
+        "cc"
+    } else if cfg!(feature = "cmake") {
+        "cmake"
+    } else if cfg!(feature = "meson") {
+        "meson"
+    } else {
+        "autotools"
+    };
+    let mut variables: Vec<String> = [
+        "OPT_LEVEL",
+        "DEBUG",
+        "CARGO_CFG_TARGET_FEATURE",
+        "CARGO_CFG_SANITIZE",
+        "CARGO_ENCODED_RUSTFLAGS",
+    ]
+    .iter()
+    .chain(BUILD_VARIABLES)
+    .map(|variable| variable.to_string())
+    .collect();
+
+    for variable in OPUS_VARIABLES {
+        if !["BUILD_CACHE_DIR", "BUILD_VERBOSE", "CC_WRAPPER", "JOBS"].contains(variable) {
+            variables.extend(opus_variable_names(variable, Some(&target)));
+        }
+    }
+
+    for tool in &["CC", "AR", "RANLIB"] {
+        variables.push(format!("{}_{}", tool, target));
+        variables.push(format!("{}_{}", tool, target.replace('-', "_")));
+    }
+
+    let mut features: Vec<String> = env::vars()
+        .map(|(variable, _)| variable)
+        .filter(|variable| variable.starts_with("CARGO_FEATURE_"))
+        .collect();
+    features.sort();
+
+    let mut key = vec![
+        backend.to_string(),
+        rustc_linking_word(is_static).to_string(),
+    ];
+    key.extend(features);
+
+    for variable in variables {
+        let value = env::var(&variable).unwrap_or_default();
+
+        key.push(format!("{}={}", variable, value));
+    }
+
+    let hash = stable_hash(key.join("\n").as_bytes());
+
+    Some(
+        Path::new(&cache_directory)
+            .join(target)
+            .join(format!("{}-{:016x}", version, hash)),
+    )
+}
+
+/// Restores the Opus cached in `cache_entry` into `build_directory` and links
+/// it, as the backends do after building.
+fn link_cached_opus(
+    cache_entry: &Path,
+    build_directory: &Path,
+    is_static: bool,
+) -> Result<(), BuildError> {
+    println!(
+        "cargo:info=Reusing the Opus cached in {}.",
+        cache_entry.display()
+    );
+
+    copy_directory(cache_entry, build_directory).map_err(|error| {
+        BuildError::new(
+            "Restoring the cached Opus",
+            format!(
+                "Could not copy {} to {}: {}",
+                cache_entry.display(),
+                build_directory.display(),
+                error
+            ),
+        )
+    })?;
+
+    let lib_directory = build_directory.join("lib");
+    let header_directory = build_directory.join("include").join("opus");
+
+    emit_link("opus", is_static, &lib_directory)?;
+    probe_opus("opus", is_static, &lib_directory)?;
+    generate_bindings(std::slice::from_ref(&header_directory))?;
+    check_constants(std::slice::from_ref(&header_directory))?;
+    emit_pkg_config_file(build_directory)?;
+    emit_metadata(
+        is_static,
+        "opus",
+        &lib_directory,
+        Some(&header_directory),
+        Some(build_directory),
+        opus_source_version().as_ref().map(String::as_str),
+    );
+
+    Ok(())
+}
+
+/// Stores the Opus installed in `build_directory` as `cache_entry`.
+///
+/// The entry is staged next to its final path and renamed into place, thus
+/// concurrent builds never see a partial entry. Failing to store it only
+/// costs rebuilding later, hence it does not fail the build.
+fn store_in_build_cache(build_directory: &Path, cache_entry: &Path) {
+    let staging_entry = cache_entry.with_extension(format!("tmp{}", std::process::id()));
+
+    let result = ["lib", "include"]
+        .iter()
+        .try_for_each(|directory| {
+            copy_directory(
+                &build_directory.join(directory),
+                &staging_entry.join(directory),
+            )
+        })
+        .and_then(|_| std::fs::rename(&staging_entry, cache_entry));
+
+    match result {
+        Ok(()) => println!(
+            "cargo:info=Cached the built Opus in {}.",
+            cache_entry.display()
+        ),
+        Err(error) => {
+            println!(
+                "cargo:info=Could not cache the built Opus in {}: {}",
+                cache_entry.display(),
+                error
+            );
+
+            let _ = std::fs::remove_dir_all(&staging_entry);
+        }
+    }
+}
+
+/// Finds, builds, or links Opus, trying each source in turn.
+fn find_or_build_opus() -> Result<(), BuildError> {
+    check_symbol_prefixing()?;
+
+    if cfg!(feature = "dlopen") {
+        println!("cargo:info=Opus will be loaded at runtime, skipping linking.");
+
+        return generate_bindings(&[]);
+    }
+
+    if let Some(dll_path) = opus_env("DLL") {
+        return link_opus_dll(Path::new(&dll_path));
+    }
+
+    if cfg!(feature = "raw-dylib") && cargo_cfg("TARGET_OS") == "windows" {
+        println!("cargo:info=Opus will be linked via `raw-dylib`, requiring no import library.");
+
+        return generate_bindings(&[]);
+    }
+
+    // docs.rs lacks network access and most tools, yet only needs the
+    // declarations of the bindings.
+    if env::var_os("DOCS_RS").is_some() {
+        println!("cargo:info=Building on docs.rs, skipping building and linking Opus.");
+
+        return generate_bindings(&[]);
+    }
+
+    let is_system_only = is_system_only()?;
+
+    if is_vendored() && is_system_only {
+        return Err(BuildError::new(
+            "Finding a system Opus",
+            "The `vendored`-feature building the bundled Opus contradicts the \
+             `system-only`-feature or `LIBOPUS_SYSTEM_ONLY`/`OPUS_SYSTEM_ONLY`.",
+        ));
+    }
+
+    let installed_lib_directories = if is_vendored() {
+        println!("cargo:info=The `vendored`-feature forces building the bundled Opus.");
+
+        None
+    } else {
+        find_installed_libraries()
+    };
+
+    let is_static = is_static_build()?;
+    let is_static = match installed_lib_directories {
+        Some(ref lib_directories) => installed_linkage(lib_directories, is_static)?,
+        None => is_static,
+    };
+
+    link_companion_libraries(is_static)?;
+
+    if !is_vendored() && link_via_pc_file(is_static)? {
+        return Ok(());
+    }
+
+    #[cfg(any(unix, target_env = "gnu"))]
+    {
+        if is_vendored() || opus_env("NO_PKG").is_some() {
+            println!("cargo:info=Bypassed `pkg-config`.");
+        } else if find_via_pkg_config(is_static)? {
+            println!("cargo:info=Found `Opus` via `pkg_config`.");
+
+            return Ok(());
+        } else {
+            println!("cargo:info=`pkg_config` could not find `Opus`.");
+        }
+    }
+
+    #[cfg(all(windows, target_env = "msvc"))]
+    {
+        if is_vendored() || opus_env("NO_VCPKG").is_some() {
+            println!("cargo:info=Bypassed `vcpkg`.");
+        } else if find_via_vcpkg()? {
+            println!("cargo:info=Found `Opus` via `vcpkg`.");
+
+            return Ok(());
+        } else {
+            println!("cargo:info=`vcpkg` could not find `Opus`.");
+        }
+    }
+
+    if let Some(lib_directories) = installed_lib_directories {
+        let lib_name = installed_lib_name();
+        let lib_directory = find_library_directory(&lib_directories, &lib_name, is_static)?;
+
+        println!(
+            "cargo:info=Pre-installed Opus will be linked: {}",
+            lib_directory.display()
+        );
+
+        let include_directories: Vec<PathBuf> = find_installed_include()?.into_iter().collect();
+
+        return link_installed_opus(&lib_name, is_static, &lib_directory, &include_directories);
+    }
+
+    if !is_vendored() {
+        if opus_env("NO_BREW").is_some() {
+            println!("cargo:info=Bypassed Homebrew and MacPorts.");
+        } else if let Some((lib_directory, include_directory)) = find_via_macos_prefixes(is_static)
+        {
+            println!("cargo:info=Found `Opus` in {}.", lib_directory.display());
+
+            return link_installed_opus("opus", is_static, &lib_directory, &[include_directory]);
+        }
+
+        if let Some((lib_directory, include_directory)) = find_via_package_prefixes(is_static) {
+            println!("cargo:info=Found `Opus` in {}.", lib_directory.display());
+
+            return link_installed_opus("opus", is_static, &lib_directory, &[include_directory]);
+        }
+    }
+
+    if is_system_only {
+        let searched = if cfg!(all(windows, target_env = "msvc")) {
+            "`vcpkg`"
+        } else {
+            "`pkg-config`"
+        };
+
+        return Err(BuildError::new(
+            "Finding a system Opus",
+            format!(
+                "Opus could not be found via {}, `LIBOPUS_LIB_DIR`, or \
+                 `OPUS_LIB_DIR` and building the bundled Opus is forbidden by the \
+                 `system-only`-feature or `LIBOPUS_SYSTEM_ONLY`/`OPUS_SYSTEM_ONLY`.",
+                searched
+            ),
+        ));
+    }
+
+    if let Some(jobs) = build_jobs()? {
+        println!("cargo:info=Building Opus with {} parallel jobs.", jobs);
+
+        // The `cc`- and `cmake`-crate read the job count from `NUM_JOBS`.
+        env::set_var("NUM_JOBS", jobs.to_string());
+    }
+
+    let build_variable =
+        std::env::var("OUT_DIR").expect("Environment variable `OUT_DIR` is missing.");
+
+    let build_path = Path::new(&build_variable);
+
+    let _ = std::fs::remove_file(build_log_path());
+
+    #[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+    {
+        let missing_tools = if is_building_with_cc() || cfg!(feature = "meson") {
+            Vec::new()
+        } else {
+            missing_autotools()
+        };
+
+        if !missing_tools.is_empty() {
+            let missing_tools: Vec<String> =
+                missing_tools.iter().map(|tool| format!("`{}`", tool)).collect();
+
+            println!(
+                "cargo:info=Missing {}, building Opus via the `cc`-crate instead of autotools.",
+                missing_tools.join(", ")
+            );
+
+            if is_in_cross_container() {
+                println!(
+                    "cargo:info=Install `autoconf`, `automake`, and `libtool` via \
+                     `pre-build` in `Cross.toml` to build via autotools within `cross`."
+                );
+            }
+
+            IS_FALLING_BACK_TO_CC.store(true, Ordering::Relaxed);
+        }
+    }
+
+    // The `cc`-crate only builds static libraries.
+    if is_building_with_cc() {
+        check_cc_linkage(is_static)?;
+    }
+
+    let is_linked_statically = is_static || is_building_with_cc();
+    let cache_entry = build_cache_entry(is_linked_statically);
+
+    match cache_entry {
+        Some(ref cache_entry) if cache_entry.join("lib").is_dir() => {
+            link_cached_opus(cache_entry, build_path, is_linked_statically)?;
+        }
+        _ => {
+            if is_building_with_cc() {
+                build_opus_with_cc(build_path)?;
+            } else {
+                build_opus(build_path, is_static)?;
+            }
+
+            println!(
+                "cargo:info=Built Opus, the output of all steps is logged in {}.",
+                build_log_path().display()
+            );
+
+            if let Some(ref cache_entry) = cache_entry {
+                store_in_build_cache(build_path, cache_entry);
+            }
+        }
+    }
+
+    emit_c_deps(is_linked_statically)
+}
+
+/// Returns the commands installing a system Opus via the target's package
+/// managers, letting users who do not need the bundled Opus get going.
+fn install_hint() -> Option<String> {
+    let commands: &[&str] = match (cargo_cfg("TARGET_OS").as_str(), cargo_cfg("TARGET_ENV").as_str()) {
+        ("linux", _) => &[
+            "apt install libopus-dev pkg-config    (Debian, Ubuntu)",
+            "dnf install opus-devel pkgconf        (Fedora)",
+            "pacman -S opus pkgconf                (Arch Linux)",
+            "apk add opus-dev pkgconf              (Alpine Linux)",
+        ],
+        ("macos", _) => &["brew install opus pkg-config", "port install libopus pkgconfig"],
+        ("windows", "msvc") => &["vcpkg install opus"],
+        ("windows", "gnu") => &["pacman -S mingw-w64-x86_64-opus    (MSYS2)"],
+        ("freebsd", _) | ("dragonfly", _) => &["pkg install opus pkgconf"],
+        ("openbsd", _) => &["pkg_add opus"],
+        ("netbsd", _) => &["pkgin install libopus"],
+        ("haiku", _) => &["pkgman install opus_devel"],
+        _ => return None,
+    };
+
+    let mut hint = String::from("Installing Opus via a package manager may resolve this:");
+
+    for command in commands {
+        hint.push_str("\n    ");
+        hint.push_str(command);
+    }
+
+    Some(hint)
+}
+
+/// Suffixes of the `LIBOPUS_`- and `OPUS_`-prefixed variables read by the
+/// build script.
+const OPUS_VARIABLES: &[&str] = &[
+    "BUILD_CACHE_DIR",
+    "BUILD_VERBOSE",
+    "CC_WRAPPER",
+    "CHECK_ASM",
+    "CMAKE_ARGS",
+    "CMAKE_FLAGS",
+    "CMAKE_TOOLCHAIN_FILE",
+    "CONFIGURE_FLAGS",
+    "DLL",
+    "DLL_OUT_DIR",
+    "EMIT_PC",
+    "FUCHSIA_SYSROOT",
+    "GC_SECTIONS",
+    "HIDE_SYMBOLS",
+    "INCLUDE_DIR",
+    "INHERIT_CPU",
+    "INTRINSICS",
+    "JOBS",
+    "LIB_DIR",
+    "LIB_NAME",
+    "LINKAGE",
+    "LINK_LIBS",
+    "LINK_SONAME",
+    "LTO",
+    "MARCH",
+    "MAX_SIMD",
+    "MESON_ARGS",
+    "MIN_VERSION",
+    "MSVC_CRT",
+    "MSVC_DIR",
+    "MSVC_PREBUILT",
+    "MTUNE",
+    "NEON",
+    "NO_BREW",
+    "NO_PKG",
+    "NO_PROBE",
+    "NO_VCPKG",
+    "OPT_LEVEL",
+    "PC_FILE",
+    "RTCD",
+    "SKIP_CONFIGURE",
+    "SOURCE_DIR",
+    "STATIC",
+    "STRIP_DEBUG",
+    "SYSTEM_ONLY",
+    "UNIVERSAL_ARCHS",
+    "VERIFY_EXPORTS",
+    "WHOLE_ARCHIVE",
+];
+
+/// Further variables read by the build script, not counting those Cargo sets.
+const BUILD_VARIABLES: &[&str] = &[
+    "ANDROID_API_LEVEL",
+    "ANDROID_NDK",
+    "ANDROID_NDK_HOME",
+    "ANDROID_NDK_ROOT",
+    "ANDROID_PLATFORM",
+    "AR",
+    "CARGO_NDK_ANDROID_PLATFORM",
+    "CC",
+    "CFLAGS",
+    "CMAKE",
+    "CMAKE_TOOLCHAIN_FILE",
+    "CROSS_COMPILE",
+    "CROSS_RUNNER",
+    "CROSS_TOOLCHAIN_PREFIX",
+    "DLLTOOL",
+    "DOCS_RS",
+    "IPHONEOS_DEPLOYMENT_TARGET",
+    "LDFLAGS",
+    "MAKE",
+    "MESON",
+    "MINGW_PREFIX",
+    "MSYSTEM",
+    "NINJA",
+    "NM",
+    "OBJCOPY",
+    "OHOS_NDK_HOME",
+    "OHOS_SDK_NATIVE",
+    "QNX_HOST",
+    "QNX_TARGET",
+    "RANLIB",
+    "RUSTC_WRAPPER",
+    "SH",
+    "TARGET_AR",
+    "TARGET_CC",
+    "TARGET_RANLIB",
+    "TVOS_DEPLOYMENT_TARGET",
+    "WATCHOS_DEPLOYMENT_TARGET",
+];
+
+/// Tells Cargo to rerun the build script only if a variable it reads, the Opus
+/// sources, or the bindings checked against the headers change, instead of
+/// on every change to the package, rebuilding Opus needlessly.
+fn emit_rerun_directives() {
+    let target = env::var("TARGET").ok();
+
+    for variable in OPUS_VARIABLES {
+        for name in opus_variable_names(variable, target.as_ref().map(String::as_str)) {
+            println!("cargo:rerun-if-env-changed={}", name);
+        }
+    }
+
+    for variable in BUILD_VARIABLES {
+        println!("cargo:rerun-if-env-changed={}", variable);
+    }
+
+    if let Ok(target) = env::var("TARGET") {
+        for tool in &["CC", "AR", "RANLIB"] {
+            println!("cargo:rerun-if-env-changed={}_{}", tool, target);
+            println!(
+                "cargo:rerun-if-env-changed={}_{}",
+                tool,
+                target.replace('-', "_")
+            );
+        }
+    }
+
+    let source_directory = opus_env("SOURCE_DIR").unwrap_or_else(|| "opus".to_string());
+
+    // Cargo reruns the build script every time if a watched path is missing,
+    // e.g. the uninitialised submodule when downloading the sources.
+    for path in &[
+        source_directory.as_str(),
+        "src/bindings.rs",
+        "src/projection.rs",
+    ] {
+        if Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={}", path);
+        }
+    }
+
+    for name in &["DLL", "PC_FILE"] {
+        if let Some(path) = opus_env(name) {
+            println!("cargo:rerun-if-changed={}", path);
+        }
+    }
+}
+
+fn main() {
+    emit_rerun_directives();
+    write_build_info(false, false, None);
+
+    if let Err(error) = find_or_build_opus() {
+        eprintln!("error: {}", error);
+        eprintln!(
+            "\nTarget: {}",
+            env::var("TARGET").unwrap_or_else(|_| cargo_cfg("TARGET_OS"))
+        );
+
+        if let Some(hint) = install_hint() {
+            eprintln!("{}", hint);
+        }
+
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,1632 @@
+//! Helpers of the build script depending on nothing but `std`, hence they can
+//! be unit-tested via `tests/build_util.rs`.
+
+use std::env;
+use std::ffi::OsString;
+use std::path::Path;
+use std::{fs, io};
+
+/// Outputs the library-file's prefix as word usable for actual arguments on
+/// commands or paths.
+pub fn rustc_linking_word(is_static_link: bool) -> &'static str {
+    if is_static_link {
+        "static"
+    } else {
+        "dylib"
+    }
+}
+
+/// Reads a `CARGO_CFG_`-prefixed environment variable describing the target,
+/// such as `TARGET_OS`, defaulting to an empty string.
+pub fn cargo_cfg(name: &str) -> String {
+    env::var(format!("CARGO_CFG_{}", name)).unwrap_or_default()
+}
+
+/// The names of the variable `name` prefixed with `LIBOPUS_` or `OPUS_` in
+/// order of precedence, those suffixed with `target`, either verbatim or with
+/// underscores as in `OPUS_LIB_DIR_x86_64_unknown_linux_gnu`, coming first.
+pub fn opus_variable_names(name: &str, target: Option<&str>) -> Vec<String> {
+    let mut suffixes = Vec::new();
+
+    if let Some(target) = target {
+        suffixes.push(format!("_{}", target));
+
+        if target.contains('-') {
+            suffixes.push(format!("_{}", target.replace('-', "_")));
+        }
+    }
+
+    suffixes.push(String::new());
+
+    suffixes
+        .iter()
+        .flat_map(|suffix| {
+            vec![
+                format!("LIBOPUS_{}{}", name, suffix),
+                format!("OPUS_{}{}", name, suffix),
+            ]
+        })
+        .collect()
+}
+
+/// Returns the name of the first set variable of `opus_variable_names` for
+/// the target being built.
+pub fn opus_env_name(name: &str) -> Option<String> {
+    let target = env::var("TARGET").ok();
+
+    opus_variable_names(name, target.as_ref().map(String::as_str))
+        .into_iter()
+        .find(|variable| env::var_os(variable).is_some())
+}
+
+/// Reads an environment variable either prefixed with `LIBOPUS_` or `OPUS_`,
+/// the former taking precedence, preferring the variants suffixed with the
+/// target, see `opus_variable_names`.
+pub fn opus_env(name: &str) -> Option<String> {
+    opus_env_name(name).and_then(|variable| env::var(variable).ok())
+}
+
+/// Like `opus_env`, but also returns the name of the variable read, such that
+/// errors name the one actually set.
+pub fn opus_env_var(name: &str) -> Option<(String, String)> {
+    opus_env_name(name).and_then(|variable| env::var(&variable).ok().map(|value| (variable, value)))
+}
+
+/// Parses the value of a boolean environment variable.
+///
+/// An empty value counts as `true`, as the mere presence of such variables
+/// used to enable them.
+pub fn parse_bool(name: &str, value: &str) -> Result<bool, String> {
+    match value.trim().to_lowercase().as_str() {
+        "" | "1" | "true" | "yes" | "on" => Ok(true),
+        "0" | "false" | "no" | "off" => Ok(false),
+        _ => Err(format!(
+            "Invalid value for `{}`: `{}`, expected `1`, `true`, `0`, or `false`.",
+            name, value
+        )),
+    }
+}
+
+/// Parses the value of a linkage variable, `static` or `dynamic`, into
+/// whether Opus is linked statically.
+pub fn parse_linkage(name: &str, value: &str) -> Result<bool, String> {
+    match value.trim().to_lowercase().as_str() {
+        "static" => Ok(true),
+        "dynamic" | "dylib" => Ok(false),
+        _ => Err(format!(
+            "Invalid value for `{}`: `{}`, expected `static` or `dynamic`.",
+            name, value
+        )),
+    }
+}
+
+/// Parses the value of the variable `name` selecting link-time optimisation
+/// into `fat` or `thin`, `1` or `true` being `fat`, and `None` if disabled.
+pub fn parse_lto(name: &str, value: &str) -> Result<Option<&'static str>, String> {
+    match value.trim().to_lowercase().as_str() {
+        "fat" | "1" | "true" | "yes" | "on" => Ok(Some("fat")),
+        "thin" => Ok(Some("thin")),
+        "0" | "false" | "no" | "off" => Ok(None),
+        _ => Err(format!(
+            "Invalid value for `{}`: `{}`, expected `fat`, `thin`, or `false`.",
+            name, value
+        )),
+    }
+}
+
+/// Parses the libraries of the variable `name`, separated by whitespace or
+/// commas, each either a plain name such as `m` or prefixed with its kind as
+/// in `static=ssp`, into the values of `cargo:rustc-link-lib`.
+pub fn parse_link_libs(name: &str, value: &str) -> Result<Vec<String>, String> {
+    value
+        .split(|character: char| character == ',' || character.is_whitespace())
+        .filter(|library| !library.is_empty())
+        .map(|library| {
+            let mut parts = library.splitn(2, '=');
+
+            match (parts.next(), parts.next()) {
+                (Some(_), None) => Ok(library.to_string()),
+                (Some("dylib"), Some(lib_name))
+                | (Some("static"), Some(lib_name))
+                | (Some("framework"), Some(lib_name))
+                    if !lib_name.is_empty() =>
+                {
+                    Ok(library.to_string())
+                }
+                _ => Err(format!(
+                    "Invalid library `{}` in `{}`, expected `NAME` or `KIND=NAME` with the \
+                     kind `dylib`, `static`, or `framework`.",
+                    library, name
+                )),
+            }
+        })
+        .collect()
+}
+
+/// Parses a checksum-manifest in the format of `sha256sum`, lines of a
+/// hexadecimal digest and a relative path separated by two spaces, into
+/// pairs of the path and the lowercase digest.
+///
+/// Empty lines and lines starting with `#` are skipped.
+#[cfg(all(windows, target_env = "msvc", not(feature = "cmake")))]
+pub fn parse_checksums(manifest: &str) -> Result<Vec<(String, String)>, String> {
+    manifest
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let mut parts = line.splitn(2, char::is_whitespace);
+
+            match (parts.next(), parts.next()) {
+                (Some(digest), Some(path))
+                    if digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit()) =>
+                {
+                    let path = path.trim_start().trim_start_matches('*');
+
+                    Ok((path.replace('\\', "/"), digest.to_lowercase()))
+                }
+                _ => Err(format!("Invalid checksum line `{}`.", line)),
+            }
+        })
+        .collect()
+}
+
+/// Opus' x86 SIMD-levels from the lowest, with the compiler flags presuming
+/// each and the suffix of its CMake options, e.g. `OPUS_X86_PRESUME_SSE4_1`.
+#[cfg(any(unix, target_env = "gnu", feature = "cmake"))]
+pub const X86_SIMD_LEVELS: &[(&str, &[&str], &str)] = &[
+    ("sse", &["-msse"], "SSE"),
+    ("sse2", &["-msse2"], "SSE2"),
+    ("sse4.1", &["-msse4.1"], "SSE4_1"),
+    ("avx2", &["-mavx", "-mfma", "-mavx2"], "AVX"),
+];
+
+/// Parses the highest x86 SIMD-level `value` of the variable `name` into the
+/// number of `X86_SIMD_LEVELS` up to and including it, `none` being zero.
+#[cfg(any(unix, target_env = "gnu", feature = "cmake"))]
+pub fn parse_simd_level(name: &str, value: &str) -> Result<usize, String> {
+    let level = value.trim().to_lowercase();
+
+    if level == "none" {
+        return Ok(0);
+    }
+
+    X86_SIMD_LEVELS
+        .iter()
+        .position(|(name, _, _)| *name == level)
+        .map(|index| index + 1)
+        .ok_or_else(|| {
+            format!(
+                "Invalid value for `{}`: `{}`, expected `none`, `sse`, `sse2`, `sse4.1`, or \
+                 `avx2`.",
+                name, value
+            )
+        })
+}
+
+/// Returns the CPU of the last `-C target-cpu` in Cargo's
+/// `CARGO_ENCODED_RUSTFLAGS`, whose flags are separated by `\x1f`.
+pub fn rustflags_target_cpu(encoded_rustflags: &str) -> Option<String> {
+    let flags: Vec<&str> = encoded_rustflags.split('\x1f').collect();
+    let mut target_cpu = None;
+
+    for (index, flag) in flags.iter().enumerate() {
+        let option = if *flag == "-C" || *flag == "--codegen" {
+            flags.get(index + 1).cloned().unwrap_or_default()
+        } else if flag.starts_with("--codegen=") {
+            &flag["--codegen=".len()..]
+        } else if flag.starts_with("-C") {
+            &flag["-C".len()..]
+        } else {
+            continue;
+        };
+
+        if option.starts_with("target-cpu=") {
+            target_cpu = Some(option["target-cpu=".len()..].to_string());
+        }
+    }
+
+    target_cpu
+}
+
+/// Converts `path` into a single argument for `configure`.
+///
+/// MSYS2 expects forward slashes on Windows, elsewhere the path is passed
+/// verbatim, keeping spaces and non-UTF-8 characters intact.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+pub fn configure_path(path: &Path) -> OsString {
+    if cfg!(windows) {
+        OsString::from(path.to_string_lossy().replace('\\', "/"))
+    } else {
+        path.as_os_str().to_os_string()
+    }
+}
+
+/// Copies the directory `source` with all its contents into `destination`,
+/// creating it if missing, so the build needs no `cp`.
+///
+/// Symbolic links are followed, the permissions of files are kept, making
+/// scripts such as `autogen.sh` stay executable.
+pub fn copy_directory(source: &Path, destination: &Path) -> io::Result<()> {
+    fs::create_dir_all(destination)?;
+
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let target = destination.join(entry.file_name());
+
+        if fs::metadata(entry.path())?.is_dir() {
+            copy_directory(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Recognises common causes of a failed build step in its captured `output`,
+/// returning how to remedy them.
+pub fn failure_hint(output: &str) -> Option<&'static str> {
+    for line in output.lines() {
+        let is_missing = line.contains("not found") || line.contains("No such file");
+
+        if line.contains("C compiler cannot create executables")
+            || line.contains("no acceptable C compiler found")
+        {
+            return Some(
+                "The C compiler cannot build for the target. Install a C compiler \
+                 or cross-compiler for it and point `CC` or `CC_<target>` to it.",
+            );
+        }
+
+        let is_missing_autotool = is_missing
+            && ["aclocal", "autoreconf", "automake", "libtoolize"]
+                .iter()
+                .any(|tool| line.contains(tool));
+
+        if is_missing_autotool || line.contains("LT_INIT") || line.contains("AC_PROG_LIBTOOL") {
+            return Some(
+                "Autotools are incomplete. Install `autoconf`, `automake`, and \
+                 `libtool`, or enable the `cmake`- or `cc-build`-feature.",
+            );
+        }
+
+        if line.contains("file format not recognized") || line.contains("wrong ELF class") {
+            return Some(
+                "Objects of another architecture were linked. Run `cargo clean` \
+                 and make sure `CC`, `AR`, and `RANLIB` target the same platform.",
+            );
+        }
+    }
+
+    None
+}
+
+/// Hashes `bytes` via 64-bit FNV-1a, which unlike `std`'s hashers is stable
+/// across Rust releases, hence suits keys persisted between builds.
+pub fn stable_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Joins a command-line flag such as `-L` with `path` into a single argument,
+/// without requiring the path to be valid UTF-8.
+pub fn prefixed_path(prefix: &str, path: &Path) -> OsString {
+    let mut argument = OsString::from(prefix);
+    argument.push(path);
+
+    argument
+}
+
+/// Parses the listing of `nm -g --defined-only` or `nm -gU` into the sorted
+/// symbols an archive defines, skipping the headers of its members.
+pub fn defined_symbols(listing: &str) -> Vec<String> {
+    let mut symbols: Vec<String> = listing
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+
+            match fields.as_slice() {
+                [_, kind, symbol] if *kind != "U" => Some(symbol.to_string()),
+                _ => None,
+            }
+        })
+        .collect();
+
+    symbols.sort();
+    symbols.dedup();
+
+    symbols
+}
+
+/// Returns the line of `objcopy --redefine-syms` renaming `symbol` to start
+/// with `prefix`, after the underscore that Mach-O and 32-bit Windows prepend
+/// to C symbols if `has_underscore`.
+pub fn symbol_redefinition(symbol: &str, prefix: &str, has_underscore: bool) -> String {
+    let (underscore, name) = if has_underscore && symbol.starts_with('_') {
+        ("_", &symbol[1..])
+    } else {
+        ("", symbol)
+    };
+
+    format!("{} {}{}{}", symbol, underscore, prefix, name)
+}
+
+/// Inserts a `link_name` with `prefix` before every `opus_`-function declared
+/// in the `bindings` generated via `bindgen`.
+#[cfg(feature = "bindgen")]
+pub fn prefix_link_names(bindings: &str, prefix: &str) -> String {
+    let mut prefixed = String::with_capacity(bindings.len());
+
+    for line in bindings.lines() {
+        let declaration = line.trim_start();
+
+        if declaration.starts_with("pub fn opus_") {
+            let name = declaration["pub fn ".len()..].split('(').next().unwrap_or_default();
+            let indentation = &line[..line.len() - declaration.len()];
+
+            prefixed.push_str(&format!(
+                "{}#[link_name = \"{}{}\"]\n",
+                indentation, prefix, name
+            ));
+        }
+
+        prefixed.push_str(line);
+        prefixed.push('\n');
+    }
+
+    prefixed
+}
+
+/// Wraps every `extern`-block of the `bindings` generated via `bindgen` in the
+/// crate's `opus_extern!`, which links `opus.dll` via `raw-dylib` if enabled.
+#[cfg(feature = "bindgen")]
+pub fn wrap_extern_blocks(bindings: &str) -> String {
+    let mut wrapped = String::with_capacity(bindings.len());
+
+    for line in bindings.lines() {
+        if line.trim() == "extern \"C\" {" {
+            wrapped.push_str(&line.replace("extern \"C\" {", "opus_extern! {"));
+        } else {
+            wrapped.push_str(line);
+        }
+
+        wrapped.push('\n');
+    }
+
+    wrapped
+}
+
+/// Maps a Rust target-triple to the one Clang understands, such that `bindgen`
+/// generates the bindings for the target rather than the build host.
+#[cfg(feature = "bindgen")]
+pub fn clang_target(target: &str) -> String {
+    let (architecture, _) = architecture_and_os(target);
+
+    let clang_architecture = if architecture.starts_with("riscv64") {
+        "riscv64"
+    } else if architecture.starts_with("riscv32") {
+        "riscv32"
+    } else {
+        architecture
+    };
+    let target = target.replacen(architecture, clang_architecture, 1);
+
+    // Apple's simulator targets, e.g. `aarch64-apple-ios-sim`.
+    if target.ends_with("-sim") {
+        format!("{}ulator", target)
+    } else {
+        target
+    }
+}
+
+/// Reads the whitespace-separated values assigned to `variable` in a Makefile,
+/// such as Opus' `celt_sources.mk`, following line continuations.
+pub fn make_variable(makefile: &str, variable: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut is_assigning = false;
+
+    for line in makefile.lines() {
+        let mut line = line.trim();
+
+        if !is_assigning {
+            let mut assignment = line.splitn(2, '=');
+
+            match (assignment.next(), assignment.next()) {
+                (Some(name), Some(value))
+                    if name.trim_end_matches(|c| c == '+' || c == ':').trim() == variable =>
+                {
+                    line = value;
+                }
+                _ => continue,
+            }
+        }
+
+        is_assigning = line.ends_with('\\');
+        values.extend(
+            line.trim_end_matches('\\')
+                .split_whitespace()
+                .map(str::to_string),
+        );
+    }
+
+    values
+}
+
+/// Whether the Opus `version`, such as `libopus 1.3.1-fixed` as reported by
+/// `opus_get_version_string` or `1.3.1` by `pkg-config`, is at least
+/// `minimum`, e.g. `1.3`.
+///
+/// Returns `None` if `version` contains no version number, such as for
+/// `libopus unknown`.
+pub fn is_at_least_version(version: &str, minimum: &str) -> Option<bool> {
+    fn components(version: &str) -> Option<Vec<u32>> {
+        let number = version
+            .split(|c: char| c.is_whitespace() || c == '-')
+            .find(|word| word.starts_with(|c: char| c.is_ascii_digit()))?;
+
+        number
+            .split('.')
+            .map(|component| component.parse().ok())
+            .collect()
+    }
+
+    let mut version = components(version)?;
+    let mut minimum = components(minimum)?;
+    let length = version.len().max(minimum.len());
+
+    version.resize(length, 0);
+    minimum.resize(length, 0);
+
+    Some(version >= minimum)
+}
+
+/// Where a companion library of Opus, such as `libopusfile`, was found.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CompanionSource {
+    PkgConfig,
+    LibDir,
+}
+
+/// Returns where `libogg` is linked from, given the `sources` of the companion
+/// libraries depending on it, or `None` if none is linked.
+///
+/// `pkg-config` resolves `libogg` along with a companion, hence it is only
+/// linked from `LIBOPUS_LIB_DIR` or `OPUS_LIB_DIR` if every companion was
+/// found there, rather than linking a second `libogg`.
+pub fn ogg_source(sources: &[CompanionSource]) -> Option<CompanionSource> {
+    if sources.contains(&CompanionSource::PkgConfig) {
+        Some(CompanionSource::PkgConfig)
+    } else if sources.contains(&CompanionSource::LibDir) {
+        Some(CompanionSource::LibDir)
+    } else {
+        None
+    }
+}
+
+/// What linking Opus needs of a pkg-config file, read without `pkg-config`.
+#[derive(Debug, Default, PartialEq)]
+pub struct PcFile {
+    pub version: Option<String>,
+    pub lib_directories: Vec<String>,
+    pub lib_names: Vec<String>,
+    pub include_directories: Vec<String>,
+}
+
+/// Parses the pkg-config file `contents`, expanding its variables, such as
+/// `${libdir}`, as well as `${pcfiledir}` to `pc_directory`, the directory
+/// containing it, as relocatable installations refer to their prefix by it.
+///
+/// Only the `-L` and `-l` flags of `Libs` and the `-I` flags of `Cflags` are
+/// kept, the C library `Libs.private` lists is linked anyway.
+pub fn parse_pc_file(contents: &str, pc_directory: &str) -> PcFile {
+    let mut variables = vec![("pcfiledir".to_string(), pc_directory.to_string())];
+    let mut pc_file = PcFile::default();
+
+    let expand = |value: &str, variables: &[(String, String)]| {
+        let mut value = value.trim().to_string();
+
+        // Variables may refer to ones defined before them only.
+        for (name, variable_value) in variables.iter().rev() {
+            value = value.replace(&format!("${{{}}}", name), variable_value);
+        }
+
+        value
+    };
+
+    for line in contents.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        let separator = match line.find(|c: char| c == '=' || c == ':') {
+            Some(separator) => separator,
+            None => continue,
+        };
+        let (name, value) = (line[..separator].trim(), &line[separator + 1..]);
+        let value = expand(value, &variables);
+
+        if line[separator..].starts_with('=') {
+            variables.push((name.to_string(), value));
+
+            continue;
+        }
+
+        let flags = value.split_whitespace();
+
+        match name {
+            "Version" => pc_file.version = Some(value),
+            "Libs" => {
+                for flag in flags {
+                    if flag.starts_with("-L") {
+                        pc_file.lib_directories.push(flag["-L".len()..].to_string());
+                    } else if flag.starts_with("-l") {
+                        pc_file.lib_names.push(flag["-l".len()..].to_string());
+                    }
+                }
+            }
+            "Cflags" => pc_file.include_directories.extend(
+                flags
+                    .filter(|flag| flag.starts_with("-I"))
+                    .map(|flag| flag["-I".len()..].to_string()),
+            ),
+            _ => {}
+        }
+    }
+
+    pc_file
+}
+
+/// Splits a target-triple such as `x86_64-unknown-linux-gnu` into its
+/// architecture and operating system, the vendor may be omitted as in
+/// `aarch64-linux-android`.
+fn architecture_and_os(triple: &str) -> (&str, &str) {
+    let components: Vec<&str> = triple.split('-').collect();
+
+    match components.len() {
+        0 | 1 => (triple, ""),
+        2 => (components[0], components[1]),
+        _ => (components[0], components[2]),
+    }
+}
+
+/// Whether binaries built for `target` cannot run on `host`, as their
+/// architecture or operating system differ.
+///
+/// Cargo always sets `HOST` for build scripts, should it be absent anyway,
+/// compiling natively is assumed.
+pub fn is_cross_compiled(host: Option<&str>, target: &str) -> bool {
+    match host {
+        Some(host) => architecture_and_os(host) != architecture_and_os(target),
+        None => false,
+    }
+}
+
+/// Whether `target` is 32-bit x86 on a 64-bit x86 `host` of the same
+/// operating system and environment, e.g. `i686-unknown-linux-gnu` on
+/// `x86_64-unknown-linux-gnu`, whose multilib compiler builds it via `-m32`.
+#[cfg(any(unix, target_env = "gnu"))]
+pub fn is_multilib_target(host: Option<&str>, target: &str) -> bool {
+    let host = match host {
+        Some(host) => host,
+        None => return false,
+    };
+    let (host_architecture, host_os) = architecture_and_os(host);
+    let (target_architecture, target_os) = architecture_and_os(target);
+    let is_x86 = ["i386", "i586", "i686"].contains(&target_architecture);
+
+    is_x86
+        && host_architecture == "x86_64"
+        && host_os == target_os
+        && host.rsplit('-').next() == target.rsplit('-').next()
+}
+
+/// The file-names the linker accepts for the library `lib_name` on the target
+/// described by `target_os` and `target_env`, statically or dynamically.
+///
+/// On MSVC, `lib_name.lib` is an import library when linking dynamically,
+/// MinGW links a DLL directly as well.
+pub fn library_file_names(
+    lib_name: &str,
+    is_static: bool,
+    target_os: &str,
+    target_env: &str,
+) -> Vec<String> {
+    let patterns: &[&str] = match (target_os, target_env, is_static) {
+        ("windows", "msvc", true) => &["{}.lib", "lib{}.a"],
+        ("windows", "msvc", false) => &["{}.lib"],
+        ("windows", _, true) => &["lib{}.a", "{}.lib"],
+        ("windows", _, false) => &["lib{}.dll.a", "{}.dll.a", "lib{}.dll", "{}.dll"],
+        ("macos", _, false) | ("ios", _, false) | ("tvos", _, false) | ("watchos", _, false) => {
+            &["lib{}.dylib", "lib{}.tbd"]
+        }
+        (_, _, true) => &["lib{}.a"],
+        (_, _, false) => &["lib{}.so"],
+    };
+
+    patterns
+        .iter()
+        .map(|pattern| pattern.replace("{}", lib_name))
+        .collect()
+}
+
+/// Whether the C `compiler`, possibly followed by arguments, is `zig cc` or one
+/// of the `zigcc-*`-wrappers `cargo-zigbuild` generates.
+pub fn is_zig_compiler(compiler: &str) -> bool {
+    let program = compiler.split_whitespace().next().unwrap_or_default();
+    let name = Path::new(program)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    name == "zig" || name == "zig.exe" || name.starts_with("zigcc")
+}
+
+/// Maps a Rust target-triple to the GNU host triple `configure` and GNU
+/// cross-toolchains, such as `aarch64-linux-gnu-gcc`, are named after.
+pub fn gnu_host_triple(target: &str) -> String {
+    let (rust_architecture, os) = architecture_and_os(target);
+    let environment = target.rsplit('-').next().unwrap_or_default();
+
+    let architecture = if rust_architecture.starts_with("arm") || rust_architecture.starts_with("thumb") {
+        "arm"
+    } else if rust_architecture.starts_with("riscv64") {
+        "riscv64"
+    } else if rust_architecture.starts_with("riscv32") {
+        "riscv32"
+    } else if rust_architecture == "i586" {
+        "i686"
+    } else {
+        rust_architecture
+    };
+
+    match os {
+        "linux" => format!("{}-linux-{}", architecture, environment),
+        "windows" => format!("{}-w64-mingw32", architecture),
+        // QNX' GCC names its version dotted, e.g. `aarch64-unknown-nto-qnx7.1.0`.
+        "nto" => {
+            let version: Vec<String> = environment
+                .trim_start_matches("qnx")
+                .chars()
+                .take_while(char::is_ascii_digit)
+                .map(|digit| digit.to_string())
+                .collect();
+
+            format!("{}-unknown-nto-qnx{}", architecture, version.join("."))
+        }
+        _ => target.replacen(rust_architecture, architecture, 1),
+    }
+}
+
+/// The prefix of the tools of QNX' SDP for `CARGO_CFG_TARGET_ARCH`, such as
+/// `ntoaarch64` of `ntoaarch64-gcc`, if known.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+pub fn qnx_tool_prefix(architecture: &str) -> Option<&'static str> {
+    match architecture {
+        "aarch64" => Some("ntoaarch64"),
+        "arm" => Some("ntoarmv7"),
+        "x86" => Some("ntox86"),
+        "x86_64" => Some("ntox86_64"),
+        _ => None,
+    }
+}
+
+/// Maps Rust's `CARGO_CFG_TARGET_OS` and `CARGO_CFG_TARGET_ARCH` to the
+/// `system` and `cpu_family` of a Meson machine file, e.g. `darwin` and
+/// `aarch64` for `aarch64-apple-ios`.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+pub fn meson_machine<'a>(target_os: &'a str, target_architecture: &'a str) -> (&'a str, &'a str) {
+    let system = match target_os {
+        "macos" | "ios" | "tvos" | "watchos" | "visionos" => "darwin",
+        "none" => "bare metal",
+        os => os,
+    };
+
+    let cpu_family = match target_architecture {
+        "powerpc" => "ppc",
+        "powerpc64" => "ppc64",
+        architecture if architecture.starts_with("riscv32") => "riscv32",
+        architecture if architecture.starts_with("riscv64") => "riscv64",
+        architecture => architecture,
+    };
+
+    (system, cpu_family)
+}
+
+/// Formats `values` as an array of Meson strings, escaping backslashes and
+/// quotes, e.g. `['-O2', '-g']`.
+#[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+pub fn meson_array<S: AsRef<str>>(values: &[S]) -> String {
+    let strings: Vec<String> = values
+        .iter()
+        .map(|value| value.as_ref().replace('\\', "\\\\").replace('\'', "\\'"))
+        .map(|value| format!("'{}'", value))
+        .collect();
+
+    format!("[{}]", strings.join(", "))
+}
+
+/// The members of the `ar`-archive `bytes`, such as the objects of a static
+/// library, or `None` if it is no archive.
+///
+/// Symbol tables and the table of long names are skipped, BSD's long names,
+/// stored in front of the member's data as named by `#1/20`, are stripped.
+fn archive_members(bytes: &[u8]) -> Option<Vec<&[u8]>> {
+    const ARCHIVE_MAGIC: &[u8] = b"!<arch>\n";
+    const MEMBER_HEADER_LENGTH: usize = 60;
+
+    if !bytes.starts_with(ARCHIVE_MAGIC) {
+        return None;
+    }
+
+    let mut members = Vec::new();
+    let mut offset = ARCHIVE_MAGIC.len();
+
+    while offset + MEMBER_HEADER_LENGTH <= bytes.len() {
+        let mut name = &bytes[offset..offset + 16];
+        let size = std::str::from_utf8(&bytes[offset + 48..offset + 58])
+            .ok()?
+            .trim()
+            .parse::<usize>()
+            .ok()?;
+        let mut start = offset + MEMBER_HEADER_LENGTH;
+        let end = bytes.len().min(start + size);
+
+        if name.starts_with(b"#1/") {
+            let name_length = std::str::from_utf8(&name[3..])
+                .ok()?
+                .trim()
+                .parse::<usize>()
+                .ok()?;
+
+            name = &bytes[start..end.min(start + name_length)];
+            start = end.min(start + name_length);
+        }
+
+        let is_symbol_table = name.starts_with(b"/ ")
+            || name.starts_with(b"// ")
+            || name.starts_with(b"/SYM64/")
+            || name.starts_with(b"__.SYMDEF");
+
+        if !is_symbol_table {
+            members.push(&bytes[start..end]);
+        }
+
+        offset = end + (end % 2);
+    }
+
+    Some(members)
+}
+
+/// The ELF machine, e.g. `183` for AArch64, of a shared library or of the
+/// first object in a static archive.
+///
+/// Returns `None` for files neither ELF nor an archive of ELF objects, such
+/// as Mach-O or PE.
+pub fn elf_machine(bytes: &[u8]) -> Option<u16> {
+    if let Some(members) = archive_members(bytes) {
+        return members.into_iter().filter_map(elf_machine).next();
+    }
+
+    if bytes.len() < 20 || !bytes.starts_with(b"\x7fELF") {
+        return None;
+    }
+
+    let machine = [bytes[18], bytes[19]];
+
+    match bytes[5] {
+        1 => Some(u16::from_le_bytes(machine)),
+        2 => Some(u16::from_be_bytes(machine)),
+        _ => None,
+    }
+}
+
+/// The word size in bits, `32` or `64`, of an ELF shared library or of the
+/// first object in a static archive, as recorded by its ELF class.
+///
+/// Returns `None` for files neither ELF nor an archive of ELF objects.
+pub fn elf_word_size(bytes: &[u8]) -> Option<u32> {
+    if let Some(members) = archive_members(bytes) {
+        return members.into_iter().filter_map(elf_word_size).next();
+    }
+
+    if bytes.len() < 5 || !bytes.starts_with(b"\x7fELF") {
+        return None;
+    }
+
+    match bytes[4] {
+        1 => Some(32),
+        2 => Some(64),
+        _ => None,
+    }
+}
+
+/// The Mach-O CPU-type, e.g. `0x0100000c` for ARM64, of a dynamic library or
+/// of the first object in a static archive.
+///
+/// Returns `None` for other files as well as universal binaries, which may
+/// span several architectures.
+pub fn macho_cpu_type(bytes: &[u8]) -> Option<u32> {
+    if let Some(members) = archive_members(bytes) {
+        return members.into_iter().filter_map(macho_cpu_type).next();
+    }
+
+    if bytes.len() < 8 {
+        return None;
+    }
+
+    let cpu_type = [bytes[4], bytes[5], bytes[6], bytes[7]];
+
+    match &bytes[..4] {
+        b"\xce\xfa\xed\xfe" | b"\xcf\xfa\xed\xfe" => Some(u32::from_le_bytes(cpu_type)),
+        b"\xfe\xed\xfa\xce" | b"\xfe\xed\xfa\xcf" => Some(u32::from_be_bytes(cpu_type)),
+        _ => None,
+    }
+}
+
+/// The COFF machine, e.g. `0xaa64` for ARM64, of a DLL, of the first object
+/// in a static library, or of the first entry in an import library.
+///
+/// Objects are only recognised by a machine `expected_coff_machine` knows, as
+/// they lack a magic number.
+pub fn coff_machine(bytes: &[u8]) -> Option<u16> {
+    if let Some(members) = archive_members(bytes) {
+        return members.into_iter().filter_map(coff_machine).next();
+    }
+
+    let read_u16 = |offset: usize| {
+        bytes
+            .get(offset..offset + 2)
+            .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+    };
+
+    if bytes.starts_with(b"MZ") {
+        let header = bytes.get(0x3c..0x40)?;
+        let offset = u32::from_le_bytes([header[0], header[1], header[2], header[3]]) as usize;
+
+        if bytes.get(offset..offset + 4)? != b"PE\0\0" {
+            return None;
+        }
+
+        return read_u16(offset + 4);
+    }
+
+    // Import libraries consist of short import headers, starting with
+    // `IMAGE_FILE_MACHINE_UNKNOWN` and `0xffff`.
+    let machine = if read_u16(0)? == 0 && read_u16(2)? == 0xffff {
+        read_u16(6)?
+    } else {
+        read_u16(0)?
+    };
+
+    match machine {
+        0x014c | 0x8664 | 0x01c4 | 0xaa64 => Some(machine),
+        _ => None,
+    }
+}
+
+/// Whether the `ar`-archive `bytes`, such as an `opus.lib` on Windows, is an
+/// import library, whose members are short import headers or import
+/// descriptors, rather than a static library of objects.
+///
+/// Returns `None` for files that are no archive, such as a DLL.
+pub fn is_import_library(bytes: &[u8]) -> Option<bool> {
+    const IMPORT_DESCRIPTOR: &[u8] = b"__IMPORT_DESCRIPTOR_";
+
+    let members = archive_members(bytes)?;
+
+    Some(members.iter().any(|member| {
+        member.starts_with(&[0, 0, 0xff, 0xff])
+            || member
+                .windows(IMPORT_DESCRIPTOR.len())
+                .any(|window| window == IMPORT_DESCRIPTOR)
+    }))
+}
+
+/// The names exported by the DLL `bytes`, read from the export directory of
+/// its PE header, e.g. to write the `.def`-file of an import library.
+///
+/// Returns `None` if `bytes` is no DLL or lacks an export directory.
+pub fn dll_exports(bytes: &[u8]) -> Option<Vec<String>> {
+    let read_u16 = |offset: usize| {
+        bytes
+            .get(offset..offset + 2)
+            .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]) as usize)
+    };
+    let read_u32 = |offset: usize| {
+        bytes
+            .get(offset..offset + 4)
+            .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+    };
+
+    if !bytes.starts_with(b"MZ") {
+        return None;
+    }
+
+    let header = read_u32(0x3c)?;
+
+    if bytes.get(header..header + 4)? != b"PE\0\0" {
+        return None;
+    }
+
+    let section_count = read_u16(header + 6)?;
+    let optional_header = header + 24;
+    let sections = optional_header + read_u16(header + 20)?;
+
+    // The data directories follow the optional header's fields, which are
+    // wider in PE32+.
+    let data_directories = match read_u16(optional_header)? {
+        0x10b => optional_header + 96,
+        0x20b => optional_header + 112,
+        _ => return None,
+    };
+
+    let export_directory = read_u32(data_directories)?;
+
+    if export_directory == 0 {
+        return None;
+    }
+
+    let file_offset = |address: usize| {
+        (0..section_count).find_map(|index| {
+            let section = sections + index * 40;
+            let virtual_address = read_u32(section + 12)?;
+            let size = read_u32(section + 8)?.max(read_u32(section + 16)?);
+
+            if address >= virtual_address && address < virtual_address + size {
+                Some(address - virtual_address + read_u32(section + 20)?)
+            } else {
+                None
+            }
+        })
+    };
+
+    let export_directory = file_offset(export_directory)?;
+    let name_count = read_u32(export_directory + 24)?;
+    let names = file_offset(read_u32(export_directory + 32)?)?;
+
+    (0..name_count)
+        .map(|index| {
+            let name = file_offset(read_u32(names + index * 4)?)?;
+            let length = bytes.get(name..)?.iter().position(|&byte| byte == 0)?;
+
+            Some(String::from_utf8_lossy(&bytes[name..name + length]).into_owned())
+        })
+        .collect()
+}
+
+/// The ELF machine of objects built for `CARGO_CFG_TARGET_ARCH`, if known.
+pub fn expected_elf_machine(architecture: &str) -> Option<u16> {
+    match architecture {
+        "x86" => Some(3),
+        "mips" | "mips64" => Some(8),
+        "powerpc" => Some(20),
+        "powerpc64" => Some(21),
+        "s390x" => Some(22),
+        "arm" => Some(40),
+        "sparc64" => Some(43),
+        "x86_64" => Some(62),
+        "aarch64" => Some(183),
+        "riscv32" | "riscv64" => Some(243),
+        _ => None,
+    }
+}
+
+/// The Mach-O CPU-type of objects built for `CARGO_CFG_TARGET_ARCH`, if known.
+pub fn expected_macho_cpu_type(architecture: &str) -> Option<u32> {
+    match architecture {
+        "x86" => Some(7),
+        "arm" => Some(12),
+        "x86_64" => Some(0x0100_0007),
+        "aarch64" => Some(0x0100_000c),
+        _ => None,
+    }
+}
+
+/// The COFF machine of objects built for `CARGO_CFG_TARGET_ARCH`, if known.
+pub fn expected_coff_machine(architecture: &str) -> Option<u16> {
+    match architecture {
+        "x86" => Some(0x014c),
+        "arm" => Some(0x01c4),
+        "x86_64" => Some(0x8664),
+        "aarch64" => Some(0xaa64),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPACED_DIRECTORY: &str = "target dir/My Name/out";
+
+    #[test]
+    fn parse_bool_values() {
+        assert_eq!(parse_bool("X", ""), Ok(true));
+        assert_eq!(parse_bool("X", " On "), Ok(true));
+        assert_eq!(parse_bool("X", "0"), Ok(false));
+        assert!(parse_bool("X", "maybe").is_err());
+    }
+
+    #[test]
+    fn opus_variable_names_per_target() {
+        assert_eq!(
+            opus_variable_names("STATIC", Some("aarch64-apple-darwin")),
+            vec![
+                "LIBOPUS_STATIC_aarch64-apple-darwin",
+                "OPUS_STATIC_aarch64-apple-darwin",
+                "LIBOPUS_STATIC_aarch64_apple_darwin",
+                "OPUS_STATIC_aarch64_apple_darwin",
+                "LIBOPUS_STATIC",
+                "OPUS_STATIC",
+            ]
+        );
+        assert_eq!(
+            opus_variable_names("LIB_DIR", None),
+            vec!["LIBOPUS_LIB_DIR", "OPUS_LIB_DIR"]
+        );
+    }
+
+    #[test]
+    fn opus_env_var_names_the_variable_read() {
+        assert_eq!(opus_env_var("UTIL_TEST_FLAG"), None);
+
+        env::set_var("OPUS_UTIL_TEST_FLAG", "x");
+        let (variable, value) = opus_env_var("UTIL_TEST_FLAG").unwrap();
+        assert_eq!(
+            parse_bool(&variable, &value),
+            Err("Invalid value for `OPUS_UTIL_TEST_FLAG`: `x`, expected `1`, `true`, `0`, or `false`.".to_string())
+        );
+
+        env::set_var("LIBOPUS_UTIL_TEST_FLAG", "2");
+        assert_eq!(
+            opus_env_var("UTIL_TEST_FLAG"),
+            Some(("LIBOPUS_UTIL_TEST_FLAG".to_string(), "2".to_string()))
+        );
+    }
+
+    #[test]
+    fn ogg_source_of_companions() {
+        use CompanionSource::{LibDir, PkgConfig};
+
+        assert_eq!(ogg_source(&[]), None);
+        assert_eq!(ogg_source(&[PkgConfig, PkgConfig]), Some(PkgConfig));
+        assert_eq!(ogg_source(&[LibDir]), Some(LibDir));
+        assert_eq!(ogg_source(&[LibDir, PkgConfig]), Some(PkgConfig));
+        assert_eq!(ogg_source(&[PkgConfig, LibDir]), Some(PkgConfig));
+    }
+
+    #[test]
+    fn parse_lto_values() {
+        assert_eq!(parse_lto("X", "Fat"), Ok(Some("fat")));
+        assert_eq!(parse_lto("X", "1"), Ok(Some("fat")));
+        assert_eq!(parse_lto("X", " thin "), Ok(Some("thin")));
+        assert_eq!(parse_lto("X", "off"), Ok(None));
+        assert!(parse_lto("X", "full").is_err());
+    }
+
+    #[test]
+    fn parse_linkage_values() {
+        assert_eq!(parse_linkage("X", "Static"), Ok(true));
+        assert_eq!(parse_linkage("X", " dynamic "), Ok(false));
+        assert_eq!(parse_linkage("X", "dylib"), Ok(false));
+        assert!(parse_linkage("X", "").is_err());
+        assert!(parse_linkage("X", "1").is_err());
+    }
+
+    #[test]
+    fn parse_link_libs_values() {
+        assert_eq!(
+            parse_link_libs("X", "m, static=ssp  dylib=pthread"),
+            Ok(vec![
+                "m".to_string(),
+                "static=ssp".to_string(),
+                "dylib=pthread".to_string()
+            ])
+        );
+        assert_eq!(parse_link_libs("X", ""), Ok(Vec::new()));
+        assert!(parse_link_libs("X", "shared=ssp").is_err());
+        assert!(parse_link_libs("X", "static=").is_err());
+    }
+
+    #[cfg(all(windows, target_env = "msvc", not(feature = "cmake")))]
+    #[test]
+    fn parse_checksum_manifests() {
+        let digest = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        let manifest = format!(
+            "# comment\n\n{}  x64/dy/opus.dll\n{} *x86\\opus.lib\n",
+            digest,
+            digest.to_uppercase()
+        );
+
+        assert_eq!(
+            parse_checksums(&manifest),
+            Ok(vec![
+                ("x64/dy/opus.dll".to_string(), digest.to_string()),
+                ("x86/opus.lib".to_string(), digest.to_string()),
+            ])
+        );
+        assert!(parse_checksums("abc  opus.lib").is_err());
+    }
+
+    #[test]
+    fn library_file_names_per_target() {
+        assert_eq!(
+            library_file_names("opus", true, "linux", "gnu"),
+            vec!["libopus.a"]
+        );
+        assert_eq!(
+            library_file_names("opus", false, "macos", ""),
+            vec!["libopus.dylib", "libopus.tbd"]
+        );
+        assert_eq!(
+            library_file_names("libopus", false, "windows", "msvc"),
+            vec!["libopus.lib"]
+        );
+        assert!(library_file_names("opus-0", false, "windows", "gnu")
+            .contains(&"libopus-0.dll".to_string()));
+    }
+
+    #[cfg(any(unix, target_env = "gnu", feature = "cmake"))]
+    #[test]
+    fn parse_simd_levels() {
+        assert_eq!(parse_simd_level("X", "none"), Ok(0));
+        assert_eq!(parse_simd_level("X", "SSE4.1"), Ok(3));
+        assert_eq!(parse_simd_level("X", " avx2 "), Ok(X86_SIMD_LEVELS.len()));
+        assert!(parse_simd_level("X", "avx512").is_err());
+    }
+
+    #[test]
+    fn target_cpu_of_rustflags() {
+        assert_eq!(rustflags_target_cpu(""), None);
+        assert_eq!(
+            rustflags_target_cpu("-Ctarget-cpu=native"),
+            Some("native".to_string())
+        );
+        assert_eq!(
+            rustflags_target_cpu("-C\x1ftarget-cpu=skylake\x1f-Copt-level=3"),
+            Some("skylake".to_string())
+        );
+        assert_eq!(
+            rustflags_target_cpu("-Ctarget-cpu=x86-64\x1f--codegen=target-cpu=znver3"),
+            Some("znver3".to_string())
+        );
+        assert_eq!(rustflags_target_cpu("-Ctarget-feature=+avx2"), None);
+    }
+
+    #[test]
+    fn prefixed_path_keeps_spaces() {
+        let argument = prefixed_path("-L", Path::new(SPACED_DIRECTORY));
+
+        assert_eq!(argument, OsString::from(format!("-L{}", SPACED_DIRECTORY)));
+    }
+
+    #[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+    #[test]
+    fn configure_path_keeps_spaces() {
+        let argument = configure_path(Path::new(SPACED_DIRECTORY));
+
+        assert_eq!(argument, OsString::from(SPACED_DIRECTORY));
+    }
+
+    #[cfg(all(unix, not(feature = "cmake")))]
+    #[test]
+    fn configure_path_keeps_non_utf8() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = Path::new(std::ffi::OsStr::from_bytes(b"out/\xFF dir"));
+
+        assert_eq!(configure_path(path), path.as_os_str());
+    }
+
+    #[test]
+    fn native_targets_are_not_cross_compiled() {
+        let host = Some("x86_64-unknown-linux-gnu");
+
+        assert!(!is_cross_compiled(host, "x86_64-unknown-linux-gnu"));
+        assert!(!is_cross_compiled(host, "x86_64-unknown-linux-musl"));
+        assert!(!is_cross_compiled(
+            Some("x86_64-pc-windows-msvc"),
+            "x86_64-pc-windows-gnu"
+        ));
+    }
+
+    #[test]
+    fn foreign_targets_are_cross_compiled() {
+        let host = Some("x86_64-unknown-linux-gnu");
+
+        assert!(is_cross_compiled(host, "aarch64-unknown-linux-gnu"));
+        assert!(is_cross_compiled(host, "x86_64-pc-windows-gnu"));
+        assert!(is_cross_compiled(host, "aarch64-linux-android"));
+        assert!(is_cross_compiled(
+            Some("aarch64-apple-darwin"),
+            "x86_64-apple-darwin"
+        ));
+        assert!(is_cross_compiled(
+            Some("aarch64-apple-darwin"),
+            "aarch64-apple-ios"
+        ));
+    }
+
+    #[test]
+    fn missing_host_is_not_cross_compiled() {
+        assert!(!is_cross_compiled(None, "aarch64-linux-android"));
+    }
+
+    #[cfg(any(unix, target_env = "gnu"))]
+    #[test]
+    fn multilib_targets() {
+        let host = Some("x86_64-unknown-linux-gnu");
+
+        assert!(is_multilib_target(host, "i686-unknown-linux-gnu"));
+        assert!(is_multilib_target(host, "i586-unknown-linux-gnu"));
+        assert!(!is_multilib_target(host, "i686-unknown-linux-musl"));
+        assert!(!is_multilib_target(host, "i686-pc-windows-gnu"));
+        assert!(!is_multilib_target(host, "aarch64-unknown-linux-gnu"));
+        assert!(!is_multilib_target(None, "i686-unknown-linux-gnu"));
+    }
+
+    #[test]
+    fn zig_compilers() {
+        assert!(is_zig_compiler("zig cc -target aarch64-linux-musl"));
+        assert!(is_zig_compiler(
+            "/home/me/.cache/cargo-zigbuild/0.19.0/zigcc-aarch64-unknown-linux-musl-d8f2.sh"
+        ));
+        assert!(!is_zig_compiler("aarch64-linux-gnu-gcc"));
+        assert!(!is_zig_compiler("ccache clang"));
+    }
+
+    #[test]
+    fn defined_symbols_of_archive() {
+        let listing = "\n\
+                       bands.o:\n\
+                       0000000000000000 T celt_bands\n\
+                       0000000000000010 T opus_encode\n\
+                       \n\
+                       opus.o:\n\
+                       0000000000000000 T opus_encode\n\
+                       0000000000000020 R opus_tables\n";
+
+        assert_eq!(
+            defined_symbols(listing),
+            vec!["celt_bands", "opus_encode", "opus_tables"]
+        );
+        assert!(defined_symbols("bands.o:\n").is_empty());
+    }
+
+    #[test]
+    fn symbol_redefinitions() {
+        assert_eq!(
+            symbol_redefinition("opus_encode", "audiopus_", false),
+            "opus_encode audiopus_opus_encode"
+        );
+        assert_eq!(
+            symbol_redefinition("_opus_encode", "audiopus_", true),
+            "_opus_encode _audiopus_opus_encode"
+        );
+    }
+
+    #[cfg(feature = "bindgen")]
+    #[test]
+    fn prefixed_link_names() {
+        let bindings = "extern \"C\" {\n    pub fn opus_encode(st: *mut OpusEncoder) -> i32;\n}\n";
+
+        assert_eq!(
+            prefix_link_names(bindings, "audiopus_"),
+            "extern \"C\" {\n    \
+             #[link_name = \"audiopus_opus_encode\"]\n    \
+             pub fn opus_encode(st: *mut OpusEncoder) -> i32;\n\
+             }\n"
+        );
+    }
+
+    #[cfg(feature = "bindgen")]
+    #[test]
+    fn wrapped_extern_blocks() {
+        let bindings = "extern \"C\" {\n    pub fn opus_encode(st: *mut OpusEncoder) -> i32;\n}\n";
+
+        assert_eq!(
+            wrap_extern_blocks(bindings),
+            "opus_extern! {\n    pub fn opus_encode(st: *mut OpusEncoder) -> i32;\n}\n"
+        );
+    }
+
+    #[cfg(feature = "bindgen")]
+    #[test]
+    fn clang_targets() {
+        assert_eq!(
+            clang_target("x86_64-pc-windows-msvc"),
+            "x86_64-pc-windows-msvc"
+        );
+        assert_eq!(
+            clang_target("wasm32-unknown-unknown"),
+            "wasm32-unknown-unknown"
+        );
+        assert_eq!(
+            clang_target("riscv64gc-unknown-linux-gnu"),
+            "riscv64-unknown-linux-gnu"
+        );
+        assert_eq!(
+            clang_target("aarch64-apple-ios-sim"),
+            "aarch64-apple-ios-simulator"
+        );
+    }
+
+    #[test]
+    fn make_variable_follows_continuations() {
+        let makefile = "CELT_SOURCES = celt/bands.c \\\n\
+                        celt/celt.c \\\n\
+                        \tcelt/modes.c\n\
+                        CELT_SOURCES_SSE = celt/x86/pitch_sse.c\n\
+                        CELT_SOURCES += celt/vq.c\n";
+
+        assert_eq!(
+            make_variable(makefile, "CELT_SOURCES"),
+            vec!["celt/bands.c", "celt/celt.c", "celt/modes.c", "celt/vq.c"]
+        );
+        assert_eq!(
+            make_variable(makefile, "CELT_SOURCES_SSE"),
+            vec!["celt/x86/pitch_sse.c"]
+        );
+        assert!(make_variable(makefile, "SILK_SOURCES").is_empty());
+    }
+
+    #[test]
+    fn copy_directory_recursively() {
+        let root = env::temp_dir().join(format!("audiopus_sys_copy_{}", std::process::id()));
+        let source = root.join("source dir");
+        let destination = root.join("destination");
+
+        fs::create_dir_all(source.join("celt")).unwrap();
+        fs::write(source.join("configure"), "#!/bin/sh\n").unwrap();
+        fs::write(source.join("celt").join("celt.c"), "int celt;\n").unwrap();
+
+        copy_directory(&source, &destination).unwrap();
+
+        assert_eq!(fs::read_to_string(destination.join("configure")).unwrap(), "#!/bin/sh\n");
+        assert_eq!(
+            fs::read_to_string(destination.join("celt").join("celt.c")).unwrap(),
+            "int celt;\n"
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn failure_hints() {
+        let compiler_hint = failure_hint("configure: error: C compiler cannot create executables");
+        let autotools_hint = failure_hint("autogen.sh: 1: autoreconf: not found");
+        let libtool_hint =
+            failure_hint("configure.ac:14: error: possibly undefined macro: LT_INIT");
+
+        assert!(compiler_hint.unwrap().contains("`CC_<target>`"));
+        assert!(autotools_hint.unwrap().contains("`libtool`"));
+        assert_eq!(libtool_hint, autotools_hint);
+        assert_eq!(failure_hint("make: *** [all] Error 2"), None);
+    }
+
+    #[test]
+    fn stable_hashes() {
+        assert_eq!(stable_hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(stable_hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(stable_hash(b"static"), stable_hash(b"dylib"));
+    }
+
+    #[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+    #[test]
+    fn gnu_host_triples() {
+        assert_eq!(gnu_host_triple("aarch64-unknown-linux-gnu"), "aarch64-linux-gnu");
+        assert_eq!(
+            gnu_host_triple("armv7-unknown-linux-gnueabihf"),
+            "arm-linux-gnueabihf"
+        );
+        assert_eq!(gnu_host_triple("mipsel-unknown-linux-musl"), "mipsel-linux-musl");
+        assert_eq!(gnu_host_triple("riscv64gc-unknown-linux-gnu"), "riscv64-linux-gnu");
+        assert_eq!(
+            gnu_host_triple("powerpc64le-unknown-linux-gnu"),
+            "powerpc64le-linux-gnu"
+        );
+        assert_eq!(gnu_host_triple("s390x-unknown-linux-gnu"), "s390x-linux-gnu");
+        assert_eq!(gnu_host_triple("x86_64-pc-windows-gnu"), "x86_64-w64-mingw32");
+        assert_eq!(
+            gnu_host_triple("aarch64-pc-windows-gnullvm"),
+            "aarch64-w64-mingw32"
+        );
+        assert_eq!(gnu_host_triple("x86_64-unknown-freebsd"), "x86_64-unknown-freebsd");
+        assert_eq!(
+            gnu_host_triple("aarch64-unknown-nto-qnx710"),
+            "aarch64-unknown-nto-qnx7.1.0"
+        );
+        assert_eq!(
+            gnu_host_triple("x86_64-pc-nto-qnx710_iosock"),
+            "x86_64-unknown-nto-qnx7.1.0"
+        );
+        assert_eq!(qnx_tool_prefix("aarch64"), Some("ntoaarch64"));
+        assert_eq!(qnx_tool_prefix("riscv64"), None);
+    }
+
+    #[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+    #[test]
+    fn meson_machines() {
+        assert_eq!(meson_machine("linux", "aarch64"), ("linux", "aarch64"));
+        assert_eq!(meson_machine("ios", "aarch64"), ("darwin", "aarch64"));
+        assert_eq!(meson_machine("linux", "powerpc64"), ("linux", "ppc64"));
+        assert_eq!(meson_machine("none", "riscv32imac"), ("bare metal", "riscv32"));
+    }
+
+    #[cfg(all(any(unix, target_env = "gnu"), not(feature = "cmake")))]
+    #[test]
+    fn meson_arrays() {
+        assert_eq!(meson_array(&["-O2", "-g"]), "['-O2', '-g']");
+        assert_eq!(
+            meson_array(&["-DNAME='x'", "C:\\opus"]),
+            "['-DNAME=\\'x\\'', 'C:\\\\opus']"
+        );
+        assert_eq!(meson_array::<&str>(&[]), "[]");
+    }
+
+    #[test]
+    fn import_and_static_libraries() {
+        let archive = |member: &[u8]| {
+            let mut archive = b"!<arch>\n".to_vec();
+            archive.extend(format!("{:<48}{:<10}`\n", "opus.dll/", member.len()).as_bytes());
+            archive.extend(member);
+            archive
+        };
+
+        let mut short_import = vec![0, 0, 0xff, 0xff, 0, 0, 0x64, 0x86];
+        short_import.extend(b"opus_encode\0opus.dll\0");
+        let mut descriptor = vec![0x64, 0x86, 3, 0];
+        descriptor.extend(b"__IMPORT_DESCRIPTOR_opus\0");
+
+        assert_eq!(is_import_library(&archive(&short_import)), Some(true));
+        assert_eq!(is_import_library(&archive(&descriptor)), Some(true));
+        assert_eq!(is_import_library(&archive(&[0x64, 0x86, 3, 0])), Some(false));
+        assert_eq!(is_import_library(b"MZ"), None);
+    }
+
+    #[test]
+    fn pc_files() {
+        let contents = "# Opus codec reference implementation pkg-config file\n\
+                        \n\
+                        prefix=${pcfiledir}/../..\n\
+                        libdir=${prefix}/lib\n\
+                        includedir=${prefix}/include\n\
+                        \n\
+                        Name: Opus\n\
+                        Version: 1.3.1\n\
+                        Libs: -L${libdir} -lopus\n\
+                        Libs.private: -lm\n\
+                        Cflags: -I${includedir}/opus\n";
+
+        assert_eq!(
+            parse_pc_file(contents, "/opt/opus/lib/pkgconfig"),
+            PcFile {
+                version: Some("1.3.1".to_string()),
+                lib_directories: vec!["/opt/opus/lib/pkgconfig/../../lib".to_string()],
+                lib_names: vec!["opus".to_string()],
+                include_directories: vec!["/opt/opus/lib/pkgconfig/../../include/opus".to_string()],
+            }
+        );
+        assert_eq!(parse_pc_file("Name: Opus\n", "."), PcFile::default());
+    }
+
+    #[test]
+    fn exports_of_dlls() {
+        let mut dll = vec![0u8; 0x300];
+        let mut write_u32 = |offset: usize, value: u32| {
+            dll[offset..offset + 4].copy_from_slice(&value.to_le_bytes())
+        };
+
+        // A PE32+ header with one section mapping 0x1000 to 0x200.
+        write_u32(0x3c, 0x40);
+        write_u32(0x40, u32::from_le_bytes(*b"PE\0\0"));
+        write_u32(0x46, 1);
+        write_u32(0x54, 240);
+        write_u32(0x58, 0x20b);
+        write_u32(0x58 + 112, 0x1000);
+        write_u32(0x148 + 8, 0x100);
+        write_u32(0x148 + 12, 0x1000);
+        write_u32(0x148 + 20, 0x200);
+
+        // The export directory with two names.
+        write_u32(0x200 + 24, 2);
+        write_u32(0x200 + 32, 0x1040);
+        write_u32(0x240, 0x1050);
+        write_u32(0x244, 0x1060);
+        dll[..2].copy_from_slice(b"MZ");
+        dll[0x250..0x25c].copy_from_slice(b"opus_encode\0");
+        dll[0x260..0x26c].copy_from_slice(b"opus_decode\0");
+
+        assert_eq!(
+            dll_exports(&dll),
+            Some(vec!["opus_encode".to_string(), "opus_decode".to_string()])
+        );
+
+        dll[0x58 + 112..0x58 + 116].copy_from_slice(&[0; 4]);
+
+        assert_eq!(dll_exports(&dll), None);
+        assert_eq!(dll_exports(b"!<arch>\n"), None);
+    }
+
+    #[test]
+    fn elf_machine_of_objects_and_archives() {
+        let mut object = vec![0u8; 64];
+        object[..4].copy_from_slice(b"\x7fELF");
+        object[5] = 1;
+        object[18] = 183;
+
+        assert_eq!(elf_machine(&object), Some(183));
+
+        let mut archive = b"!<arch>\n".to_vec();
+        archive.extend(format!("{:<48}{:<10}`\n", "/", 4).as_bytes());
+        archive.extend(b"\0\0\0\0");
+        archive.extend(format!("{:<48}{:<10}`\n", "celt.o/", object.len()).as_bytes());
+        archive.extend(&object);
+
+        assert_eq!(elf_machine(&archive), Some(183));
+        assert_eq!(elf_machine(b"\xcf\xfa\xed\xfe"), None);
+        assert_eq!(elf_word_size(&object), None);
+
+        object[4] = 1;
+
+        assert_eq!(elf_word_size(&archive), None);
+        assert_eq!(elf_word_size(&object), Some(32));
+        assert_eq!(expected_elf_machine("aarch64"), Some(183));
+
+        // s390x and big-endian PowerPC store the machine big-endian.
+        object[5] = 2;
+        object[18] = 0;
+        object[19] = 22;
+
+        assert_eq!(elf_machine(&object), Some(22));
+        assert_eq!(expected_elf_machine("s390x"), Some(22));
+        assert_eq!(expected_elf_machine("riscv64"), Some(243));
+    }
+
+    #[test]
+    fn macho_cpu_type_of_objects_and_archives() {
+        let object = b"\xcf\xfa\xed\xfe\x0c\x00\x00\x01";
+
+        assert_eq!(macho_cpu_type(object), Some(0x0100_000c));
+
+        let mut archive = b"!<arch>\n".to_vec();
+        archive.extend(format!("{:<16}{:<32}{:<10}`\n", "#1/20", "", 28).as_bytes());
+        archive.extend(b"__.SYMDEF SORTED\0\0\0\0\0\0\0\0\0\0\0\0");
+        archive.extend(format!("{:<16}{:<32}{:<10}`\n", "#1/12", "", 20).as_bytes());
+        archive.extend(b"celt.o\0\0\0\0\0\0");
+        archive.extend(object);
+
+        assert_eq!(macho_cpu_type(&archive), Some(0x0100_000c));
+        assert_eq!(macho_cpu_type(b"\xca\xfe\xba\xbe\x00\x00\x00\x02"), None);
+        assert_eq!(expected_macho_cpu_type("aarch64"), Some(0x0100_000c));
+    }
+
+    #[test]
+    fn coff_machine_of_dlls_and_libraries() {
+        let mut dll = vec![0u8; 0x86];
+        dll[..2].copy_from_slice(b"MZ");
+        dll[0x3c] = 0x80;
+        dll[0x80..0x84].copy_from_slice(b"PE\0\0");
+        dll[0x84..0x86].copy_from_slice(&0x8664u16.to_le_bytes());
+
+        assert_eq!(coff_machine(&dll), Some(0x8664));
+
+        let mut import_library = b"!<arch>\n".to_vec();
+        import_library.extend(format!("{:<48}{:<10}`\n", "/", 4).as_bytes());
+        import_library.extend(b"\0\0\0\0");
+        import_library.extend(format!("{:<48}{:<10}`\n", "opus.dll/", 8).as_bytes());
+        import_library.extend(b"\0\0\xff\xff\0\0\x64\xaa");
+
+        assert_eq!(coff_machine(&import_library), Some(0xaa64));
+        assert_eq!(coff_machine(b"\x7fELF"), None);
+        assert_eq!(expected_coff_machine("x86"), Some(0x014c));
+    }
+
+    #[test]
+    fn compares_opus_versions() {
+        assert_eq!(is_at_least_version("libopus 1.3.1-fixed", "1.3"), Some(true));
+        assert_eq!(is_at_least_version("libopus 1.1.2", "1.3"), Some(false));
+        assert_eq!(is_at_least_version("1.3", "1.3.0"), Some(true));
+        assert_eq!(is_at_least_version("1.10", "1.5"), Some(true));
+        assert_eq!(is_at_least_version("libopus unknown", "1.3"), None);
+    }
+}
@@ -0,0 +1,108 @@
+# Rebuilds the prebuilt library-files of this directory from the bundled
+# `opus`-submodule via CMake and MSVC, then rewrites `SHA256SUMS`, recording
+# the Opus commit, CMake, and the MSVC compiler they were built with.
+#
+# Compiling and linking with `/Brepro` omits timestamps, hence the same
+# sources, CMake, and MSVC toolset rebuild bit-identical files.
+# Passing `-Check` rebuilds into a temporary directory instead and compares
+# the files against `SHA256SUMS`, failing on any difference.
+param(
+    [switch] $Check
+)
+
+$ErrorActionPreference = 'Stop'
+
+$msvcDirectory = $PSScriptRoot
+$sourceDirectory = Join-Path $msvcDirectory '..\opus'
+$buildRoot = Join-Path ([System.IO.Path]::GetTempPath()) 'audiopus_sys-msvc'
+$outputDirectory = if ($Check) { Join-Path $buildRoot 'out' } else { $msvcDirectory }
+$manifestPath = Join-Path $msvcDirectory 'SHA256SUMS'
+
+if (-not (Test-Path (Join-Path $sourceDirectory 'CMakeLists.txt'))) {
+    throw "No Opus sources in $sourceDirectory, run ``git submodule update --init``."
+}
+
+Remove-Item -Recurse -Force $buildRoot -ErrorAction SilentlyContinue
+
+$platforms = [ordered]@{ 'x86' = 'Win32'; 'x64' = 'x64' }
+$compilerVersion = 'unknown'
+
+foreach ($architecture in $platforms.Keys) {
+    foreach ($isShared in $false, $true) {
+        $buildDirectory = Join-Path $buildRoot "$architecture-$(if ($isShared) { 'dy' } else { 'static' })"
+        $targetDirectory = Join-Path $outputDirectory $architecture
+
+        if ($isShared) {
+            $targetDirectory = Join-Path $targetDirectory 'dy'
+        }
+
+        cmake -S $sourceDirectory -B $buildDirectory -A $platforms[$architecture] `
+            "-DBUILD_SHARED_LIBS=$(if ($isShared) { 'ON' } else { 'OFF' })" `
+            '-DCMAKE_C_FLAGS_RELEASE=/O2 /DNDEBUG /Brepro' `
+            '-DCMAKE_SHARED_LINKER_FLAGS=/Brepro' `
+            '-DCMAKE_STATIC_LINKER_FLAGS=/Brepro' `
+            '-DOPUS_BUILD_PROGRAMS=OFF' `
+            '-DOPUS_BUILD_TESTING=OFF'
+
+        if ($LASTEXITCODE -ne 0) {
+            throw "Configuring Opus for $architecture failed."
+        }
+
+        cmake --build $buildDirectory --config Release
+
+        if ($LASTEXITCODE -ne 0) {
+            throw "Building Opus for $architecture failed."
+        }
+
+        $compilerFile = Get-ChildItem -Recurse (Join-Path $buildDirectory 'CMakeFiles') -Filter 'CMakeCCompiler.cmake' |
+            Select-Object -First 1
+
+        if ($compilerFile -and ((Get-Content $compilerFile.FullName) -join "`n") -match 'CMAKE_C_COMPILER_VERSION "([^"]+)"') {
+            $compilerVersion = $Matches[1]
+        }
+
+        New-Item -ItemType Directory -Force $targetDirectory | Out-Null
+        Copy-Item (Join-Path $buildDirectory 'Release\opus.lib') $targetDirectory
+
+        if ($isShared) {
+            Copy-Item (Join-Path $buildDirectory 'Release\opus.dll') $targetDirectory
+            Copy-Item (Join-Path $buildDirectory 'Release\opus.exp') $targetDirectory
+        } else {
+            # Kept for dependents naming the library `libopus` via `OPUS_LIB_NAME`.
+            Copy-Item (Join-Path $buildDirectory 'Release\opus.lib') (Join-Path $targetDirectory 'libopus.lib')
+        }
+    }
+}
+
+$outputPrefix = (Resolve-Path $outputDirectory).Path.TrimEnd('\') + '\'
+$checksums = Get-ChildItem -Recurse -File $outputDirectory |
+    Where-Object { $_.Name -ne 'SHA256SUMS' -and $_.Extension -ne '.ps1' } |
+    Sort-Object FullName |
+    ForEach-Object {
+        $relativePath = $_.FullName.Substring($outputPrefix.Length).Replace('\', '/')
+        $checksum = (Get-FileHash -Algorithm SHA256 $_.FullName).Hash.ToLower()
+
+        "$checksum  $relativePath"
+    }
+
+if ($Check) {
+    $expected = Get-Content $manifestPath | Where-Object { $_ -and -not $_.StartsWith('#') }
+    $differences = $checksums | Where-Object { $expected -notcontains $_ }
+
+    if ($differences) {
+        $differences | ForEach-Object { Write-Host "Differs from SHA256SUMS: $_" }
+
+        throw 'The rebuilt library-files are not identical to the prebuilt ones.'
+    }
+
+    Write-Host 'The rebuilt library-files are identical to the prebuilt ones.'
+} else {
+    $commit = git -C $sourceDirectory rev-parse HEAD
+    $cmakeVersion = (cmake --version | Select-Object -First 1)
+    $header = @(
+        "# Built via msvc/regenerate.ps1 from opus $commit",
+        "# with $cmakeVersion and MSVC $compilerVersion."
+    )
+
+    [System.IO.File]::WriteAllText($manifestPath, (($header + $checksums) -join "`n") + "`n")
+}
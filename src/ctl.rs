@@ -0,0 +1,241 @@
+//! Typed wrappers around `opus_encoder_ctl` and `opus_decoder_ctl`, one per
+//! request.
+//!
+//! Rust passes variadic arguments as they are, hence a request expecting an
+//! `opus_int32` but receiving an `i64` or a pointer to an `i16` corrupts the
+//! stack or memory without any diagnostic. These wrappers fix the argument's
+//! type of each request instead.
+
+use super::*;
+use crate::ctypes::c_int;
+
+macro_rules! ctl_wrappers {
+    ($ctl:ident, $state:ty; $($(#[$attribute:meta])* $name:ident($request:ident, $argument:ty);)*) => {
+        $(
+            $(#[$attribute])*
+            ///
+            /// # Safety
+            ///
+            /// `st` must point to a live, initialised state.
+            #[inline]
+            pub unsafe fn $name(st: *mut $state, value: $argument) -> c_int {
+                $ctl(st, $request, value)
+            }
+        )*
+    };
+}
+
+/// Resets the encoder to the state of a freshly initialised one, via
+/// `OPUS_RESET_STATE`.
+///
+/// # Safety
+///
+/// `st` must point to a live, initialised state.
+#[cfg(feature = "encoder")]
+#[inline]
+pub unsafe fn opus_encoder_reset_state(st: *mut OpusEncoder) -> c_int {
+    opus_encoder_ctl(st, OPUS_RESET_STATE)
+}
+
+/// Resets the decoder to the state of a freshly initialised one, via
+/// `OPUS_RESET_STATE`.
+///
+/// # Safety
+///
+/// `st` must point to a live, initialised state.
+#[cfg(feature = "decoder")]
+#[inline]
+pub unsafe fn opus_decoder_reset_state(st: *mut OpusDecoder) -> c_int {
+    opus_decoder_ctl(st, OPUS_RESET_STATE)
+}
+
+#[cfg(feature = "encoder")]
+ctl_wrappers! {
+    opus_encoder_ctl, OpusEncoder;
+    /// Sets the complexity from 0 to 10, via `OPUS_SET_COMPLEXITY_REQUEST`.
+    opus_encoder_set_complexity(OPUS_SET_COMPLEXITY_REQUEST, opus_int32);
+    /// Gets the complexity, via `OPUS_GET_COMPLEXITY_REQUEST`.
+    opus_encoder_get_complexity(OPUS_GET_COMPLEXITY_REQUEST, *mut opus_int32);
+    /// Sets the bitrate in bits per second, `OPUS_AUTO`, or
+    /// `OPUS_BITRATE_MAX`, via `OPUS_SET_BITRATE_REQUEST`.
+    opus_encoder_set_bitrate(OPUS_SET_BITRATE_REQUEST, opus_int32);
+    /// Gets the bitrate, via `OPUS_GET_BITRATE_REQUEST`.
+    opus_encoder_get_bitrate(OPUS_GET_BITRATE_REQUEST, *mut opus_int32);
+    /// Enables (1) or disables (0) variable bitrate, via
+    /// `OPUS_SET_VBR_REQUEST`.
+    opus_encoder_set_vbr(OPUS_SET_VBR_REQUEST, opus_int32);
+    /// Gets whether variable bitrate is enabled, via `OPUS_GET_VBR_REQUEST`.
+    opus_encoder_get_vbr(OPUS_GET_VBR_REQUEST, *mut opus_int32);
+    /// Enables (1) or disables (0) constrained variable bitrate, via
+    /// `OPUS_SET_VBR_CONSTRAINT_REQUEST`.
+    opus_encoder_set_vbr_constraint(OPUS_SET_VBR_CONSTRAINT_REQUEST, opus_int32);
+    /// Gets whether variable bitrate is constrained, via
+    /// `OPUS_GET_VBR_CONSTRAINT_REQUEST`.
+    opus_encoder_get_vbr_constraint(OPUS_GET_VBR_CONSTRAINT_REQUEST, *mut opus_int32);
+    /// Forces mono (1), stereo (2), or `OPUS_AUTO`, via
+    /// `OPUS_SET_FORCE_CHANNELS_REQUEST`.
+    opus_encoder_set_force_channels(OPUS_SET_FORCE_CHANNELS_REQUEST, opus_int32);
+    /// Gets the forced channels, via `OPUS_GET_FORCE_CHANNELS_REQUEST`.
+    opus_encoder_get_force_channels(OPUS_GET_FORCE_CHANNELS_REQUEST, *mut opus_int32);
+    /// Sets the maximum `OPUS_BANDWIDTH_*`, via
+    /// `OPUS_SET_MAX_BANDWIDTH_REQUEST`.
+    opus_encoder_set_max_bandwidth(OPUS_SET_MAX_BANDWIDTH_REQUEST, opus_int32);
+    /// Gets the maximum bandwidth, via `OPUS_GET_MAX_BANDWIDTH_REQUEST`.
+    opus_encoder_get_max_bandwidth(OPUS_GET_MAX_BANDWIDTH_REQUEST, *mut opus_int32);
+    /// Sets the `OPUS_BANDWIDTH_*` or `OPUS_AUTO`, via
+    /// `OPUS_SET_BANDWIDTH_REQUEST`.
+    opus_encoder_set_bandwidth(OPUS_SET_BANDWIDTH_REQUEST, opus_int32);
+    /// Gets the bandwidth of the last encoded packet, via
+    /// `OPUS_GET_BANDWIDTH_REQUEST`.
+    opus_encoder_get_bandwidth(OPUS_GET_BANDWIDTH_REQUEST, *mut opus_int32);
+    /// Sets the `OPUS_SIGNAL_*` or `OPUS_AUTO`, via `OPUS_SET_SIGNAL_REQUEST`.
+    opus_encoder_set_signal(OPUS_SET_SIGNAL_REQUEST, opus_int32);
+    /// Gets the signal type, via `OPUS_GET_SIGNAL_REQUEST`.
+    opus_encoder_get_signal(OPUS_GET_SIGNAL_REQUEST, *mut opus_int32);
+    /// Sets the `OPUS_APPLICATION_*`, via `OPUS_SET_APPLICATION_REQUEST`.
+    opus_encoder_set_application(OPUS_SET_APPLICATION_REQUEST, opus_int32);
+    /// Gets the application, via `OPUS_GET_APPLICATION_REQUEST`.
+    opus_encoder_get_application(OPUS_GET_APPLICATION_REQUEST, *mut opus_int32);
+    /// Gets the samples of delay the encoder adds, via
+    /// `OPUS_GET_LOOKAHEAD_REQUEST`.
+    opus_encoder_get_lookahead(OPUS_GET_LOOKAHEAD_REQUEST, *mut opus_int32);
+    /// Enables (1) or disables (0) inband forward error correction, via
+    /// `OPUS_SET_INBAND_FEC_REQUEST`.
+    opus_encoder_set_inband_fec(OPUS_SET_INBAND_FEC_REQUEST, opus_int32);
+    /// Gets whether inband forward error correction is enabled, via
+    /// `OPUS_GET_INBAND_FEC_REQUEST`.
+    opus_encoder_get_inband_fec(OPUS_GET_INBAND_FEC_REQUEST, *mut opus_int32);
+    /// Sets the expected packet loss from 0 to 100 percent, via
+    /// `OPUS_SET_PACKET_LOSS_PERC_REQUEST`.
+    opus_encoder_set_packet_loss_perc(OPUS_SET_PACKET_LOSS_PERC_REQUEST, opus_int32);
+    /// Gets the expected packet loss, via `OPUS_GET_PACKET_LOSS_PERC_REQUEST`.
+    opus_encoder_get_packet_loss_perc(OPUS_GET_PACKET_LOSS_PERC_REQUEST, *mut opus_int32);
+    /// Enables (1) or disables (0) discontinuous transmission, via
+    /// `OPUS_SET_DTX_REQUEST`.
+    opus_encoder_set_dtx(OPUS_SET_DTX_REQUEST, opus_int32);
+    /// Gets whether discontinuous transmission is enabled, via
+    /// `OPUS_GET_DTX_REQUEST`.
+    opus_encoder_get_dtx(OPUS_GET_DTX_REQUEST, *mut opus_int32);
+    /// Sets the depth of the signal from 8 to 24 bits, via
+    /// `OPUS_SET_LSB_DEPTH_REQUEST`.
+    opus_encoder_set_lsb_depth(OPUS_SET_LSB_DEPTH_REQUEST, opus_int32);
+    /// Gets the depth of the signal, via `OPUS_GET_LSB_DEPTH_REQUEST`.
+    opus_encoder_get_lsb_depth(OPUS_GET_LSB_DEPTH_REQUEST, *mut opus_int32);
+    /// Sets the `OPUS_FRAMESIZE_*`, via
+    /// `OPUS_SET_EXPERT_FRAME_DURATION_REQUEST`.
+    opus_encoder_set_expert_frame_duration(OPUS_SET_EXPERT_FRAME_DURATION_REQUEST, opus_int32);
+    /// Gets the frame duration, via `OPUS_GET_EXPERT_FRAME_DURATION_REQUEST`.
+    opus_encoder_get_expert_frame_duration(
+        OPUS_GET_EXPERT_FRAME_DURATION_REQUEST,
+        *mut opus_int32
+    );
+    /// Disables (1) or enables (0) inter-frame prediction, via
+    /// `OPUS_SET_PREDICTION_DISABLED_REQUEST`.
+    opus_encoder_set_prediction_disabled(OPUS_SET_PREDICTION_DISABLED_REQUEST, opus_int32);
+    /// Gets whether inter-frame prediction is disabled, via
+    /// `OPUS_GET_PREDICTION_DISABLED_REQUEST`.
+    opus_encoder_get_prediction_disabled(OPUS_GET_PREDICTION_DISABLED_REQUEST, *mut opus_int32);
+    /// Disables (1) or enables (0) phase inversion, via
+    /// `OPUS_SET_PHASE_INVERSION_DISABLED_REQUEST`.
+    opus_encoder_set_phase_inversion_disabled(
+        OPUS_SET_PHASE_INVERSION_DISABLED_REQUEST,
+        opus_int32
+    );
+    /// Gets whether phase inversion is disabled, via
+    /// `OPUS_GET_PHASE_INVERSION_DISABLED_REQUEST`.
+    opus_encoder_get_phase_inversion_disabled(
+        OPUS_GET_PHASE_INVERSION_DISABLED_REQUEST,
+        *mut opus_int32
+    );
+    /// Gets the sampling rate, via `OPUS_GET_SAMPLE_RATE_REQUEST`.
+    opus_encoder_get_sample_rate(OPUS_GET_SAMPLE_RATE_REQUEST, *mut opus_int32);
+    /// Gets the final state of the range coder, via
+    /// `OPUS_GET_FINAL_RANGE_REQUEST`.
+    opus_encoder_get_final_range(OPUS_GET_FINAL_RANGE_REQUEST, *mut opus_uint32);
+    /// Gets whether the encoder is in discontinuous transmission, via
+    /// `OPUS_GET_IN_DTX_REQUEST`.
+    #[cfg(feature = "v1_4")]
+    opus_encoder_get_in_dtx(OPUS_GET_IN_DTX_REQUEST, *mut opus_int32);
+    /// Sets the milliseconds of Deep REDundancy from 0 to 1040, via
+    /// `OPUS_SET_DRED_DURATION_REQUEST`.
+    #[cfg(feature = "dred")]
+    opus_encoder_set_dred_duration(OPUS_SET_DRED_DURATION_REQUEST, opus_int32);
+    /// Gets the milliseconds of Deep REDundancy, via
+    /// `OPUS_GET_DRED_DURATION_REQUEST`.
+    #[cfg(feature = "dred")]
+    opus_encoder_get_dred_duration(OPUS_GET_DRED_DURATION_REQUEST, *mut opus_int32);
+}
+
+#[cfg(feature = "decoder")]
+ctl_wrappers! {
+    opus_decoder_ctl, OpusDecoder;
+    /// Sets the gain in Q8 dB from -32768 to 32767, via
+    /// `OPUS_SET_GAIN_REQUEST`.
+    opus_decoder_set_gain(OPUS_SET_GAIN_REQUEST, opus_int32);
+    /// Gets the gain, via `OPUS_GET_GAIN_REQUEST`.
+    opus_decoder_get_gain(OPUS_GET_GAIN_REQUEST, *mut opus_int32);
+    /// Gets the duration of the last decoded or concealed packet in samples,
+    /// via `OPUS_GET_LAST_PACKET_DURATION_REQUEST`.
+    opus_decoder_get_last_packet_duration(OPUS_GET_LAST_PACKET_DURATION_REQUEST, *mut opus_int32);
+    /// Gets the pitch of the last decoded frame, via
+    /// `OPUS_GET_PITCH_REQUEST`.
+    opus_decoder_get_pitch(OPUS_GET_PITCH_REQUEST, *mut opus_int32);
+    /// Gets the bandwidth of the last decoded packet, via
+    /// `OPUS_GET_BANDWIDTH_REQUEST`.
+    opus_decoder_get_bandwidth(OPUS_GET_BANDWIDTH_REQUEST, *mut opus_int32);
+    /// Disables (1) or enables (0) phase inversion, via
+    /// `OPUS_SET_PHASE_INVERSION_DISABLED_REQUEST`.
+    opus_decoder_set_phase_inversion_disabled(
+        OPUS_SET_PHASE_INVERSION_DISABLED_REQUEST,
+        opus_int32
+    );
+    /// Gets whether phase inversion is disabled, via
+    /// `OPUS_GET_PHASE_INVERSION_DISABLED_REQUEST`.
+    opus_decoder_get_phase_inversion_disabled(
+        OPUS_GET_PHASE_INVERSION_DISABLED_REQUEST,
+        *mut opus_int32
+    );
+    /// Gets the sampling rate, via `OPUS_GET_SAMPLE_RATE_REQUEST`.
+    opus_decoder_get_sample_rate(OPUS_GET_SAMPLE_RATE_REQUEST, *mut opus_int32);
+    /// Gets the final state of the range coder, via
+    /// `OPUS_GET_FINAL_RANGE_REQUEST`.
+    opus_decoder_get_final_range(OPUS_GET_FINAL_RANGE_REQUEST, *mut opus_uint32);
+}
+
+#[cfg(all(
+    test,
+    feature = "encoder",
+    feature = "decoder",
+    not(feature = "dlopen")
+))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_and_get_through_wrappers() {
+        let mut error = 0;
+        let mut value = 0;
+
+        unsafe {
+            let encoder = opus_encoder_create(48000, 2, OPUS_APPLICATION_AUDIO, &mut error);
+            assert_eq!(error, OPUS_OK);
+
+            assert_eq!(opus_encoder_set_bitrate(encoder, 64000), OPUS_OK);
+            assert_eq!(opus_encoder_get_bitrate(encoder, &mut value), OPUS_OK);
+            assert_eq!(value, 64000);
+
+            assert_eq!(opus_encoder_set_complexity(encoder, 11), OPUS_BAD_ARG);
+            assert_eq!(opus_encoder_reset_state(encoder), OPUS_OK);
+
+            let decoder = opus_decoder_create(48000, 2, &mut error);
+            assert_eq!(error, OPUS_OK);
+
+            assert_eq!(opus_decoder_set_gain(decoder, 256), OPUS_OK);
+            assert_eq!(opus_decoder_get_gain(decoder, &mut value), OPUS_OK);
+            assert_eq!(value, 256);
+
+            opus_encoder_destroy(encoder);
+            opus_decoder_destroy(decoder);
+        }
+    }
+}
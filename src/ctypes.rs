@@ -0,0 +1,58 @@
+//! The C types of the bindings, re-exported from `std` with the `std`-feature
+//! and defined for the target otherwise, as `core` lacks them before Rust 1.64.
+//!
+//! Opus requires an `int` of at least 32 bits, hence 16-bit targets are not
+//! supported.
+
+#[cfg(feature = "std")]
+pub use std::os::raw::{c_char, c_int, c_longlong, c_short, c_uchar, c_uint, c_ushort, c_void};
+
+#[cfg(not(feature = "std"))]
+pub use core::ffi::c_void;
+
+// Mirrors `std::os::raw::c_char`: unsigned on ARM, PowerPC, RISC-V, and
+// s390x, except for Apple's and Windows' ABIs on 64-bit ARM.
+#[cfg(all(
+    not(feature = "std"),
+    any(
+        all(
+            target_arch = "aarch64",
+            not(any(target_vendor = "apple", target_os = "windows"))
+        ),
+        target_arch = "arm",
+        target_arch = "powerpc",
+        target_arch = "powerpc64",
+        target_arch = "riscv32",
+        target_arch = "riscv64",
+        target_arch = "s390x"
+    )
+))]
+pub type c_char = u8;
+#[cfg(all(
+    not(feature = "std"),
+    not(any(
+        all(
+            target_arch = "aarch64",
+            not(any(target_vendor = "apple", target_os = "windows"))
+        ),
+        target_arch = "arm",
+        target_arch = "powerpc",
+        target_arch = "powerpc64",
+        target_arch = "riscv32",
+        target_arch = "riscv64",
+        target_arch = "s390x"
+    ))
+))]
+pub type c_char = i8;
+#[cfg(not(feature = "std"))]
+pub type c_uchar = u8;
+#[cfg(not(feature = "std"))]
+pub type c_short = i16;
+#[cfg(not(feature = "std"))]
+pub type c_ushort = u16;
+#[cfg(not(feature = "std"))]
+pub type c_int = i32;
+#[cfg(not(feature = "std"))]
+pub type c_uint = u32;
+#[cfg(not(feature = "std"))]
+pub type c_longlong = i64;
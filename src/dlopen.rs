@@ -0,0 +1,227 @@
+//! Bindings loading Opus at runtime instead of linking it, enabled via the
+//! `dlopen`-feature.
+//!
+//! The build script emits no linking directives when this feature is enabled,
+//! hence the functions declared in the crate's root must not be called.
+
+#[cfg(any(feature = "encoder", feature = "decoder"))]
+use super::*;
+use libloading::Library;
+use std::{
+    ffi::OsStr,
+    io, ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+/// The file name of Opus passed to the platform's dynamic loader by
+/// [`OpusLib::open`].
+///
+/// [`OpusLib::open`]: struct.OpusLib.html#method.open
+#[cfg(target_os = "windows")]
+pub const OPUS_LIBRARY_NAME: &str = "opus.dll";
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+pub const OPUS_LIBRARY_NAME: &str = "libopus.0.dylib";
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "ios")))]
+pub const OPUS_LIBRARY_NAME: &str = "libopus.so.0";
+
+macro_rules! opus_lib {
+    ($($(#[$attribute:meta])* $name:ident: $function:ty;)*) => {
+        /// A runtime-loaded Opus, exposing its functions as function-pointers
+        /// named after their counterparts in the crate's root.
+        ///
+        /// The library stays loaded as long as this value lives.
+        pub struct OpusLib {
+            $($(#[$attribute])* pub $name: $function,)*
+            _library: Library,
+        }
+
+        impl OpusLib {
+            /// Loads Opus from `path` and resolves all its functions.
+            ///
+            /// Fails if the library cannot be loaded or any function is
+            /// missing.
+            pub fn open_from<P: AsRef<OsStr>>(path: P) -> io::Result<Self> {
+                let library = Library::new(path)?;
+
+                unsafe {
+                    $(
+                        $(#[$attribute])*
+                        let $name = *library
+                            .get::<$function>(concat!(stringify!($name), "\0").as_bytes())?;
+                    )*
+
+                    Ok(OpusLib {
+                        $($(#[$attribute])* $name,)*
+                        _library: library,
+                    })
+                }
+            }
+        }
+    };
+}
+
+opus_lib! {
+    opus_strerror: unsafe extern "C" fn(crate::ctypes::c_int) -> *const crate::ctypes::c_char;
+    opus_get_version_string: unsafe extern "C" fn() -> *const crate::ctypes::c_char;
+    #[cfg(feature = "encoder")]
+    opus_encoder_get_size: unsafe extern "C" fn(crate::ctypes::c_int) -> crate::ctypes::c_int;
+    #[cfg(feature = "encoder")]
+    opus_encoder_create: unsafe extern "C" fn(
+        opus_int32,
+        crate::ctypes::c_int,
+        crate::ctypes::c_int,
+        *mut crate::ctypes::c_int,
+    ) -> *mut OpusEncoder;
+    #[cfg(feature = "encoder")]
+    opus_encoder_init: unsafe extern "C" fn(
+        *mut OpusEncoder,
+        opus_int32,
+        crate::ctypes::c_int,
+        crate::ctypes::c_int,
+    ) -> crate::ctypes::c_int;
+    #[cfg(feature = "encoder")]
+    opus_encode: unsafe extern "C" fn(
+        *mut OpusEncoder,
+        *const opus_int16,
+        crate::ctypes::c_int,
+        *mut crate::ctypes::c_uchar,
+        opus_int32,
+    ) -> opus_int32;
+    #[cfg(not(feature = "disable-float-api"))]
+    #[cfg(feature = "encoder")]
+    opus_encode_float: unsafe extern "C" fn(
+        *mut OpusEncoder,
+        *const f32,
+        crate::ctypes::c_int,
+        *mut crate::ctypes::c_uchar,
+        opus_int32,
+    ) -> opus_int32;
+    #[cfg(feature = "encoder")]
+    opus_encoder_destroy: unsafe extern "C" fn(*mut OpusEncoder);
+    #[cfg(feature = "encoder")]
+    opus_encoder_ctl: unsafe extern "C" fn(
+        *mut OpusEncoder,
+        crate::ctypes::c_int,
+        ...
+    ) -> crate::ctypes::c_int;
+    #[cfg(feature = "decoder")]
+    opus_decoder_get_size: unsafe extern "C" fn(crate::ctypes::c_int) -> crate::ctypes::c_int;
+    #[cfg(feature = "decoder")]
+    opus_decoder_create: unsafe extern "C" fn(
+        opus_int32,
+        crate::ctypes::c_int,
+        *mut crate::ctypes::c_int,
+    ) -> *mut OpusDecoder;
+    #[cfg(feature = "decoder")]
+    opus_decoder_init: unsafe extern "C" fn(
+        *mut OpusDecoder,
+        opus_int32,
+        crate::ctypes::c_int,
+    ) -> crate::ctypes::c_int;
+    #[cfg(feature = "decoder")]
+    opus_decode: unsafe extern "C" fn(
+        *mut OpusDecoder,
+        *const crate::ctypes::c_uchar,
+        opus_int32,
+        *mut opus_int16,
+        crate::ctypes::c_int,
+        crate::ctypes::c_int,
+    ) -> crate::ctypes::c_int;
+    #[cfg(not(feature = "disable-float-api"))]
+    #[cfg(feature = "decoder")]
+    opus_decode_float: unsafe extern "C" fn(
+        *mut OpusDecoder,
+        *const crate::ctypes::c_uchar,
+        opus_int32,
+        *mut f32,
+        crate::ctypes::c_int,
+        crate::ctypes::c_int,
+    ) -> crate::ctypes::c_int;
+    #[cfg(feature = "decoder")]
+    opus_decoder_ctl: unsafe extern "C" fn(
+        *mut OpusDecoder,
+        crate::ctypes::c_int,
+        ...
+    ) -> crate::ctypes::c_int;
+    #[cfg(feature = "decoder")]
+    opus_decoder_destroy: unsafe extern "C" fn(*mut OpusDecoder);
+    #[cfg(feature = "decoder")]
+    opus_decoder_get_nb_samples: unsafe extern "C" fn(
+        *const OpusDecoder,
+        *const crate::ctypes::c_uchar,
+        opus_int32,
+    ) -> crate::ctypes::c_int;
+    #[cfg(not(feature = "disable-float-api"))]
+    opus_pcm_soft_clip: unsafe extern "C" fn(
+        *mut f32,
+        crate::ctypes::c_int,
+        crate::ctypes::c_int,
+        *mut f32,
+    );
+}
+
+impl OpusLib {
+    /// Loads Opus by its platform-specific [`OPUS_LIBRARY_NAME`].
+    ///
+    /// [`OPUS_LIBRARY_NAME`]: constant.OPUS_LIBRARY_NAME.html
+    pub fn open() -> io::Result<Self> {
+        Self::open_from(OPUS_LIBRARY_NAME)
+    }
+}
+
+/// The Opus loaded by [`init`], never unloaded once set.
+///
+/// [`init`]: fn.init.html
+static OPUS_LIB: AtomicPtr<OpusLib> = AtomicPtr::new(ptr::null_mut());
+
+/// Loads Opus via [`OpusLib::open`] once for the whole process and returns it.
+///
+/// A failed attempt is not remembered, hence calling this again retries
+/// loading, e.g. after the user installed Opus.
+///
+/// [`OpusLib::open`]: struct.OpusLib.html#method.open
+pub fn init() -> io::Result<&'static OpusLib> {
+    let loaded = OPUS_LIB.load(Ordering::Acquire);
+
+    if !loaded.is_null() {
+        return Ok(unsafe { &*loaded });
+    }
+
+    let opened = Box::into_raw(Box::new(OpusLib::open()?));
+
+    match OPUS_LIB.compare_exchange(ptr::null_mut(), opened, Ordering::AcqRel, Ordering::Acquire) {
+        Ok(_) => Ok(unsafe { &*opened }),
+        // Another thread won the race, its library is kept instead.
+        Err(winner) => {
+            drop(unsafe { Box::from_raw(opened) });
+
+            Ok(unsafe { &*winner })
+        }
+    }
+}
+
+/// Whether Opus can be loaded at runtime, loading it via [`init`] if not yet
+/// done.
+///
+/// [`init`]: fn.init.html
+pub fn is_available() -> bool {
+    init().is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_library_is_an_error() {
+        assert!(OpusLib::open_from("this-library-does-not-exist").is_err());
+    }
+
+    #[test]
+    fn init_is_shared() {
+        if let (Ok(first), Ok(second)) = (init(), init()) {
+            assert!(std::ptr::eq(first, second));
+            assert!(is_available());
+        }
+    }
+}
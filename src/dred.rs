@@ -0,0 +1,208 @@
+//! Bindings to the Deep REDundancy (DRED) API of Opus 1.5, enabled via the
+//! `dred`-feature.
+//!
+//! DRED embeds a neural, low-bitrate copy of the recent audio in packets,
+//! which a decoder uses to conceal the loss of preceding packets. The linked
+//! Opus must be built with DRED enabled.
+
+use super::*;
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct OpusDREDDecoder {
+    _data: [u8; 0],
+    _marker: ::core::marker::PhantomData<(*mut u8, ::core::marker::PhantomPinned)>,
+}
+#[repr(C)]
+#[derive(Debug)]
+pub struct OpusDRED {
+    _data: [u8; 0],
+    _marker: ::core::marker::PhantomData<(*mut u8, ::core::marker::PhantomPinned)>,
+}
+opus_extern! {
+    #[doc = " Gets the size of an <code>OpusDREDDecoder</code> structure."]
+    #[doc = " @returns The size in bytes."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "audiopus_opus_dred_decoder_get_size"
+    )]
+    pub fn opus_dred_decoder_get_size() -> crate::ctypes::c_int;
+}
+opus_extern! {
+    #[doc = " Allocates and initializes an OpusDREDDecoder state."]
+    #[doc = " @param [out] error <tt>int*</tt>: #OPUS_OK Success or @ref opus_errorcodes"]
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "audiopus_opus_dred_decoder_create"
+    )]
+    pub fn opus_dred_decoder_create(error: *mut crate::ctypes::c_int) -> *mut OpusDREDDecoder;
+}
+opus_extern! {
+    #[doc = " Initializes an <code>OpusDREDDecoder</code> state."]
+    #[doc = " @param[in] dec <tt>OpusDREDDecoder*</tt>: State to be initialized."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "audiopus_opus_dred_decoder_init"
+    )]
+    pub fn opus_dred_decoder_init(dec: *mut OpusDREDDecoder) -> crate::ctypes::c_int;
+}
+opus_extern! {
+    #[doc = " Frees an <code>OpusDREDDecoder</code> allocated by opus_dred_decoder_create()."]
+    #[doc = " @param[in] dec <tt>OpusDREDDecoder*</tt>: State to be freed."]
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "audiopus_opus_dred_decoder_destroy"
+    )]
+    pub fn opus_dred_decoder_destroy(dec: *mut OpusDREDDecoder);
+}
+opus_extern! {
+    #[doc = " Perform a CTL function on an Opus DRED decoder."]
+    #[doc = ""]
+    #[doc = " Generally the request and subsequent arguments are generated"]
+    #[doc = " by a convenience macro."]
+    #[doc = " @param dred_dec <tt>OpusDREDDecoder*</tt>: DRED Decoder state."]
+    #[doc = " @param request This and all remaining parameters should be replaced by one"]
+    #[doc = "                of the convenience macros in @ref opus_genericctls or"]
+    #[doc = "                @ref opus_decoderctls."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "audiopus_opus_dred_decoder_ctl"
+    )]
+    pub fn opus_dred_decoder_ctl(
+        dred_dec: *mut OpusDREDDecoder,
+        request: crate::ctypes::c_int,
+        ...
+    ) -> crate::ctypes::c_int;
+}
+opus_extern! {
+    #[doc = " Gets the size of an <code>OpusDRED</code> structure."]
+    #[doc = " @returns The size in bytes."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "audiopus_opus_dred_get_size")]
+    pub fn opus_dred_get_size() -> crate::ctypes::c_int;
+}
+opus_extern! {
+    #[doc = " Allocates and initializes a DRED state."]
+    #[doc = " @param [out] error <tt>int*</tt>: #OPUS_OK Success or @ref opus_errorcodes"]
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "audiopus_opus_dred_alloc")]
+    pub fn opus_dred_alloc(error: *mut crate::ctypes::c_int) -> *mut OpusDRED;
+}
+opus_extern! {
+    #[doc = " Frees an <code>OpusDRED</code> allocated by opus_dred_alloc()."]
+    #[doc = " @param[in] dec <tt>OpusDRED*</tt>: State to be freed."]
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "audiopus_opus_dred_free")]
+    pub fn opus_dred_free(dec: *mut OpusDRED);
+}
+opus_extern! {
+    #[doc = " Decode an Opus DRED packet."]
+    #[doc = " @param [in] dred_dec <tt>OpusDRED*</tt>: DRED Decoder state"]
+    #[doc = " @param [in] dred <tt>OpusDRED*</tt>: DRED state"]
+    #[doc = " @param [in] data <tt>char*</tt>: Input payload"]
+    #[doc = " @param [in] len <tt>opus_int32</tt>: Number of bytes in payload"]
+    #[doc = " @param [in] max_dred_samples <tt>opus_int32</tt>: Maximum number of DRED samples that may be needed (if available in the packet)."]
+    #[doc = " @param [in] sampling_rate <tt>opus_int32</tt>: Sampling rate used for max_dred_samples argument. Needs not match the actual sampling rate of the decoder."]
+    #[doc = " @param [out] dred_end <tt>opus_int32*</tt>: Number of non-encoded (silence) samples between the DRED timestamp and the last DRED sample."]
+    #[doc = " @param [in] defer_processing <tt>int</tt>: Flag (0 or 1). If set to one, the CPU-intensive part of the DRED decoding is deferred until opus_dred_process() is called."]
+    #[doc = " @returns Offset (positive) of the first decoded DRED samples, zero if no DRED is present, or @ref opus_errorcodes"]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "audiopus_opus_dred_parse")]
+    pub fn opus_dred_parse(
+        dred_dec: *mut OpusDREDDecoder,
+        dred: *mut OpusDRED,
+        data: *const crate::ctypes::c_uchar,
+        len: opus_int32,
+        max_dred_samples: opus_int32,
+        sampling_rate: opus_int32,
+        dred_end: *mut crate::ctypes::c_int,
+        defer_processing: crate::ctypes::c_int,
+    ) -> crate::ctypes::c_int;
+}
+opus_extern! {
+    #[doc = " Finish decoding an Opus DRED packet. The function only needs to be called if opus_dred_parse() was called with defer_processing=1."]
+    #[doc = " The source and destination will often be the same DRED state."]
+    #[doc = " @param [in] dred_dec <tt>OpusDRED*</tt>: DRED Decoder state"]
+    #[doc = " @param [in] src <tt>OpusDRED*</tt>: Source DRED state to start the processing from."]
+    #[doc = " @param [out] dst <tt>OpusDRED*</tt>: Destination DRED state to store the updated state after processing."]
+    #[doc = " @returns @ref opus_errorcodes"]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "audiopus_opus_dred_process")]
+    pub fn opus_dred_process(
+        dred_dec: *mut OpusDREDDecoder,
+        src: *const OpusDRED,
+        dst: *mut OpusDRED,
+    ) -> crate::ctypes::c_int;
+}
+opus_extern! {
+    #[doc = " Decode audio from an Opus DRED packet with 16-bit output."]
+    #[doc = " @param [in] st <tt>OpusDecoder*</tt>: Decoder state"]
+    #[doc = " @param [in] dred <tt>OpusDRED*</tt>: DRED state"]
+    #[doc = " @param [in] dred_offset <tt>opus_int32</tt>: position of the redundancy to decode (in samples before the beginning of the real audio data in the packet)."]
+    #[doc = " @param [out] pcm <tt>opus_int16*</tt>: Output signal (interleaved if 2 channels). length"]
+    #[doc = "  is frame_size*channels*sizeof(opus_int16)"]
+    #[doc = " @param [in] frame_size Number of samples per channel to decode in \\a pcm."]
+    #[doc = "  frame_size <b>must</b> be a multiple of 2.5 ms."]
+    #[doc = " @returns Number of decoded samples or @ref opus_errorcodes"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "audiopus_opus_decoder_dred_decode"
+    )]
+    pub fn opus_decoder_dred_decode(
+        st: *mut OpusDecoder,
+        dred: *const OpusDRED,
+        dred_offset: opus_int32,
+        pcm: *mut opus_int16,
+        frame_size: opus_int32,
+    ) -> crate::ctypes::c_int;
+}
+opus_extern! {
+    #[doc = " Decode audio from an Opus DRED packet with floating point output."]
+    #[doc = " @param [in] st <tt>OpusDecoder*</tt>: Decoder state"]
+    #[doc = " @param [in] dred <tt>OpusDRED*</tt>: DRED state"]
+    #[doc = " @param [in] dred_offset <tt>opus_int32</tt>: position of the redundancy to decode (in samples before the beginning of the real audio data in the packet)."]
+    #[doc = " @param [out] pcm <tt>float*</tt>: Output signal (interleaved if 2 channels). length"]
+    #[doc = "  is frame_size*channels*sizeof(float)"]
+    #[doc = " @param [in] frame_size Number of samples per channel to decode in \\a pcm."]
+    #[doc = "  frame_size <b>must</b> be a multiple of 2.5 ms."]
+    #[doc = " @returns Number of decoded samples or @ref opus_errorcodes"]
+    #[cfg(not(feature = "disable-float-api"))]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "audiopus_opus_decoder_dred_decode_float"
+    )]
+    pub fn opus_decoder_dred_decode_float(
+        st: *mut OpusDecoder,
+        dred: *const OpusDRED,
+        dred_offset: opus_int32,
+        pcm: *mut f32,
+        frame_size: opus_int32,
+    ) -> crate::ctypes::c_int;
+}
+
+#[cfg(all(
+    test,
+    feature = "encoder",
+    feature = "decoder",
+    not(feature = "dlopen")
+))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_dred_decoder() {
+        let mut error = 0;
+
+        unsafe {
+            assert!(opus_dred_decoder_get_size() > 0);
+            assert!(opus_dred_get_size() > 0);
+
+            let decoder = opus_dred_decoder_create(&mut error);
+            assert_eq!(error, OPUS_OK);
+            let dred = opus_dred_alloc(&mut error);
+            assert_eq!(error, OPUS_OK);
+
+            opus_dred_free(dred);
+            opus_dred_decoder_destroy(decoder);
+        }
+    }
+}
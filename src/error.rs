@@ -0,0 +1,86 @@
+//! Names of Opus' error codes, such as `OPUS_BAD_ARG`, as returned by nearly
+//! every function of the bindings.
+//!
+//! `opus_strerror` describes an error code in prose but requires a linked
+//! Opus, whereas `opus_error_name` maps it to the name of its constant in Rust
+//! alone. Branching in a `const fn` requires Rust 1.46, hence it looks the name
+//! up in a table instead of matching.
+
+use super::*;
+use crate::ctypes::c_int;
+
+/// The number of error codes, as `len` isn't a `const fn` on Rust 1.33.
+const OPUS_ERROR_COUNT: usize = 8;
+
+/// The error codes, ordered from `OPUS_OK` to `OPUS_ALLOC_FAIL`.
+pub const OPUS_ERROR_CODES: [c_int; OPUS_ERROR_COUNT] = [
+    OPUS_OK,
+    OPUS_BAD_ARG,
+    OPUS_BUFFER_TOO_SMALL,
+    OPUS_INTERNAL_ERROR,
+    OPUS_INVALID_PACKET,
+    OPUS_UNIMPLEMENTED,
+    OPUS_INVALID_STATE,
+    OPUS_ALLOC_FAIL,
+];
+
+/// The names of `OPUS_ERROR_CODES` in the same order, followed by `None` for
+/// any other value.
+const OPUS_ERROR_NAMES: [Option<&str>; OPUS_ERROR_COUNT + 1] = [
+    Some("OPUS_OK"),
+    Some("OPUS_BAD_ARG"),
+    Some("OPUS_BUFFER_TOO_SMALL"),
+    Some("OPUS_INTERNAL_ERROR"),
+    Some("OPUS_INVALID_PACKET"),
+    Some("OPUS_UNIMPLEMENTED"),
+    Some("OPUS_INVALID_STATE"),
+    Some("OPUS_ALLOC_FAIL"),
+    None,
+];
+
+/// Returns `offset` as an index into a table of `len` entries followed by a
+/// fallback, i.e. `len` if `offset` is negative or not below `len`.
+///
+/// Computed without branching, which a `const fn` on Rust 1.33 can't do.
+pub(crate) const fn table_index(offset: i64, len: usize) -> usize {
+    // Negative offsets wrap around to above any table's length.
+    let offset = offset as u64;
+    let is_outside = (offset >= len as u64) as u64;
+
+    (offset * (1 - is_outside) + len as u64 * is_outside) as usize
+}
+
+/// Returns the name of the constant equal to `error`, e.g. `"OPUS_BAD_ARG"`
+/// for `-1`, or `None` if `error` is no error code of Opus.
+///
+/// Functions returning a length or count on success, such as `opus_encode`,
+/// return error codes only if negative, hence their `0` is no `OPUS_OK`.
+pub const fn opus_error_name(error: c_int) -> Option<&'static str> {
+    OPUS_ERROR_NAMES[table_index(-(error as i64), OPUS_ERROR_COUNT)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_every_error_code() {
+        for (index, &error) in OPUS_ERROR_CODES.iter().enumerate() {
+            assert_eq!(error, -(index as c_int));
+            assert!(opus_error_name(error).is_some());
+        }
+
+        assert_eq!(opus_error_name(OPUS_BAD_ARG), Some("OPUS_BAD_ARG"));
+        assert_eq!(opus_error_name(1), None);
+        assert_eq!(opus_error_name(OPUS_ALLOC_FAIL - 1), None);
+        assert_eq!(opus_error_name(c_int::min_value()), None);
+        assert_eq!(opus_error_name(c_int::max_value()), None);
+    }
+
+    #[test]
+    fn is_const() {
+        const NAME: Option<&str> = opus_error_name(OPUS_INVALID_STATE);
+
+        assert_eq!(NAME, Some("OPUS_INVALID_STATE"));
+    }
+}
@@ -0,0 +1,170 @@
+//! Conversions between the constants of frame sizes, bandwidths, and sample
+//! rates, which wrappers otherwise compute from magic numbers.
+//!
+//! Like `opus_error_name`, these are `const fn`s looking values up in tables,
+//! as branching therein requires Rust 1.46, except `opus_samples_framesize`,
+//! which searches `OPUS_FRAMESIZES`.
+
+use super::*;
+use crate::ctypes::c_int;
+use crate::error::table_index;
+
+/// The sample rates Opus encodes and decodes at, in Hz.
+pub const OPUS_SAMPLE_RATES: [opus_int32; 5] = [8000, 12000, 16000, 24000, 48000];
+
+/// The `OPUS_FRAMESIZE_*`-constants of fixed durations, from 2.5 ms to 120 ms.
+pub const OPUS_FRAMESIZES: [c_int; 9] = [
+    OPUS_FRAMESIZE_2_5_MS,
+    OPUS_FRAMESIZE_5_MS,
+    OPUS_FRAMESIZE_10_MS,
+    OPUS_FRAMESIZE_20_MS,
+    OPUS_FRAMESIZE_40_MS,
+    OPUS_FRAMESIZE_60_MS,
+    OPUS_FRAMESIZE_80_MS,
+    OPUS_FRAMESIZE_100_MS,
+    OPUS_FRAMESIZE_120_MS,
+];
+
+/// The durations of `OPUS_FRAMESIZES` in multiples of 2.5 ms, followed by `0`
+/// for any other frame size.
+const OPUS_FRAMESIZE_MULTIPLES: [c_int; 10] = [1, 2, 4, 8, 16, 24, 32, 40, 48, 0];
+
+/// Whether `sample_rate` is one of `OPUS_SAMPLE_RATES`.
+const fn is_sample_rate(sample_rate: opus_int32) -> bool {
+    (sample_rate == OPUS_SAMPLE_RATES[0])
+        | (sample_rate == OPUS_SAMPLE_RATES[1])
+        | (sample_rate == OPUS_SAMPLE_RATES[2])
+        | (sample_rate == OPUS_SAMPLE_RATES[3])
+        | (sample_rate == OPUS_SAMPLE_RATES[4])
+}
+
+/// Returns the number of samples per channel of a frame lasting `frame_size`,
+/// one of `OPUS_FRAMESIZES`, at `sample_rate`, e.g. 960 for
+/// `OPUS_FRAMESIZE_20_MS` at 48 kHz.
+///
+/// Returns `None` for `OPUS_FRAMESIZE_ARG`, unknown frame sizes, or sample
+/// rates not in `OPUS_SAMPLE_RATES`.
+pub const fn opus_framesize_samples(sample_rate: opus_int32, frame_size: c_int) -> Option<c_int> {
+    let offset = frame_size as i64 - OPUS_FRAMESIZE_2_5_MS as i64;
+    let multiple = OPUS_FRAMESIZE_MULTIPLES[table_index(offset, 9)];
+    let is_valid = is_sample_rate(sample_rate) & (multiple != 0);
+
+    [None, Some(sample_rate / 400 * multiple)][is_valid as usize]
+}
+
+/// Returns the `OPUS_FRAMESIZE_*`-constant lasting `samples` per channel at
+/// `sample_rate`, the inverse of `opus_framesize_samples`.
+pub fn opus_samples_framesize(sample_rate: opus_int32, samples: c_int) -> Option<c_int> {
+    OPUS_FRAMESIZES
+        .iter()
+        .cloned()
+        .find(|&frame_size| opus_framesize_samples(sample_rate, frame_size) == Some(samples))
+}
+
+/// The sample rates of the `OPUS_BANDWIDTH_*`-constants from narrowband to
+/// fullband, followed by `None` for any other bandwidth.
+const OPUS_BANDWIDTH_SAMPLE_RATES: [Option<opus_int32>; 6] = [
+    Some(8000),
+    Some(12000),
+    Some(16000),
+    Some(24000),
+    Some(48000),
+    None,
+];
+
+/// Returns the sample rate, in Hz, sufficient to represent `bandwidth`, one of
+/// the `OPUS_BANDWIDTH_*`-constants, e.g. 16000 for `OPUS_BANDWIDTH_WIDEBAND`,
+/// or `None` for `OPUS_AUTO` and unknown bandwidths.
+pub const fn opus_bandwidth_sample_rate(bandwidth: c_int) -> Option<opus_int32> {
+    let offset = bandwidth as i64 - OPUS_BANDWIDTH_NARROWBAND as i64;
+
+    OPUS_BANDWIDTH_SAMPLE_RATES[table_index(offset, 5)]
+}
+
+/// Whether `application` is one of the `OPUS_APPLICATION_*`-constants, which
+/// `opus_encoder_create` requires.
+pub const fn opus_is_application(application: c_int) -> bool {
+    (application == OPUS_APPLICATION_VOIP)
+        | (application == OPUS_APPLICATION_AUDIO)
+        | (application == OPUS_APPLICATION_RESTRICTED_LOWDELAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn framesize_samples() {
+        assert_eq!(
+            opus_framesize_samples(48000, OPUS_FRAMESIZE_2_5_MS),
+            Some(120)
+        );
+        assert_eq!(
+            opus_framesize_samples(48000, OPUS_FRAMESIZE_20_MS),
+            Some(960)
+        );
+        assert_eq!(
+            opus_framesize_samples(8000, OPUS_FRAMESIZE_120_MS),
+            Some(960)
+        );
+        assert_eq!(
+            opus_framesize_samples(12000, OPUS_FRAMESIZE_2_5_MS),
+            Some(30)
+        );
+        assert_eq!(opus_framesize_samples(48000, OPUS_FRAMESIZE_ARG), None);
+        assert_eq!(opus_framesize_samples(44100, OPUS_FRAMESIZE_20_MS), None);
+
+        for &sample_rate in &OPUS_SAMPLE_RATES {
+            for &frame_size in &OPUS_FRAMESIZES {
+                let samples = opus_framesize_samples(sample_rate, frame_size).unwrap();
+
+                assert_eq!(
+                    opus_samples_framesize(sample_rate, samples),
+                    Some(frame_size)
+                );
+            }
+        }
+
+        assert_eq!(opus_samples_framesize(48000, 961), None);
+        assert_eq!(
+            opus_framesize_samples(48000, OPUS_FRAMESIZE_120_MS + 1),
+            None
+        );
+        assert_eq!(opus_framesize_samples(48000, c_int::min_value()), None);
+    }
+
+    #[test]
+    fn are_const() {
+        const SAMPLES: Option<c_int> = opus_framesize_samples(16000, OPUS_FRAMESIZE_10_MS);
+        const SAMPLE_RATE: Option<opus_int32> =
+            opus_bandwidth_sample_rate(OPUS_BANDWIDTH_NARROWBAND);
+        const IS_APPLICATION: bool = opus_is_application(OPUS_APPLICATION_VOIP);
+
+        assert_eq!(SAMPLES, Some(160));
+        assert_eq!(SAMPLE_RATE, Some(8000));
+        assert!(IS_APPLICATION);
+    }
+
+    #[test]
+    fn bandwidth_sample_rates() {
+        assert_eq!(
+            opus_bandwidth_sample_rate(OPUS_BANDWIDTH_WIDEBAND),
+            Some(16000)
+        );
+        assert_eq!(
+            opus_bandwidth_sample_rate(OPUS_BANDWIDTH_FULLBAND),
+            Some(48000)
+        );
+        assert_eq!(opus_bandwidth_sample_rate(OPUS_AUTO), None);
+        assert_eq!(
+            opus_bandwidth_sample_rate(OPUS_BANDWIDTH_FULLBAND + 1),
+            None
+        );
+    }
+
+    #[test]
+    fn applications() {
+        assert!(opus_is_application(OPUS_APPLICATION_AUDIO));
+        assert!(!opus_is_application(OPUS_SIGNAL_MUSIC));
+    }
+}
@@ -0,0 +1,25 @@
+//! The channel mapping families of Ogg Opus, defined by RFC 7845 and RFC 8486
+//! rather than Opus' headers, passed as `mapping_family` to e.g.
+//! `opus_multistream_surround_encoder_create`, which derives the streams and
+//! the channel mapping from it.
+
+use crate::ctypes::c_int;
+
+/// Mono or stereo in a single stream, i.e. 1 or 2 channels.
+pub const OPUS_MAPPING_FAMILY_RTP: c_int = 0;
+
+/// Surround in the Vorbis channel order, i.e. 1 to 8 channels, such as
+/// 6 channels for 5.1 surround.
+pub const OPUS_MAPPING_FAMILY_VORBIS: c_int = 1;
+
+/// Ambisonics of orders 0 to 14, optionally accompanied by a non-diegetic
+/// stereo pair.
+pub const OPUS_MAPPING_FAMILY_AMBISONICS: c_int = 2;
+
+/// Ambisonics mixed via a demixing matrix, encoded by the
+/// `opus_projection_*`-functions of the `projection`-feature rather than the
+/// surround encoder.
+pub const OPUS_MAPPING_FAMILY_AMBISONICS_PROJECTION: c_int = 3;
+
+/// Up to 255 channels without any defined meaning, each coded separately.
+pub const OPUS_MAPPING_FAMILY_DISCRETE: c_int = 255;
@@ -0,0 +1,235 @@
+//! Bindings to `libopusenc`, encoding Ogg Opus streams, enabled via the
+//! `opusenc`-feature.
+
+use super::*;
+
+pub const OPE_API_VERSION: crate::ctypes::c_int = 0;
+pub const OPE_OK: crate::ctypes::c_int = 0;
+pub const OPE_BAD_ARG: crate::ctypes::c_int = -11;
+pub const OPE_INTERNAL_ERROR: crate::ctypes::c_int = -13;
+pub const OPE_UNIMPLEMENTED: crate::ctypes::c_int = -15;
+pub const OPE_ALLOC_FAIL: crate::ctypes::c_int = -17;
+pub const OPE_CANNOT_OPEN: crate::ctypes::c_int = -30;
+pub const OPE_TOO_LATE: crate::ctypes::c_int = -31;
+pub const OPE_INVALID_PICTURE: crate::ctypes::c_int = -32;
+pub const OPE_INVALID_ICON: crate::ctypes::c_int = -33;
+pub const OPE_WRITE_FAIL: crate::ctypes::c_int = -34;
+pub const OPE_CLOSE_FAIL: crate::ctypes::c_int = -35;
+pub const OPE_SET_DECISION_DELAY_REQUEST: crate::ctypes::c_int = 14000;
+pub const OPE_GET_DECISION_DELAY_REQUEST: crate::ctypes::c_int = 14001;
+pub const OPE_SET_MUXING_DELAY_REQUEST: crate::ctypes::c_int = 14002;
+pub const OPE_GET_MUXING_DELAY_REQUEST: crate::ctypes::c_int = 14003;
+pub const OPE_SET_COMMENT_PADDING_REQUEST: crate::ctypes::c_int = 14004;
+pub const OPE_GET_COMMENT_PADDING_REQUEST: crate::ctypes::c_int = 14005;
+pub const OPE_SET_SERIALNO_REQUEST: crate::ctypes::c_int = 14006;
+pub const OPE_GET_SERIALNO_REQUEST: crate::ctypes::c_int = 14007;
+pub const OPE_SET_PACKET_CALLBACK_REQUEST: crate::ctypes::c_int = 14008;
+pub const OPE_SET_HEADER_GAIN_REQUEST: crate::ctypes::c_int = 14010;
+pub const OPE_GET_HEADER_GAIN_REQUEST: crate::ctypes::c_int = 14011;
+pub const OPE_GET_NB_STREAMS_REQUEST: crate::ctypes::c_int = 14013;
+pub const OPE_GET_NB_COUPLED_STREAMS_REQUEST: crate::ctypes::c_int = 14015;
+#[doc = " Called for writing a page."]
+pub type ope_write_func = ::core::option::Option<
+    unsafe extern "C" fn(
+        user_data: *mut crate::ctypes::c_void,
+        ptr: *const crate::ctypes::c_uchar,
+        len: opus_int32,
+    ) -> crate::ctypes::c_int,
+>;
+#[doc = " Called for closing a stream."]
+pub type ope_close_func = ::core::option::Option<
+    unsafe extern "C" fn(user_data: *mut crate::ctypes::c_void) -> crate::ctypes::c_int,
+>;
+#[doc = " Callback functions for accessing the stream."]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct OpusEncCallbacks {
+    #[doc = " Callback for writing to the stream."]
+    pub write: ope_write_func,
+    #[doc = " Callback for closing the stream."]
+    pub close: ope_close_func,
+}
+#[repr(C)]
+#[derive(Debug)]
+pub struct OggOpusComments {
+    _data: [u8; 0],
+    _marker: ::core::marker::PhantomData<(*mut u8, ::core::marker::PhantomPinned)>,
+}
+#[repr(C)]
+#[derive(Debug)]
+pub struct OggOpusEnc {
+    _data: [u8; 0],
+    _marker: ::core::marker::PhantomData<(*mut u8, ::core::marker::PhantomPinned)>,
+}
+extern "C" {
+    #[doc = " Create a new comments object."]
+    #[doc = "\\return Newly-created comments object."]
+    pub fn ope_comments_create() -> *mut OggOpusComments;
+}
+extern "C" {
+    #[doc = " Create a deep copy of a comments object."]
+    #[doc = "\\return Deep copy of input."]
+    pub fn ope_comments_copy(comments: *mut OggOpusComments) -> *mut OggOpusComments;
+}
+extern "C" {
+    #[doc = " Destroys a comments object."]
+    pub fn ope_comments_destroy(comments: *mut OggOpusComments);
+}
+extern "C" {
+    #[doc = " Add a comment."]
+    #[doc = "\\return Error code"]
+    pub fn ope_comments_add(
+        comments: *mut OggOpusComments,
+        tag: *const crate::ctypes::c_char,
+        val: *const crate::ctypes::c_char,
+    ) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Add a comment as a single tag=value string."]
+    #[doc = "\\return Error code"]
+    pub fn ope_comments_add_string(
+        comments: *mut OggOpusComments,
+        tag_and_val: *const crate::ctypes::c_char,
+    ) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Add a picture from a file."]
+    #[doc = "\\return Error code"]
+    pub fn ope_comments_add_picture(
+        comments: *mut OggOpusComments,
+        filename: *const crate::ctypes::c_char,
+        picture_type: crate::ctypes::c_int,
+        description: *const crate::ctypes::c_char,
+    ) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Add a picture already in memory."]
+    #[doc = "\\return Error code"]
+    pub fn ope_comments_add_picture_from_memory(
+        comments: *mut OggOpusComments,
+        ptr: *const crate::ctypes::c_char,
+        size: usize,
+        picture_type: crate::ctypes::c_int,
+        description: *const crate::ctypes::c_char,
+    ) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Create a new OggOpus file."]
+    #[doc = "\\return Newly-created encoder."]
+    pub fn ope_encoder_create_file(
+        path: *const crate::ctypes::c_char,
+        comments: *mut OggOpusComments,
+        rate: opus_int32,
+        channels: crate::ctypes::c_int,
+        family: crate::ctypes::c_int,
+        error: *mut crate::ctypes::c_int,
+    ) -> *mut OggOpusEnc;
+}
+extern "C" {
+    #[doc = " Create a new OggOpus stream to be handled using callbacks"]
+    #[doc = "\\return Newly-created encoder."]
+    pub fn ope_encoder_create_callbacks(
+        callbacks: *const OpusEncCallbacks,
+        user_data: *mut crate::ctypes::c_void,
+        comments: *mut OggOpusComments,
+        rate: opus_int32,
+        channels: crate::ctypes::c_int,
+        family: crate::ctypes::c_int,
+        error: *mut crate::ctypes::c_int,
+    ) -> *mut OggOpusEnc;
+}
+extern "C" {
+    #[doc = " Create a new OggOpus stream to be used along with.ope_encoder_get_page()."]
+    #[doc = "This is mostly useful for muxing with other streams."]
+    #[doc = "\\return Newly-created encoder."]
+    pub fn ope_encoder_create_pull(
+        comments: *mut OggOpusComments,
+        rate: opus_int32,
+        channels: crate::ctypes::c_int,
+        family: crate::ctypes::c_int,
+        error: *mut crate::ctypes::c_int,
+    ) -> *mut OggOpusEnc;
+}
+extern "C" {
+    #[doc = " Add/encode any number of float samples to the stream."]
+    #[doc = "\\return Error code"]
+    pub fn ope_encoder_write_float(
+        enc: *mut OggOpusEnc,
+        pcm: *const f32,
+        samples_per_channel: crate::ctypes::c_int,
+    ) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Add/encode any number of 16-bit linear samples to the stream."]
+    #[doc = "\\return Error code"]
+    pub fn ope_encoder_write(
+        enc: *mut OggOpusEnc,
+        pcm: *const opus_int16,
+        samples_per_channel: crate::ctypes::c_int,
+    ) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Get the next page from the stream (only if using ope_encoder_create_pull())."]
+    #[doc = "\\return 1 if there is a page available, 0 if not."]
+    pub fn ope_encoder_get_page(
+        enc: *mut OggOpusEnc,
+        page: *mut *mut crate::ctypes::c_uchar,
+        len: *mut opus_int32,
+        flush: crate::ctypes::c_int,
+    ) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Finalizes the stream, but does not deallocate the object."]
+    #[doc = "\\return Error code"]
+    pub fn ope_encoder_drain(enc: *mut OggOpusEnc) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Deallocates the obect. Make sure to ope_drain() first."]
+    pub fn ope_encoder_destroy(enc: *mut OggOpusEnc);
+}
+extern "C" {
+    #[doc = " Ends the stream and create a new file."]
+    #[doc = "\\return Error code"]
+    pub fn ope_encoder_continue_new_file(
+        enc: *mut OggOpusEnc,
+        path: *const crate::ctypes::c_char,
+        comments: *mut OggOpusComments,
+    ) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Ends the stream and create a new stream within the same file."]
+    #[doc = "\\return Error code"]
+    pub fn ope_encoder_chain_current(
+        enc: *mut OggOpusEnc,
+        comments: *mut OggOpusComments,
+    ) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Write out the header now rather than wait for audio to begin."]
+    #[doc = "\\return Error code"]
+    pub fn ope_encoder_flush_header(enc: *mut OggOpusEnc) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Sets encoder options."]
+    #[doc = "\\return Error code"]
+    pub fn ope_encoder_ctl(
+        enc: *mut OggOpusEnc,
+        request: crate::ctypes::c_int,
+        ...
+    ) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Converts a libopusenc error code into a human readable string."]
+    #[doc = "\\return Error string"]
+    pub fn ope_strerror(error: crate::ctypes::c_int) -> *const crate::ctypes::c_char;
+}
+extern "C" {
+    #[doc = " Returns a string representing the version of libopusenc being used at run time."]
+    #[doc = "\\return A string describing the version of this library"]
+    pub fn ope_get_version_string() -> *const crate::ctypes::c_char;
+}
+extern "C" {
+    #[doc = " ABI version for this header. Can be used to check for features at run time."]
+    #[doc = "\\return An integer representing the ABI version"]
+    pub fn ope_get_abi_version() -> crate::ctypes::c_int;
+}
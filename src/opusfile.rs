@@ -0,0 +1,252 @@
+//! Bindings to `libopusfile`, decoding Ogg Opus streams, enabled via the
+//! `opusfile`-feature.
+
+use super::*;
+
+pub const OP_FALSE: crate::ctypes::c_int = -1;
+pub const OP_EOF: crate::ctypes::c_int = -2;
+pub const OP_HOLE: crate::ctypes::c_int = -3;
+pub const OP_EREAD: crate::ctypes::c_int = -128;
+pub const OP_EFAULT: crate::ctypes::c_int = -129;
+pub const OP_EIMPL: crate::ctypes::c_int = -130;
+pub const OP_EINVAL: crate::ctypes::c_int = -131;
+pub const OP_ENOTFORMAT: crate::ctypes::c_int = -132;
+pub const OP_EBADHEADER: crate::ctypes::c_int = -133;
+pub const OP_EVERSION: crate::ctypes::c_int = -134;
+pub const OP_ENOTAUDIO: crate::ctypes::c_int = -135;
+pub const OP_EBADPACKET: crate::ctypes::c_int = -136;
+pub const OP_EBADLINK: crate::ctypes::c_int = -137;
+pub const OP_ENOSEEK: crate::ctypes::c_int = -138;
+pub const OP_EBADTIMESTAMP: crate::ctypes::c_int = -139;
+pub const OPUS_CHANNEL_COUNT_MAX: u32 = 255;
+pub type opus_int64 = i64;
+pub type ogg_int64_t = i64;
+#[doc = " Ogg Opus bitstream information."]
+#[doc = " This contains the basic playback parameters for a stream, and corresponds to"]
+#[doc = "  the initial ID header packet of an Ogg Opus stream."]
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct OpusHead {
+    #[doc = "The Ogg Opus format version, in the range 0...255."]
+    pub version: crate::ctypes::c_int,
+    #[doc = "The number of channels, in the range 1...255."]
+    pub channel_count: crate::ctypes::c_int,
+    #[doc = "The number of samples that should be discarded from the beginning of the"]
+    #[doc = "stream."]
+    pub pre_skip: crate::ctypes::c_uint,
+    #[doc = "The sampling rate of the original input."]
+    pub input_sample_rate: opus_uint32,
+    #[doc = "The gain to apply to the decoded output, in dB, as a Q8 value in the range"]
+    #[doc = "-32768...32767."]
+    pub output_gain: crate::ctypes::c_int,
+    #[doc = "The channel mapping family, in the range 0...255."]
+    pub mapping_family: crate::ctypes::c_int,
+    #[doc = "The number of Opus streams in each Ogg packet, in the range 1...255."]
+    pub stream_count: crate::ctypes::c_int,
+    #[doc = "The number of coupled Opus streams in each Ogg packet, in the range"]
+    #[doc = "0...127."]
+    pub coupled_count: crate::ctypes::c_int,
+    #[doc = "The mapping from coded stream channels to output channels."]
+    pub mapping: [crate::ctypes::c_uchar; 255usize],
+}
+#[doc = " The metadata from an Ogg Opus stream."]
+#[doc = " This structure holds the in-stream metadata corresponding to the 'comment'"]
+#[doc = "  header packet of an Ogg Opus stream."]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct OpusTags {
+    #[doc = "The array of comment string vectors."]
+    pub user_comments: *mut *mut crate::ctypes::c_char,
+    #[doc = "An array of the corresponding length of each vector, in bytes."]
+    pub comment_lengths: *mut crate::ctypes::c_int,
+    #[doc = "The total number of comment streams."]
+    pub comments: crate::ctypes::c_int,
+    #[doc = "The null-terminated vendor string."]
+    #[doc = "This identifies the software used to encode the stream."]
+    pub vendor: *mut crate::ctypes::c_char,
+}
+#[repr(C)]
+#[derive(Debug)]
+pub struct OggOpusFile {
+    _data: [u8; 0],
+    _marker: ::core::marker::PhantomData<(*mut u8, ::core::marker::PhantomPinned)>,
+}
+extern "C" {
+    #[doc = " Test to see if this is an Opus stream."]
+    #[doc = " @param[out] _head      The parsed ID header contents."]
+    #[doc = " @param      _initial_data  An initial buffer of data from the start of the"]
+    #[doc = "                             stream."]
+    #[doc = " @param      _initial_bytes The number of bytes in \\p _initial_data."]
+    #[doc = " @return 0 if the data appears to be Opus, or a negative value on error."]
+    pub fn op_test(
+        _head: *mut OpusHead,
+        _initial_data: *const crate::ctypes::c_uchar,
+        _initial_bytes: usize,
+    ) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Open a stream from the given file path."]
+    #[doc = " @param      _path  The path to the file to open."]
+    #[doc = " @param[out] _error Returns 0 on success, or a failure code on error."]
+    #[doc = " @return A freshly opened \\c OggOpusFile, or <code>NULL</code> on error."]
+    pub fn op_open_file(
+        _path: *const crate::ctypes::c_char,
+        _error: *mut crate::ctypes::c_int,
+    ) -> *mut OggOpusFile;
+}
+extern "C" {
+    #[doc = " Open a stream from a memory buffer."]
+    #[doc = " @param      _data  The memory buffer to open."]
+    #[doc = " @param      _size  The number of bytes in the buffer."]
+    #[doc = " @param[out] _error Returns 0 on success, or a failure code on error."]
+    #[doc = " @return A freshly opened \\c OggOpusFile, or <code>NULL</code> on error."]
+    pub fn op_open_memory(
+        _data: *const crate::ctypes::c_uchar,
+        _size: usize,
+        _error: *mut crate::ctypes::c_int,
+    ) -> *mut OggOpusFile;
+}
+extern "C" {
+    #[doc = " Release all memory used by an \\c OggOpusFile."]
+    pub fn op_free(_of: *mut OggOpusFile);
+}
+extern "C" {
+    #[doc = " Returns whether or not the stream being read is seekable."]
+    pub fn op_seekable(_of: *const OggOpusFile) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Returns the number of links in this chained stream."]
+    pub fn op_link_count(_of: *const OggOpusFile) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Get the serial number of the given link in a (possibly-chained) Ogg Opus"]
+    #[doc = "  stream."]
+    pub fn op_serialno(_of: *const OggOpusFile, _li: crate::ctypes::c_int) -> opus_uint32;
+}
+extern "C" {
+    #[doc = " Get the channel count of the given link in a (possibly-chained) Ogg Opus"]
+    #[doc = "  stream."]
+    pub fn op_channel_count(
+        _of: *const OggOpusFile,
+        _li: crate::ctypes::c_int,
+    ) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Get the total (compressed) size of the stream, or of an individual link in"]
+    #[doc = "  a (possibly-chained) Ogg Opus stream, including all headers and Ogg muxing"]
+    #[doc = "  overhead."]
+    pub fn op_raw_total(_of: *const OggOpusFile, _li: crate::ctypes::c_int) -> opus_int64;
+}
+extern "C" {
+    #[doc = " Get the total PCM length (number of samples at 48 kHz) of the stream, or of"]
+    #[doc = "  an individual link in a (possibly-chained) Ogg Opus stream."]
+    pub fn op_pcm_total(_of: *const OggOpusFile, _li: crate::ctypes::c_int) -> ogg_int64_t;
+}
+extern "C" {
+    #[doc = " Get the ID header information for the given link in a (possibly chained) Ogg"]
+    #[doc = "  Opus stream."]
+    pub fn op_head(_of: *const OggOpusFile, _li: crate::ctypes::c_int) -> *const OpusHead;
+}
+extern "C" {
+    #[doc = " Get the comment header information for the given link in a (possibly"]
+    #[doc = "  chained) Ogg Opus stream."]
+    pub fn op_tags(_of: *const OggOpusFile, _li: crate::ctypes::c_int) -> *const OpusTags;
+}
+extern "C" {
+    #[doc = " Look up a comment value by its tag."]
+    #[doc = " @param _tags  An initialized #OpusTags structure."]
+    #[doc = " @param _tag   The tag to look up."]
+    #[doc = " @param _count The instance of the tag."]
+    #[doc = " @return A pointer to the queried tag's value, or <code>NULL</code> if it"]
+    #[doc = "          does not exist."]
+    pub fn opus_tags_query(
+        _tags: *const OpusTags,
+        _tag: *const crate::ctypes::c_char,
+        _count: crate::ctypes::c_int,
+    ) -> *const crate::ctypes::c_char;
+}
+extern "C" {
+    #[doc = " Look up the number of instances of a tag."]
+    #[doc = " @return The number of instances of this particular tag."]
+    pub fn opus_tags_query_count(
+        _tags: *const OpusTags,
+        _tag: *const crate::ctypes::c_char,
+    ) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Get the track gain from an R128_TRACK_GAIN tag, if one was specified."]
+    #[doc = " @param[out] _gain_q8 The track gain, in 1/256ths of a dB."]
+    #[doc = " @return 0 on success, or #OP_FALSE if there was no track gain available."]
+    pub fn opus_tags_get_track_gain(
+        _tags: *const OpusTags,
+        _gain_q8: *mut crate::ctypes::c_int,
+    ) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Compute the average bitrate for a given link in a (possibly chained) Ogg Opus"]
+    #[doc = "  stream."]
+    pub fn op_bitrate(_of: *const OggOpusFile, _li: crate::ctypes::c_int) -> opus_int32;
+}
+extern "C" {
+    #[doc = " Retrieve the index of the current link."]
+    pub fn op_current_link(_of: *const OggOpusFile) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Obtain the current value of the position indicator for \\a _of."]
+    pub fn op_raw_tell(_of: *const OggOpusFile) -> opus_int64;
+}
+extern "C" {
+    #[doc = " Obtain the PCM offset of the next sample to be read."]
+    pub fn op_pcm_tell(_of: *const OggOpusFile) -> ogg_int64_t;
+}
+extern "C" {
+    #[doc = " Seek to a byte offset relative to the <b>compressed</b> data."]
+    pub fn op_raw_seek(_of: *mut OggOpusFile, _byte_offset: opus_int64) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Seek to the specified PCM offset, such that decoding will begin at exactly"]
+    #[doc = "  the requested position."]
+    pub fn op_pcm_seek(_of: *mut OggOpusFile, _pcm_offset: ogg_int64_t) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Reads more samples from the stream."]
+    #[doc = " @return The number of samples read per channel on success, or a negative"]
+    #[doc = "          value on failure."]
+    pub fn op_read(
+        _of: *mut OggOpusFile,
+        _pcm: *mut opus_int16,
+        _buf_size: crate::ctypes::c_int,
+        _li: *mut crate::ctypes::c_int,
+    ) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Reads more samples from the stream."]
+    #[doc = " @return The number of samples read per channel on success, or a negative"]
+    #[doc = "          value on failure."]
+    pub fn op_read_float(
+        _of: *mut OggOpusFile,
+        _pcm: *mut f32,
+        _buf_size: crate::ctypes::c_int,
+        _li: *mut crate::ctypes::c_int,
+    ) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Reads more samples from the stream and downmixes to stereo, if necessary."]
+    #[doc = " @return The number of samples read per channel on success, or a negative"]
+    #[doc = "          value on failure."]
+    pub fn op_read_stereo(
+        _of: *mut OggOpusFile,
+        _pcm: *mut opus_int16,
+        _buf_size: crate::ctypes::c_int,
+    ) -> crate::ctypes::c_int;
+}
+extern "C" {
+    #[doc = " Reads more samples from the stream and downmixes to stereo, if necessary."]
+    #[doc = " @return The number of samples read per channel on success, or a negative"]
+    #[doc = "          value on failure."]
+    pub fn op_read_float_stereo(
+        _of: *mut OggOpusFile,
+        _pcm: *mut f32,
+        _buf_size: crate::ctypes::c_int,
+    ) -> crate::ctypes::c_int;
+}
@@ -0,0 +1,455 @@
+//! Bindings to the ambisonics projection API of Opus 1.3, enabled via the
+//! `projection`-feature.
+//!
+//! The projection encoder mixes ambisonic channels into multistream Opus,
+//! whose decoder demixes them again via the matrix the encoder reports.
+
+use super::*;
+
+pub const OPUS_PROJECTION_GET_DEMIXING_MATRIX_GAIN_REQUEST: crate::ctypes::c_int = 6001;
+pub const OPUS_PROJECTION_GET_DEMIXING_MATRIX_SIZE_REQUEST: crate::ctypes::c_int = 6003;
+pub const OPUS_PROJECTION_GET_DEMIXING_MATRIX_REQUEST: crate::ctypes::c_int = 6005;
+#[repr(C)]
+#[derive(Debug)]
+pub struct OpusProjectionEncoder {
+    _data: [u8; 0],
+    _marker: ::core::marker::PhantomData<(*mut u8, ::core::marker::PhantomPinned)>,
+}
+#[repr(C)]
+#[derive(Debug)]
+pub struct OpusProjectionDecoder {
+    _data: [u8; 0],
+    _marker: ::core::marker::PhantomData<(*mut u8, ::core::marker::PhantomPinned)>,
+}
+opus_extern! {
+    #[doc = " Gets the size of an OpusProjectionEncoder structure."]
+    #[doc = " @param channels <tt>int</tt>: The total number of input channels to encode."]
+    #[doc = "                               This must be no more than 255."]
+    #[doc = " @param mapping_family <tt>int</tt>: The mapping family to use for selecting"]
+    #[doc = "                                     the appropriate projection."]
+    #[doc = " @returns The size in bytes on success, or a negative error code"]
+    #[doc = "          (see @ref opus_errorcodes) on error."]
+    #[cfg(feature = "encoder")]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "audiopus_opus_projection_ambisonics_encoder_get_size"
+    )]
+    pub fn opus_projection_ambisonics_encoder_get_size(
+        channels: crate::ctypes::c_int,
+        mapping_family: crate::ctypes::c_int,
+    ) -> opus_int32;
+}
+opus_extern! {
+    #[doc = " Allocates and initializes a projection encoder state."]
+    #[doc = " Call opus_projection_encoder_destroy() to release"]
+    #[doc = " this object when finished."]
+    #[doc = " @param Fs <tt>opus_int32</tt>: Sampling rate of the input signal (in Hz)."]
+    #[doc = " @param channels <tt>int</tt>: Number of channels in the input signal."]
+    #[doc = " @param mapping_family <tt>int</tt>: The mapping family to use for selecting"]
+    #[doc = "                                     the appropriate projection."]
+    #[doc = " @param[out] streams <tt>int *</tt>: The total number of streams that will"]
+    #[doc = "                                     be encoded from the input."]
+    #[doc = " @param[out] coupled_streams <tt>int *</tt>: Number of coupled (2 channel)"]
+    #[doc = "                                 streams that will be encoded from the input."]
+    #[doc = " @param application <tt>int</tt>: The target encoder application."]
+    #[doc = " @param[out] error <tt>int *</tt>: Returns #OPUS_OK on success, or an error"]
+    #[doc = "                                   code (see @ref opus_errorcodes) on"]
+    #[doc = "                                   failure."]
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    #[cfg(feature = "encoder")]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "audiopus_opus_projection_ambisonics_encoder_create"
+    )]
+    pub fn opus_projection_ambisonics_encoder_create(
+        Fs: opus_int32,
+        channels: crate::ctypes::c_int,
+        mapping_family: crate::ctypes::c_int,
+        streams: *mut crate::ctypes::c_int,
+        coupled_streams: *mut crate::ctypes::c_int,
+        application: crate::ctypes::c_int,
+        error: *mut crate::ctypes::c_int,
+    ) -> *mut OpusProjectionEncoder;
+}
+opus_extern! {
+    #[doc = " Initialize a previously allocated projection encoder state."]
+    #[doc = " The memory pointed to by \\a st must be at least the size returned by"]
+    #[doc = " opus_projection_ambisonics_encoder_get_size()."]
+    #[doc = " @see opus_projection_ambisonics_encoder_create"]
+    #[doc = " @see opus_projection_ambisonics_encoder_get_size"]
+    #[doc = " @returns #OPUS_OK on success, or an error code (see @ref opus_errorcodes)"]
+    #[doc = "          on failure."]
+    #[cfg(feature = "encoder")]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "audiopus_opus_projection_ambisonics_encoder_init"
+    )]
+    pub fn opus_projection_ambisonics_encoder_init(
+        st: *mut OpusProjectionEncoder,
+        Fs: opus_int32,
+        channels: crate::ctypes::c_int,
+        mapping_family: crate::ctypes::c_int,
+        streams: *mut crate::ctypes::c_int,
+        coupled_streams: *mut crate::ctypes::c_int,
+        application: crate::ctypes::c_int,
+    ) -> crate::ctypes::c_int;
+}
+opus_extern! {
+    #[doc = " Encodes a projection Opus frame."]
+    #[doc = " @param st <tt>OpusProjectionEncoder*</tt>: Projection encoder state."]
+    #[doc = " @param[in] pcm <tt>const opus_int16*</tt>: The input signal as interleaved"]
+    #[doc = "                                            samples."]
+    #[doc = " @param frame_size <tt>int</tt>: Number of samples per channel in the input"]
+    #[doc = "                                 signal."]
+    #[doc = " @param[out] data <tt>unsigned char*</tt>: Output payload."]
+    #[doc = " @param max_data_bytes <tt>opus_int32</tt>: Size of the allocated"]
+    #[doc = "                                            memory for the output"]
+    #[doc = "                                            payload."]
+    #[doc = " @returns The length of the encoded packet (in bytes) on success or a"]
+    #[doc = "          negative error code (see @ref opus_errorcodes) on failure."]
+    #[cfg(feature = "encoder")]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "audiopus_opus_projection_encode"
+    )]
+    pub fn opus_projection_encode(
+        st: *mut OpusProjectionEncoder,
+        pcm: *const opus_int16,
+        frame_size: crate::ctypes::c_int,
+        data: *mut crate::ctypes::c_uchar,
+        max_data_bytes: opus_int32,
+    ) -> crate::ctypes::c_int;
+}
+opus_extern! {
+    #[doc = " Encodes a projection Opus frame from floating point input."]
+    #[doc = " @param st <tt>OpusProjectionEncoder*</tt>: Projection encoder state."]
+    #[doc = " @param[in] pcm <tt>const float*</tt>: The input signal as interleaved"]
+    #[doc = "                                       samples with a normal range of"]
+    #[doc = "                                       +/-1.0."]
+    #[doc = " @param frame_size <tt>int</tt>: Number of samples per channel in the input"]
+    #[doc = "                                 signal."]
+    #[doc = " @param[out] data <tt>unsigned char*</tt>: Output payload."]
+    #[doc = " @param max_data_bytes <tt>opus_int32</tt>: Size of the allocated"]
+    #[doc = "                                            memory for the output"]
+    #[doc = "                                            payload."]
+    #[doc = " @returns The length of the encoded packet (in bytes) on success or a"]
+    #[doc = "          negative error code (see @ref opus_errorcodes) on failure."]
+    #[cfg(not(feature = "disable-float-api"))]
+    #[cfg(feature = "encoder")]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "audiopus_opus_projection_encode_float"
+    )]
+    pub fn opus_projection_encode_float(
+        st: *mut OpusProjectionEncoder,
+        pcm: *const f32,
+        frame_size: crate::ctypes::c_int,
+        data: *mut crate::ctypes::c_uchar,
+        max_data_bytes: opus_int32,
+    ) -> crate::ctypes::c_int;
+}
+opus_extern! {
+    #[doc = " Frees an <code>OpusProjectionEncoder</code> allocated by"]
+    #[doc = " opus_projection_ambisonics_encoder_create()."]
+    #[doc = " @param st <tt>OpusProjectionEncoder*</tt>: Projection encoder state to be freed."]
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    #[cfg(feature = "encoder")]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "audiopus_opus_projection_encoder_destroy"
+    )]
+    pub fn opus_projection_encoder_destroy(st: *mut OpusProjectionEncoder);
+}
+opus_extern! {
+    #[doc = " Perform a CTL function on a projection Opus encoder."]
+    #[doc = ""]
+    #[doc = " Generally the request and subsequent arguments are generated by a"]
+    #[doc = " convenience macro."]
+    #[doc = " @param st <tt>OpusProjectionEncoder*</tt>: Projection encoder state."]
+    #[doc = " @param request This and all remaining parameters should be replaced by one"]
+    #[doc = "                of the convenience macros in @ref opus_genericctls,"]
+    #[doc = "                @ref opus_encoderctls, @ref opus_multistream_ctls, or"]
+    #[doc = "                @ref opus_projection_ctls"]
+    #[cfg(feature = "encoder")]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "audiopus_opus_projection_encoder_ctl"
+    )]
+    pub fn opus_projection_encoder_ctl(
+        st: *mut OpusProjectionEncoder,
+        request: crate::ctypes::c_int,
+        ...
+    ) -> crate::ctypes::c_int;
+}
+opus_extern! {
+    #[doc = " Gets the size of an <code>OpusProjectionDecoder</code> structure."]
+    #[doc = " @param channels <tt>int</tt>: The total number of output channels."]
+    #[doc = "                               This must be no more than 255."]
+    #[doc = " @param streams <tt>int</tt>: The total number of streams coded in the"]
+    #[doc = "                              input."]
+    #[doc = "                              This must be no more than 255."]
+    #[doc = " @param coupled_streams <tt>int</tt>: Number streams to decode as coupled"]
+    #[doc = "                                      (2 channel) streams."]
+    #[doc = "                                      This must be no larger than the total"]
+    #[doc = "                                      number of streams."]
+    #[doc = " @returns The size in bytes on success, or a negative error code"]
+    #[doc = "          (see @ref opus_errorcodes) on error."]
+    #[cfg(feature = "decoder")]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "audiopus_opus_projection_decoder_get_size"
+    )]
+    pub fn opus_projection_decoder_get_size(
+        channels: crate::ctypes::c_int,
+        streams: crate::ctypes::c_int,
+        coupled_streams: crate::ctypes::c_int,
+    ) -> opus_int32;
+}
+opus_extern! {
+    #[doc = " Allocates and initializes a projection decoder state."]
+    #[doc = " Call opus_projection_decoder_destroy() to release"]
+    #[doc = " this object when finished."]
+    #[doc = " @param Fs <tt>opus_int32</tt>: Sampling rate to decode at (in Hz)."]
+    #[doc = " @param channels <tt>int</tt>: Number of channels to output."]
+    #[doc = " @param streams <tt>int</tt>: The total number of streams coded in the"]
+    #[doc = "                              input."]
+    #[doc = " @param coupled_streams <tt>int</tt>: Number of streams to decode as coupled"]
+    #[doc = "                                      (2 channel) streams."]
+    #[doc = " @param[in] demixing_matrix <tt>const unsigned char[demixing_matrix_size]</tt>: Demixing matrix"]
+    #[doc = "                         that mapping from coded channels to output channels,"]
+    #[doc = "                         as described in @ref opus_projection and"]
+    #[doc = "                         @ref opus_projection_ctls."]
+    #[doc = " @param demixing_matrix_size <tt>opus_int32</tt>: The size in bytes of the"]
+    #[doc = "                                                  demixing matrix, as"]
+    #[doc = "                                                  described in @ref"]
+    #[doc = "                                                  opus_projection_ctls."]
+    #[doc = " @param[out] error <tt>int *</tt>: Returns #OPUS_OK on success, or an error"]
+    #[doc = "                                   code (see @ref opus_errorcodes) on"]
+    #[doc = "                                   failure."]
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    #[cfg(feature = "decoder")]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "audiopus_opus_projection_decoder_create"
+    )]
+    pub fn opus_projection_decoder_create(
+        Fs: opus_int32,
+        channels: crate::ctypes::c_int,
+        streams: crate::ctypes::c_int,
+        coupled_streams: crate::ctypes::c_int,
+        demixing_matrix: *mut crate::ctypes::c_uchar,
+        demixing_matrix_size: opus_int32,
+        error: *mut crate::ctypes::c_int,
+    ) -> *mut OpusProjectionDecoder;
+}
+opus_extern! {
+    #[doc = " Intialize a previously allocated projection decoder state object."]
+    #[doc = " The memory pointed to by \\a st must be at least the size returned by"]
+    #[doc = " opus_projection_decoder_get_size()."]
+    #[doc = " @see opus_projection_decoder_create"]
+    #[doc = " @see opus_projection_deocder_get_size"]
+    #[doc = " @returns #OPUS_OK on success, or an error code (see @ref opus_errorcodes)"]
+    #[doc = "          on failure."]
+    #[cfg(feature = "decoder")]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "audiopus_opus_projection_decoder_init"
+    )]
+    pub fn opus_projection_decoder_init(
+        st: *mut OpusProjectionDecoder,
+        Fs: opus_int32,
+        channels: crate::ctypes::c_int,
+        streams: crate::ctypes::c_int,
+        coupled_streams: crate::ctypes::c_int,
+        demixing_matrix: *mut crate::ctypes::c_uchar,
+        demixing_matrix_size: opus_int32,
+    ) -> crate::ctypes::c_int;
+}
+opus_extern! {
+    #[doc = " Decode a projection Opus packet."]
+    #[doc = " @param st <tt>OpusProjectionDecoder*</tt>: Projection decoder state."]
+    #[doc = " @param[in] data <tt>const unsigned char*</tt>: Input payload."]
+    #[doc = "                                                Use a <code>NULL</code>"]
+    #[doc = "                                                pointer to indicate packet"]
+    #[doc = "                                                loss."]
+    #[doc = " @param len <tt>opus_int32</tt>: Number of bytes in payload."]
+    #[doc = " @param[out] pcm <tt>opus_int16*</tt>: Output signal, with interleaved"]
+    #[doc = "                                       samples."]
+    #[doc = " @param frame_size <tt>int</tt>: The number of samples per channel of"]
+    #[doc = "                                 available space in \\a pcm."]
+    #[doc = " @param decode_fec <tt>int</tt>: Flag (0 or 1) to request that any in-band"]
+    #[doc = "                                 forward error correction data be decoded."]
+    #[doc = " @returns Number of samples decoded on success or a negative error code"]
+    #[doc = "          (see @ref opus_errorcodes) on failure."]
+    #[cfg(feature = "decoder")]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "audiopus_opus_projection_decode"
+    )]
+    pub fn opus_projection_decode(
+        st: *mut OpusProjectionDecoder,
+        data: *const crate::ctypes::c_uchar,
+        len: opus_int32,
+        pcm: *mut opus_int16,
+        frame_size: crate::ctypes::c_int,
+        decode_fec: crate::ctypes::c_int,
+    ) -> crate::ctypes::c_int;
+}
+opus_extern! {
+    #[doc = " Decode a projection Opus packet with floating point output."]
+    #[doc = " @param st <tt>OpusProjectionDecoder*</tt>: Projection decoder state."]
+    #[doc = " @param[in] data <tt>const unsigned char*</tt>: Input payload."]
+    #[doc = "                                                Use a <code>NULL</code>"]
+    #[doc = "                                                pointer to indicate packet"]
+    #[doc = "                                                loss."]
+    #[doc = " @param len <tt>opus_int32</tt>: Number of bytes in payload."]
+    #[doc = " @param[out] pcm <tt>float*</tt>: Output signal, with interleaved"]
+    #[doc = "                                  samples."]
+    #[doc = " @param frame_size <tt>int</tt>: The number of samples per channel of"]
+    #[doc = "                                 available space in \\a pcm."]
+    #[doc = " @param decode_fec <tt>int</tt>: Flag (0 or 1) to request that any in-band"]
+    #[doc = "                                 forward error correction data be decoded."]
+    #[doc = " @returns Number of samples decoded on success or a negative error code"]
+    #[doc = "          (see @ref opus_errorcodes) on failure."]
+    #[cfg(not(feature = "disable-float-api"))]
+    #[cfg(feature = "decoder")]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "audiopus_opus_projection_decode_float"
+    )]
+    pub fn opus_projection_decode_float(
+        st: *mut OpusProjectionDecoder,
+        data: *const crate::ctypes::c_uchar,
+        len: opus_int32,
+        pcm: *mut f32,
+        frame_size: crate::ctypes::c_int,
+        decode_fec: crate::ctypes::c_int,
+    ) -> crate::ctypes::c_int;
+}
+opus_extern! {
+    #[doc = " Perform a CTL function on a projection Opus decoder."]
+    #[doc = ""]
+    #[doc = " Generally the request and subsequent arguments are generated by a"]
+    #[doc = " convenience macro."]
+    #[doc = " @param st <tt>OpusProjectionDecoder*</tt>: Projection decoder state."]
+    #[doc = " @param request This and all remaining parameters should be replaced by one"]
+    #[doc = "                of the convenience macros in @ref opus_genericctls,"]
+    #[doc = "                @ref opus_decoderctls, @ref opus_multistream_ctls, or"]
+    #[doc = "                @ref opus_projection_ctls."]
+    #[cfg(feature = "decoder")]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "audiopus_opus_projection_decoder_ctl"
+    )]
+    pub fn opus_projection_decoder_ctl(
+        st: *mut OpusProjectionDecoder,
+        request: crate::ctypes::c_int,
+        ...
+    ) -> crate::ctypes::c_int;
+}
+opus_extern! {
+    #[doc = " Frees an <code>OpusProjectionDecoder</code> allocated by"]
+    #[doc = " opus_projection_decoder_create()."]
+    #[doc = " @param st <tt>OpusProjectionDecoder</tt>: Projection decoder state to be freed."]
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    #[cfg(feature = "decoder")]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "audiopus_opus_projection_decoder_destroy"
+    )]
+    pub fn opus_projection_decoder_destroy(st: *mut OpusProjectionDecoder);
+}
+
+#[cfg(all(
+    test,
+    feature = "encoder",
+    feature = "decoder",
+    not(feature = "dlopen")
+))]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: opus_int32 = 48000;
+    // First-order ambisonics.
+    const CHANNELS: usize = 4;
+    const FRAME_SIZE: usize = 960;
+    const MAX_PACKET_SIZE: usize = 4000;
+
+    #[test]
+    fn round_trip_ambisonics() {
+        let input = vec![0 as opus_int16; FRAME_SIZE * CHANNELS];
+        let mut packet = [0; MAX_PACKET_SIZE];
+        let mut output = vec![0 as opus_int16; FRAME_SIZE * CHANNELS];
+        let mut streams = 0;
+        let mut coupled_streams = 0;
+        let mut matrix_size: opus_int32 = 0;
+        let mut error = 0;
+
+        unsafe {
+            let encoder = opus_projection_ambisonics_encoder_create(
+                SAMPLE_RATE,
+                CHANNELS as i32,
+                OPUS_MAPPING_FAMILY_AMBISONICS_PROJECTION,
+                &mut streams,
+                &mut coupled_streams,
+                OPUS_APPLICATION_AUDIO,
+                &mut error,
+            );
+            assert_eq!(error, OPUS_OK);
+
+            assert_eq!(
+                opus_projection_encoder_ctl(
+                    encoder,
+                    OPUS_PROJECTION_GET_DEMIXING_MATRIX_SIZE_REQUEST,
+                    &mut matrix_size as *mut opus_int32,
+                ),
+                OPUS_OK
+            );
+            assert!(matrix_size > 0);
+
+            let mut matrix = vec![0; matrix_size as usize];
+            assert_eq!(
+                opus_projection_encoder_ctl(
+                    encoder,
+                    OPUS_PROJECTION_GET_DEMIXING_MATRIX_REQUEST,
+                    matrix.as_mut_ptr(),
+                    matrix_size,
+                ),
+                OPUS_OK
+            );
+
+            let decoder = opus_projection_decoder_create(
+                SAMPLE_RATE,
+                CHANNELS as i32,
+                streams,
+                coupled_streams,
+                matrix.as_mut_ptr(),
+                matrix_size,
+                &mut error,
+            );
+            assert_eq!(error, OPUS_OK);
+
+            let len = opus_projection_encode(
+                encoder,
+                input.as_ptr(),
+                FRAME_SIZE as i32,
+                packet.as_mut_ptr(),
+                MAX_PACKET_SIZE as opus_int32,
+            );
+            assert!(len > 0);
+
+            let decoded = opus_projection_decode(
+                decoder,
+                packet.as_ptr(),
+                len,
+                output.as_mut_ptr(),
+                FRAME_SIZE as i32,
+                0,
+            );
+            assert_eq!(decoded, FRAME_SIZE as i32);
+
+            opus_projection_encoder_destroy(encoder);
+            opus_projection_decoder_destroy(decoder);
+        }
+    }
+}
@@ -0,0 +1,304 @@
+//! Verifies the bindings against the headers of the linked Opus, which the
+//! build script passes as `DEP_OPUS_INCLUDE`, similar to `ctest2`:
+//! the sizes of the fixed-width types and the signature of every bound
+//! function are compiled as C, failing on any mismatch.
+//! The constants are already checked by the build script's probe.
+#![cfg(not(feature = "dlopen"))]
+
+use std::{env, fs, path::Path, process::Command};
+
+/// Returns whether `feature` of the tested build is enabled, covering the
+/// features gating functions of the bindings.
+fn is_feature_enabled(feature: &str) -> bool {
+    match feature {
+        "custom" => cfg!(feature = "custom"),
+        "decoder" => cfg!(feature = "decoder"),
+        "deep-plc" => cfg!(feature = "deep-plc"),
+        "disable-float-api" => cfg!(feature = "disable-float-api"),
+        "dred" => cfg!(feature = "dred"),
+        "encoder" => cfg!(feature = "encoder"),
+        "osce" => cfg!(feature = "osce"),
+        "projection" => cfg!(feature = "projection"),
+        "v1_4" => cfg!(feature = "v1_4"),
+        "v1_5" => cfg!(feature = "v1_5"),
+        _ => false,
+    }
+}
+
+/// Splits the comma-separated predicates of `any(...)` or `all(...)`.
+fn split_predicates(predicates: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (index, character) in predicates.char_indices() {
+        match character {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(predicates[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    parts.push(predicates[start..].trim());
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+/// Evaluates a `cfg`-predicate, `None` if it depends on the target rather
+/// than features, e.g. `target_arch = "wasm32"`, as the headers declare such
+/// functions regardless.
+fn evaluate_cfg(predicate: &str) -> Option<bool> {
+    let predicate = predicate.trim();
+    let inner = |prefix: &str| {
+        if predicate.starts_with(prefix) && predicate.ends_with(')') {
+            Some(&predicate[prefix.len()..predicate.len() - 1])
+        } else {
+            None
+        }
+    };
+
+    if predicate.starts_with("feature") {
+        predicate.split('"').nth(1).map(is_feature_enabled)
+    } else if let Some(predicate) = inner("not(") {
+        evaluate_cfg(predicate).map(|is_enabled| !is_enabled)
+    } else if let Some(predicates) = inner("any(") {
+        let results: Vec<_> = split_predicates(predicates)
+            .into_iter()
+            .map(evaluate_cfg)
+            .collect();
+
+        if results.contains(&Some(true)) {
+            Some(true)
+        } else if results.iter().all(|result| *result == Some(false)) {
+            Some(false)
+        } else {
+            None
+        }
+    } else if let Some(predicates) = inner("all(") {
+        let results: Vec<_> = split_predicates(predicates)
+            .into_iter()
+            .map(evaluate_cfg)
+            .collect();
+
+        if results.contains(&Some(false)) {
+            Some(false)
+        } else if results.iter().all(|result| *result == Some(true)) {
+            Some(true)
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+}
+
+/// Translates a Rust type of the bindings into C, e.g.
+/// `*const crate::ctypes::c_uchar` into `unsigned char const *`.
+fn c_type(rust_type: &str) -> String {
+    let rust_type = rust_type.trim();
+
+    if rust_type.starts_with("*const ") {
+        return format!("{} const *", c_type(&rust_type["*const ".len()..]));
+    }
+
+    if rust_type.starts_with("*mut ") {
+        return format!("{} *", c_type(&rust_type["*mut ".len()..]));
+    }
+
+    match rust_type.rsplit("::").next().unwrap_or(rust_type) {
+        "c_char" => "char",
+        "c_uchar" => "unsigned char",
+        "c_int" => "int",
+        "c_uint" => "unsigned int",
+        "c_void" => "void",
+        "f32" => "float",
+        name => name,
+    }
+    .to_string()
+}
+
+/// Generates a C function-pointer type checking the signature `declaration`
+/// of a `pub fn`, the initialisation fails to compile unless the signature
+/// matches the header.
+fn check_signature(declaration: &str) -> String {
+    let name = declaration["pub fn ".len()..]
+        .split('(')
+        .next()
+        .unwrap_or_default()
+        .trim();
+    let parameters_start = declaration.find('(').expect("Function lacks parameters.");
+    let parameters_end = declaration.rfind(')').expect("Function lacks parameters.");
+    let return_type = declaration[parameters_end + 1..]
+        .trim()
+        .trim_end_matches(';')
+        .trim();
+    let return_type = if return_type.starts_with("->") {
+        c_type(&return_type["->".len()..])
+    } else {
+        String::from("void")
+    };
+
+    let parameters: Vec<_> = split_predicates(&declaration[parameters_start + 1..parameters_end])
+        .into_iter()
+        .map(|parameter| match parameter.find(": ") {
+            Some(index) => c_type(&parameter[index + 2..]),
+            None => parameter.to_string(),
+        })
+        .collect();
+    let parameters = if parameters.is_empty() {
+        String::from("void")
+    } else {
+        parameters.join(", ")
+    };
+
+    format!(
+        "static {} (*check_{})({}) = {};\n",
+        return_type, name, parameters, name
+    )
+}
+
+/// Appends the checks of every `pub fn` and fixed-width type of `bindings`
+/// whose features are enabled to `source`.
+fn check_bindings(bindings: &str, source: &mut String) {
+    let mut is_enabled = true;
+    let mut declaration: Option<String> = None;
+
+    for line in bindings.lines() {
+        let line = line.trim();
+
+        if let Some(mut pending) = declaration.take() {
+            pending.push(' ');
+            pending.push_str(line);
+
+            if line.ends_with(';') {
+                if is_enabled {
+                    source.push_str(&check_signature(&pending));
+                }
+
+                is_enabled = true;
+            } else {
+                declaration = Some(pending);
+            }
+        } else if line.starts_with("#[cfg(") && line.ends_with(")]") {
+            is_enabled &= evaluate_cfg(&line["#[cfg(".len()..line.len() - 2]).unwrap_or(true);
+        } else if line.starts_with("pub fn ") {
+            if line.ends_with(';') {
+                if is_enabled {
+                    source.push_str(&check_signature(line));
+                }
+
+                is_enabled = true;
+            } else {
+                declaration = Some(line.to_string());
+            }
+        } else if line.starts_with("pub type opus_") {
+            let name = line["pub type ".len()..]
+                .split(' ')
+                .next()
+                .unwrap_or_default();
+            let rust_type = line
+                .rsplit('=')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .trim_end_matches(';');
+            let bits: usize = rust_type[1..].parse().expect("Unknown fixed-width type.");
+
+            source.push_str(&format!(
+                "typedef char check_size_{0}[sizeof({0}) == {1} ? 1 : -1];\n",
+                name,
+                bits / 8
+            ));
+            is_enabled = true;
+        } else if line.starts_with("pub ") || line == "}" {
+            is_enabled = true;
+        }
+    }
+}
+
+#[test]
+fn bindings_match_headers() {
+    let crate_directory = Path::new(env!("CARGO_MANIFEST_DIR"));
+    // Absent on docs.rs, which builds no Opus, hence not `env!`.
+    let header_directory = match option_env!("DEP_OPUS_INCLUDE") {
+        Some(header_directory) => Path::new(header_directory),
+        None => panic!("The build script found no Opus headers to check the bindings against."),
+    };
+
+    assert!(
+        header_directory.join("opus.h").is_file(),
+        "{} lacks `opus.h`.",
+        header_directory.display()
+    );
+
+    let mut source = String::from("#include <opus.h>\n#include <opus_multistream.h>\n");
+    let mut files = vec!["bindings.rs"];
+
+    if cfg!(feature = "custom") {
+        source.push_str("#include <opus_custom.h>\n");
+        files.push("custom.rs");
+    }
+
+    if cfg!(feature = "dred") {
+        files.push("dred.rs");
+    }
+
+    if cfg!(feature = "projection") {
+        source.push_str("#include <opus_projection.h>\n");
+        files.push("projection.rs");
+    }
+
+    source.push('\n');
+
+    for file in files {
+        let bindings = fs::read_to_string(crate_directory.join("src").join(file))
+            .expect("Could not read the bindings.");
+
+        check_bindings(&bindings, &mut source);
+    }
+
+    let check_directory = Path::new(env!("OUT_DIR")).join("abi_check");
+    fs::create_dir_all(&check_directory).expect("Could not create the check directory.");
+
+    let source_path = check_directory.join("opus_abi.c");
+    fs::write(&source_path, &source).expect("Could not write the check.");
+
+    let is_msvc = cfg!(target_env = "msvc");
+    let compiler =
+        env::var("CC").unwrap_or_else(|_| String::from(if is_msvc { "cl" } else { "cc" }));
+    let mut command = Command::new(compiler);
+
+    if is_msvc {
+        // Differing parameter lists, levels of indirection, and pointees.
+        command
+            .arg("/nologo")
+            .arg("/Zs")
+            .arg("/we4113")
+            .arg("/we4047")
+            .arg("/we4133")
+            .arg("/I");
+    } else {
+        command
+            .arg("-fsyntax-only")
+            .arg("-Werror=incompatible-pointer-types")
+            .arg("-I");
+    }
+
+    let output = command
+        .arg(header_directory)
+        .arg(&source_path)
+        .output()
+        .unwrap_or_else(|error| panic!("Could not run the C compiler: {}", error));
+
+    assert!(
+        output.status.success(),
+        "The bindings differ from the Opus headers, the `check_`-prefixed names reveal \
+         which ones:\n{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,6 @@
+//! Runs the unit tests of the build script's helpers depending on nothing but
+//! `std`.
+
+#[allow(dead_code)]
+#[path = "../build/util.rs"]
+mod util;
@@ -0,0 +1,68 @@
+//! Builds a trivial `staticlib` depending on `audiopus_sys` with the
+//! `bundle-archive`-feature and asserts the resulting archive contains Opus,
+//! hence is self-contained.
+#![cfg(all(feature = "bundle-archive", unix))]
+
+use std::{env, fs, path::Path, process::Command};
+
+const MANIFEST: &str = r#"[package]
+name = "bundle_check"
+version = "0.0.0"
+edition = "2018"
+
+[lib]
+crate-type = ["staticlib"]
+
+[dependencies]
+audiopus_sys = { path = "{path}", features = ["bundle-archive"] }
+
+[workspace]
+"#;
+
+const SOURCE: &str = r#"
+#[no_mangle]
+pub extern "C" fn bundle_check_version() -> *const std::os::raw::c_char {
+    unsafe { audiopus_sys::opus_get_version_string() }
+}
+"#;
+
+#[test]
+fn staticlib_contains_opus() {
+    let crate_directory = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let check_directory = crate_directory.join("target").join("bundle_check");
+
+    fs::create_dir_all(check_directory.join("src")).expect("Could not create the crate.");
+    fs::write(
+        check_directory.join("Cargo.toml"),
+        MANIFEST.replace("{path}", &crate_directory.display().to_string()),
+    )
+    .expect("Could not write the manifest.");
+    fs::write(check_directory.join("src").join("lib.rs"), SOURCE)
+        .expect("Could not write the source.");
+
+    let status = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".into()))
+        .arg("build")
+        .current_dir(&check_directory)
+        .status()
+        .expect("Could not run Cargo.");
+
+    assert!(status.success(), "Building the staticlib failed.");
+
+    let archive = check_directory
+        .join("target")
+        .join("debug")
+        .join("libbundle_check.a");
+    let output = Command::new(env::var("NM").unwrap_or_else(|_| "nm".into()))
+        .arg(&archive)
+        .output()
+        .expect("Could not run `nm`.");
+    let symbols = String::from_utf8_lossy(&output.stdout);
+
+    assert!(
+        symbols
+            .lines()
+            .any(|line| line.ends_with(" T opus_encode")),
+        "`{}` does not define `opus_encode`.",
+        archive.display()
+    );
+}
@@ -0,0 +1,7 @@
+//! Ensures the opaque Opus states cannot be constructed outside the crate.
+
+#[test]
+fn opaque_types_cannot_be_constructed() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
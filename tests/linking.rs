@@ -0,0 +1,127 @@
+//! Linking Opus is solely decided by the build script, a `#[link]`-attribute
+//! in the bindings would add a second directive that overrides the
+//! `static`- and `dynamic`-feature as well as `LIBOPUS_STATIC`/`OPUS_STATIC`.
+//!
+//! The only exception is the `raw-dylib`-attribute of `opus_extern!` behind
+//! the opt-in feature of the same name on Windows: Cargo has no directive for
+//! the `raw-dylib`-kind, and the build script emits none of its own then.
+
+use std::{env, fs, path::Path, process::Command};
+
+fn rust_files(directory: &Path, files: &mut Vec<std::path::PathBuf>) {
+    for entry in fs::read_dir(directory).expect("Could not read the sources.") {
+        let path = entry.expect("Could not read the sources.").path();
+
+        if path.is_dir() {
+            rust_files(&path, files);
+        } else if path.extension().map_or(false, |extension| extension == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+#[test]
+fn bindings_have_no_link_attributes() {
+    let mut files = Vec::new();
+    rust_files(&Path::new(env!("CARGO_MANIFEST_DIR")).join("src"), &mut files);
+
+    assert!(!files.is_empty());
+
+    for file in files {
+        let source = fs::read_to_string(&file).expect("Could not read the source.");
+
+        assert!(
+            !source.contains("#[link("),
+            "`{}` contains a `#[link]`-attribute, link via the build script instead.",
+            file.display()
+        );
+    }
+}
+
+/// Builds the crate with the linking `feature`, `static` or `dynamic`, in a
+/// target directory of its own, returning the libraries the build script
+/// links and Cargo's output.
+fn build_with_feature(feature: &str) -> (Vec<String>, String) {
+    let crate_directory = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let target_directory = Path::new(env!("OUT_DIR")).join("linking").join(feature);
+    let cargo = env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+
+    let output = Command::new(cargo)
+        .arg("build")
+        .arg("--lib")
+        .arg("--manifest-path")
+        .arg(crate_directory.join("Cargo.toml"))
+        .arg("--features")
+        .arg(feature)
+        .arg("--target-dir")
+        .arg(&target_directory)
+        .output()
+        .expect("Could not run Cargo.");
+    let cargo_output = String::from_utf8_lossy(&output.stderr).into_owned();
+
+    assert!(
+        output.status.success(),
+        "Building with the `{}`-feature failed:\n{}",
+        feature,
+        cargo_output
+    );
+
+    let mut libraries = Vec::new();
+    let build_directory = target_directory.join("debug").join("build");
+
+    for entry in fs::read_dir(&build_directory).expect("Could not read the build directory.") {
+        let script_output = entry
+            .expect("Could not read the build directory.")
+            .path()
+            .join("output");
+
+        if !script_output.is_file() {
+            continue;
+        }
+
+        let script_output =
+            fs::read_to_string(&script_output).expect("Could not read the build script's output.");
+
+        for line in script_output.lines() {
+            if line.starts_with("cargo:rustc-link-lib=") {
+                let library = &line["cargo:rustc-link-lib=".len()..];
+
+                libraries.push(library.rsplit('=').next().unwrap_or(library).to_string());
+            }
+        }
+    }
+
+    (libraries, cargo_output)
+}
+
+#[test]
+fn linking_features_emit_no_duplicate_directives() {
+    for feature in &["static", "dynamic"] {
+        let (libraries, cargo_output) = build_with_feature(feature);
+
+        assert!(
+            !libraries.is_empty(),
+            "The `{}`-feature linked no library.",
+            feature
+        );
+
+        for (index, library) in libraries.iter().enumerate() {
+            assert!(
+                !libraries[index + 1..].contains(library),
+                "The `{}`-feature links `{}` more than once: {:?}",
+                feature,
+                library,
+                libraries
+            );
+        }
+
+        for line in cargo_output.lines() {
+            assert!(
+                !(line.starts_with("warning") && line.contains("link")),
+                "Building with the `{}`-feature warned about linking: {}",
+                feature,
+                line
+            );
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! Uses the bindings the way a dependent does, from outside the crate, hence
+//! fails to link if the build script's directives do not carry over to
+//! dependents, rather than only surfacing in downstream crates.
+#![cfg(all(feature = "encoder", feature = "decoder", not(feature = "dlopen")))]
+
+use audiopus_sys::*;
+use std::{ffi::CStr, ptr};
+
+const SAMPLE_RATE: opus_int32 = 48000;
+// 20 ms of mono.
+const FRAME_SIZE: usize = 960;
+const MAX_PACKET_SIZE: usize = 1500;
+
+#[test]
+fn version_string() {
+    let version = unsafe { CStr::from_ptr(opus_get_version_string()) };
+
+    assert!(version.to_string_lossy().starts_with("libopus"));
+}
+
+#[test]
+fn round_trip_frame() {
+    let input: Vec<opus_int16> = (0..FRAME_SIZE)
+        .map(|index| {
+            let time = index as f32 / SAMPLE_RATE as f32;
+
+            ((time * 440.0 * 2.0 * std::f32::consts::PI).sin() * 8192.0) as opus_int16
+        })
+        .collect();
+    let mut packet = [0; MAX_PACKET_SIZE];
+    let mut output = [0 as opus_int16; FRAME_SIZE];
+    let mut error = 0;
+
+    unsafe {
+        let encoder = opus_encoder_create(SAMPLE_RATE, 1, OPUS_APPLICATION_AUDIO, &mut error);
+        assert_eq!(error, OPUS_OK);
+        assert_ne!(encoder, ptr::null_mut());
+
+        let decoder = opus_decoder_create(SAMPLE_RATE, 1, &mut error);
+        assert_eq!(error, OPUS_OK);
+        assert_ne!(decoder, ptr::null_mut());
+
+        let len = opus_encode(
+            encoder,
+            input.as_ptr(),
+            FRAME_SIZE as i32,
+            packet.as_mut_ptr(),
+            MAX_PACKET_SIZE as opus_int32,
+        );
+        assert!(len > 0, "Encoding failed with {}.", len);
+        assert_eq!(opus_packet_get_nb_channels(packet.as_ptr()), 1);
+
+        let decoded = opus_decode(
+            decoder,
+            packet.as_ptr(),
+            len,
+            output.as_mut_ptr(),
+            FRAME_SIZE as i32,
+            0,
+        );
+        assert_eq!(decoded, FRAME_SIZE as i32);
+        assert!(output.iter().any(|&sample| sample != 0));
+
+        opus_encoder_destroy(encoder);
+        opus_decoder_destroy(decoder);
+    }
+}
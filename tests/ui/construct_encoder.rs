@@ -0,0 +1,8 @@
+use audiopus_sys::OpusEncoder;
+
+fn main() {
+    let _encoder = OpusEncoder {
+        _data: [],
+        _marker: std::marker::PhantomData,
+    };
+}